@@ -0,0 +1,34 @@
+use splot::{Chart, Edge, Plot};
+use std::fmt::Write;
+use std::time::Instant;
+
+/// Number of points per series
+const POINTS: usize = 1_000_000;
+
+fn main() {
+    let data: Vec<(f64, f64)> = (0..POINTS)
+        .map(|i| {
+            let x = i as f64;
+            (x, (x / 1000.0).sin() * 100.0 + (x / 37.0).cos())
+        })
+        .collect();
+    for (name, plot) in [
+        ("area", Plot::area("Area", &data[..])),
+        ("line", Plot::line("Line", &data[..])),
+        ("scatter", Plot::scatter("Scatter", &data[..])),
+    ] {
+        let chart = Chart::new()
+            .domain(&data[..])
+            .axis("X", Edge::Bottom)
+            .axis("Y", Edge::Left)
+            .plot(plot);
+        let mut out = String::new();
+        let start = Instant::now();
+        write!(out, "{chart}").unwrap();
+        let elapsed = start.elapsed();
+        println!(
+            "{name:>8}: {POINTS} points, {} bytes, {elapsed:?}",
+            out.len()
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::{Days, NaiveDate};
+use splot::{Chart, Domain, Edge, Page, Plot};
+
+fn main() {
+    let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let data: Vec<(NaiveDate, f32)> = (0..31)
+        .map(|d| {
+            let date = start + Days::new(d);
+            (date, 12.0 + 6.0 * (d as f32 / 5.0).sin())
+        })
+        .collect();
+    let page = Page::new().chart(
+        Chart::new()
+            .title("Daily Values")
+            .domain(Domain::from_time_data(&data))
+            .axis("Date", Edge::Bottom)
+            .axis("Value", Edge::Left)
+            .plot(Plot::line("Value", &data).markers()),
+    );
+    print!("{page}");
+}
@@ -0,0 +1,30 @@
+use splot::{Chart, Direction, Edge, Layout, Page, Plot};
+
+fn main() -> std::io::Result<()> {
+    let temp = vec![(0, 18.5), (6, 16.0), (12, 24.5), (18, 21.0), (24, 17.5)];
+    let load = vec![(0, 12), (6, 8), (12, 64), (18, 47), (24, 15)];
+    let page = Page::new()
+        .layout(Layout::Flex {
+            direction: Direction::Row,
+            wrap: true,
+        })
+        .gap(16)
+        .chart(
+            Chart::new()
+                .title("Temperature")
+                .domain(&temp[..])
+                .axis("Hour", Edge::Bottom)
+                .axis("°C", Edge::Left)
+                .plot(Plot::line("Temperature", &temp).markers()),
+        )
+        .chart_captioned(
+            Chart::new()
+                .title("Load")
+                .domain(&load[..])
+                .axis("Hour", Edge::Bottom)
+                .axis("%", Edge::Left)
+                .plot(Plot::area("Load", &load)),
+            "Server load by hour",
+        );
+    page.write_to(std::io::stdout().lock())
+}
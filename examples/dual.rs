@@ -0,0 +1,44 @@
+use splot::{Chart, Domain, Edge, Page, Plot};
+
+fn main() {
+    let temperature = vec![
+        (1, -4),
+        (2, -2),
+        (3, 4),
+        (4, 11),
+        (5, 17),
+        (6, 22),
+        (7, 25),
+        (8, 24),
+        (9, 19),
+        (10, 12),
+        (11, 4),
+        (12, -2),
+    ];
+    let rainfall = vec![
+        (1, 22),
+        (2, 20),
+        (3, 42),
+        (4, 68),
+        (5, 91),
+        (6, 109),
+        (7, 102),
+        (8, 110),
+        (9, 78),
+        (10, 62),
+        (11, 45),
+        (12, 28),
+    ];
+    let rain = Domain::from(&rainfall[..]).include_zero_y();
+    let page = Page::new().chart(
+        Chart::new()
+            .title("Climate")
+            .domain(&temperature[..])
+            .axis("Month", Edge::Bottom)
+            .axis("Temperature (°C)", Edge::Left)
+            .axis_for(rain.clone(), "Rainfall (mm)", Edge::Right)
+            .plot(Plot::line("Temperature", &temperature))
+            .plot_in(rain, Plot::line("Rainfall", &rainfall).dashed()),
+    );
+    print!("{page}");
+}
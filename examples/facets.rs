@@ -0,0 +1,24 @@
+use splot::{Domain, Facets, Plot};
+
+fn main() {
+    let regions = ["North", "South", "East", "West", "Central", "Coast"];
+    let data: Vec<Vec<(f32, f32)>> = (0..regions.len())
+        .map(|r| {
+            (0..12)
+                .map(|m| {
+                    let m = m as f32;
+                    (m + 1.0, 20.0 + 8.0 * ((m + r as f32) / 2.0).sin())
+                })
+                .collect()
+        })
+        .collect();
+    let mut domain = Domain::from(&data[0][..]);
+    for d in &data[1..] {
+        domain = domain.including(d);
+    }
+    let mut facets = Facets::new(domain, 2, 3).x_axis("Month").y_axis("Sales");
+    for (region, d) in regions.iter().zip(&data) {
+        facets = facets.cell(*region, vec![Plot::line(*region, d)]);
+    }
+    print!("{facets}");
+}
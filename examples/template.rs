@@ -0,0 +1,29 @@
+use splot::{Chart, Edge, Plot};
+
+fn main() {
+    let data = vec![(13, 74), (111, 37), (125, 52), (190, 66)];
+    let chart = || {
+        Chart::new()
+            .title("Inline Chart")
+            .domain(&data[..])
+            .axis("X Axis", Edge::Bottom)
+            .axis("Y Axis", Edge::Left)
+            .plot(Plot::line("Series", &data).markers())
+    };
+    // a stand-alone SVG document, suitable for an `.svg` file
+    let svg = chart().to_string();
+    eprintln!("stand-alone: {} bytes", svg.len());
+    // an SVG element to place within a server-rendered template
+    let svg = chart().stand_alone(false);
+    println!("<html>");
+    println!("<head>");
+    println!("<link href='./css/splot.css' rel='stylesheet'/>");
+    println!("</head>");
+    println!("<body>");
+    println!("<h1>Report</h1>");
+    println!("<div class='chart'>");
+    println!("{svg}");
+    println!("</div>");
+    println!("</body>");
+    println!("</html>");
+}
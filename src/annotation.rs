@@ -0,0 +1,194 @@
+// annotation.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+use crate::chart::Layer;
+use crate::domain::BoundDomain;
+use crate::rect::Edge;
+use crate::style::inline_attrs;
+use crate::text::{escape, Anchor, Text, Tspan};
+use std::borrow::Cow;
+use std::fmt;
+
+/// Vertical offset of the last line above its point, in ems
+const BASE_DY: f32 = -0.67;
+
+/// Spacing of stacked annotations at one point, in ems
+const LINE_DY: f32 = 1.2;
+
+/// Text note at data coordinates
+///
+/// ```rust
+/// use splot::Annotation;
+///
+/// let note = Annotation::new(12.0, 40.0, "v2.3 released")
+///     .at_start()
+///     .offset(40, -80)
+///     .leader();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation<'a> {
+    x: f64,
+    y: f64,
+    text: Cow<'a, str>,
+    anchor: Anchor,
+    offset: (i32, i32),
+    leader: bool,
+    layer: Layer,
+}
+
+impl<'a> Annotation<'a> {
+    /// Create a new annotation at a point
+    ///
+    /// The text is centered above the point.
+    pub fn new<T>(x: f64, y: f64, text: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        Annotation {
+            x,
+            y,
+            text: text.into(),
+            anchor: Anchor::Middle,
+            offset: (0, 0),
+            leader: false,
+            layer: Layer::AbovePlots,
+        }
+    }
+
+    /// Anchor text at start, so it extends to the right
+    pub fn at_start(mut self) -> Self {
+        self.anchor = Anchor::Start;
+        self
+    }
+
+    /// Anchor text at end, so it extends to the left
+    pub fn at_end(mut self) -> Self {
+        self.anchor = Anchor::End;
+        self
+    }
+
+    /// Offset text from the point, in viewBox units
+    pub fn offset(mut self, dx: i32, dy: i32) -> Self {
+        self.offset = (dx, dy);
+        self
+    }
+
+    /// Draw a leader line from the point to the offset text
+    pub fn leader(mut self) -> Self {
+        self.leader = true;
+        self
+    }
+
+    /// Set the layer, which is above plots by default
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Check if another annotation is at the same point
+    fn same_point(&self, rhs: &Self) -> bool {
+        self.x == rhs.x && self.y == rhs.y && self.offset == rhs.offset
+    }
+
+    /// Render the annotation
+    ///
+    /// It is line `n` of `count` annotations stacked at the same point.
+    pub(crate) fn render(
+        &self,
+        f: &mut fmt::Formatter,
+        domain: &BoundDomain,
+        n: usize,
+        count: usize,
+        inline: bool,
+    ) -> fmt::Result {
+        let px = domain.x_map(self.x);
+        let py = domain.y_map(self.y);
+        let (x, y) = (px + self.offset.0, py + self.offset.1);
+        if self.leader && n == 0 && (x, y) != (px, py) {
+            write!(f, "<path class='annotation-leader'")?;
+            if inline {
+                write!(f, "{}", inline_attrs("annotation-leader"))?;
+            }
+            writeln!(f, " d='M{px} {py}L{x} {y}'/>")?;
+        }
+        let lines = (count - 1 - n) as f32;
+        let dy = ((BASE_DY - LINE_DY * lines) * 100.0).round() / 100.0;
+        let text = Text::new(Edge::Top)
+            .anchor(self.anchor)
+            .class_name("annotation")
+            .inline(inline);
+        text.display(f)?;
+        let content = escape(&self.text);
+        write!(f, "{}", Tspan::new(content.as_ref()).x(x).y(y).dy(dy))?;
+        text.display_done(f)
+    }
+}
+
+/// Render annotations on one layer, stacking those at the same point
+pub(crate) fn render_annotations(
+    f: &mut fmt::Formatter,
+    annotations: &[Annotation],
+    domain: &BoundDomain,
+    layer: Layer,
+    inline: bool,
+) -> fmt::Result {
+    for (i, annotation) in annotations.iter().enumerate() {
+        if annotation.layer != layer {
+            continue;
+        }
+        let n = annotations[..i]
+            .iter()
+            .filter(|a| a.same_point(annotation))
+            .count();
+        let count = annotations
+            .iter()
+            .filter(|a| a.same_point(annotation))
+            .count();
+        annotation.render(f, domain, n, count, inline)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Annotation, Chart, Edge, Plot};
+
+    #[test]
+    fn stacked() {
+        let data = [(0.0, 0.0), (10.0, 10.0)];
+        let svg = Chart::new()
+            .domain(&data[..])
+            .axis("X", Edge::Bottom)
+            .plot(Plot::line("A", &data[..]))
+            .annotate(5.0, 5.0, "outage")
+            .annotate(5.0, 5.0, "v2.3 <beta>")
+            .annotation(Annotation::new(10.0, 10.0, "end").at_end())
+            .to_string();
+        let clip = svg.find("</g>").unwrap();
+        let outage = svg.find(">outage</tspan>").unwrap();
+        assert!(clip < outage);
+        assert!(svg.contains("dy='-1.87em'>outage</tspan>"));
+        assert!(svg.contains("dy='-0.67em'>v2.3 &lt;beta&gt;</tspan>"));
+        assert!(svg.contains("text-anchor='end'"));
+        assert!(svg.contains("dy='-0.67em'>end</tspan>"));
+        assert!(!svg.contains("annotation-leader"));
+    }
+
+    #[test]
+    fn leader() {
+        let data = [(0.0, 0.0), (10.0, 10.0)];
+        let svg = Chart::new()
+            .margin(0)
+            .aspect_ratio(crate::AspectRatio::Custom(100, 100))
+            .domain(&data[..])
+            .annotation(
+                Annotation::new(5.0, 5.0, "note").offset(20, -30).leader(),
+            )
+            .to_string();
+        assert!(
+            svg.contains("<path class='annotation-leader' d='M50 50L70 20'/>")
+        );
+        assert!(svg.contains("<tspan x='70' y='20'"));
+    }
+}
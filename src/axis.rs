@@ -4,59 +4,555 @@
 //
 //! Axis for charts
 //!
+use crate::path::{cmd_fmt, point_dec_fmt};
 use crate::rect::{Edge, Rect};
-use crate::text::{Anchor, Label, Text, Tick};
+use crate::style::inline_attrs;
+use crate::text::{escape, Anchor, Label, Text, Tick, Tspan};
+use std::borrow::Cow;
 use std::fmt;
 
+/// Space reserved for axis name
+pub(crate) const NAME_SPACE: u16 = 80;
+
+/// Space reserved below horizontal tick label offsets
+const TICK_LABEL_SPACE: u16 = 40;
+
+/// Offset from label gap to middle of horizontal tick labels
+const TICK_LABEL_HALF: u16 = 12;
+
+/// Approximate width of one tick label character
+const CHAR_WIDTH: f32 = 20.0;
+
+/// Approximate height of tick label text
+const CHAR_HEIGHT: f32 = 32.0;
+
+/// Tick mark and label spacing
+///
+/// ```rust
+/// use splot::{AxisOpts, Edge, TickStyle};
+///
+/// let style = TickStyle {
+///     len: 8,
+///     ..TickStyle::default()
+/// };
+/// let opts = AxisOpts::new("X Axis", Edge::Bottom).tick_style(style);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TickStyle {
+    /// Length of tick marks
+    pub len: u16,
+    /// Gap between tick marks and labels
+    pub label_gap: u16,
+    /// Vertical offset of tick label text, in `em` units
+    pub label_dy: f32,
+}
+
+/// Placement of axis ticks
+#[derive(Clone, Debug, Default, PartialEq)]
+enum TickPlacement {
+    /// Uniform spacing from the scale
+    #[default]
+    Scale,
+    /// Explicit values, with optional label text
+    At(Vec<(f64, Option<String>)>),
+    /// Distinct values from the first plot
+    FromData,
+}
+
+/// Axis options
+///
+/// ```rust
+/// use splot::{AxisOpts, Edge};
+///
+/// let opts = AxisOpts::new("Y Axis", Edge::Left).no_grid();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AxisOpts<'a> {
+    name: Cow<'a, str>,
+    edge: Edge,
+    grid: bool,
+    zero_line: bool,
+    axis_line: bool,
+    tick_lines: bool,
+    tick_labels: bool,
+    tick_rotation: f32,
+    char_width: f32,
+    space: Option<u16>,
+    tick_style: TickStyle,
+    tick_placement: TickPlacement,
+    name_anchor: Anchor,
+    name_horizontal: bool,
+}
+
 /// Axis for drawing labels on a `Chart`
 #[derive(Debug, PartialEq)]
 pub struct Axis<'a> {
-    edge: Edge,
+    opts: AxisOpts<'a>,
     ticks: Vec<Tick>,
-    name: &'a str,
+    zero: Option<Tick>,
+    offset_text: Option<String>,
     label: Label,
+    inline: bool,
+    precision: u8,
     rect: Rect,
+    name_rect: Rect,
+}
+
+impl Default for TickStyle {
+    fn default() -> Self {
+        TickStyle {
+            len: 20,
+            label_gap: 8,
+            label_dy: 0.33,
+        }
+    }
+}
+
+impl TickStyle {
+    /// Get tick mark length
+    pub(crate) fn len(&self) -> i32 {
+        i32::from(self.len)
+    }
+
+    /// Get offset of tick labels beside a vertical axis
+    pub(crate) fn hlen(&self) -> i32 {
+        i32::from(self.len) + i32::from(self.label_gap)
+    }
+
+    /// Get offset of tick labels beside a horizontal axis
+    pub(crate) fn vlen(&self) -> i32 {
+        self.hlen() + i32::from(TICK_LABEL_HALF)
+    }
+
+    /// Get space reserved for horizontal tick marks and labels
+    fn horizontal_space(&self) -> u16 {
+        self.vlen() as u16 + TICK_LABEL_SPACE
+    }
+}
+
+impl<'a> AxisOpts<'a> {
+    /// Create new axis options
+    pub fn new<N>(name: N, edge: Edge) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        AxisOpts {
+            name: name.into(),
+            edge,
+            grid: true,
+            zero_line: false,
+            axis_line: true,
+            tick_lines: true,
+            tick_labels: true,
+            tick_rotation: 0.0,
+            char_width: CHAR_WIDTH,
+            space: None,
+            tick_style: TickStyle::default(),
+            tick_placement: TickPlacement::Scale,
+            name_anchor: Anchor::Middle,
+            name_horizontal: false,
+        }
+    }
+
+    /// Get the axis name
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the axis edge
+    pub(crate) fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    /// Check if grid lines are enabled
+    pub(crate) fn has_grid(&self) -> bool {
+        self.grid
+    }
+
+    /// Disable grid lines
+    pub fn no_grid(mut self) -> Self {
+        self.grid = false;
+        self
+    }
+
+    /// Check if zero line is enabled
+    pub(crate) fn has_zero_line(&self) -> bool {
+        self.zero_line
+    }
+
+    /// Emphasize the grid line at zero, if within the domain
+    pub fn highlight_zero(mut self) -> Self {
+        self.zero_line = true;
+        self
+    }
+
+    /// Render only grid lines, with no tick marks, labels or name
+    ///
+    /// No space is reserved for the axis.
+    pub fn grid_only(mut self) -> Self {
+        self.name = Cow::Borrowed("");
+        self.grid = true;
+        self.axis_line = false;
+        self.tick_lines = false;
+        self.tick_labels = false;
+        self
+    }
+
+    /// Disable the axis line
+    pub fn no_line(mut self) -> Self {
+        self.axis_line = false;
+        self
+    }
+
+    /// Disable tick marks
+    pub fn no_ticks(mut self) -> Self {
+        self.tick_lines = false;
+        self
+    }
+
+    /// Hide tick labels, while keeping tick marks and grid lines
+    pub fn no_tick_labels(mut self) -> Self {
+        self.tick_labels = false;
+        self
+    }
+
+    /// Anchor the axis name at the start, middle or end of the axis
+    ///
+    /// The start of a vertical axis is at the bottom.  The default is
+    /// `Anchor::Middle`.
+    pub fn name_anchor(mut self, anchor: Anchor) -> Self {
+        self.name_anchor = anchor;
+        self
+    }
+
+    /// Draw the name of a vertical axis horizontally, above the axis
+    ///
+    /// Space for the name is taken from the top of the chart area, instead
+    /// of beside the tick labels.  The anchor is relative to the tick label
+    /// column.  This has no effect on horizontal axes.
+    pub fn name_horizontal(mut self) -> Self {
+        self.name_horizontal = true;
+        self
+    }
+
+    /// Check if the name is drawn horizontally above a vertical axis
+    fn has_name_above(&self) -> bool {
+        self.name_horizontal
+            && !self.edge.is_horizontal()
+            && !self.name.is_empty()
+    }
+
+    /// Place ticks at explicit values, instead of uniform spacing
+    ///
+    /// Values outside of the domain are dropped.
+    pub fn ticks_at(mut self, values: &[f64]) -> Self {
+        let ticks = values.iter().map(|v| (*v, None)).collect();
+        self.tick_placement = TickPlacement::At(ticks);
+        self
+    }
+
+    /// Place ticks at explicit values, with label text
+    ///
+    /// Values outside of the domain are dropped.
+    pub fn ticks_labeled(mut self, ticks: &[(f64, &str)]) -> Self {
+        let ticks = ticks
+            .iter()
+            .map(|(v, text)| (*v, Some(text.to_string())))
+            .collect();
+        self.tick_placement = TickPlacement::At(ticks);
+        self
+    }
+
+    /// Place one tick at each distinct value in the chart's first plot
+    ///
+    /// Horizontal axes use `X` values, and vertical axes use `Y` values.
+    pub fn ticks_from_data(mut self) -> Self {
+        self.tick_placement = TickPlacement::FromData;
+        self
+    }
+
+    /// Check if ticks are placed from plot data
+    pub(crate) fn has_ticks_from_data(&self) -> bool {
+        self.tick_placement == TickPlacement::FromData
+    }
+
+    /// Get explicit tick values and label text
+    pub(crate) fn explicit_ticks(&self) -> Option<&[(f64, Option<String>)]> {
+        match &self.tick_placement {
+            TickPlacement::At(ticks) => Some(ticks),
+            _ => None,
+        }
+    }
+
+    /// Rotate tick labels on a horizontal axis
+    ///
+    /// Negative angles rotate counter-clockwise, so labels hang below a
+    /// bottom axis at -45 or -90 degrees.
+    pub fn tick_rotation(mut self, degrees: f32) -> Self {
+        self.tick_rotation = degrees;
+        self
+    }
+
+    /// Set the estimated width of one tick label character
+    ///
+    /// This is used to reserve axis space and to thin out overlapping
+    /// labels; adjust it for fonts much wider or narrower than the default.
+    pub fn char_width(mut self, width: f32) -> Self {
+        self.char_width = width;
+        self
+    }
+
+    /// Set the total space reserved for the axis, including its name
+    ///
+    /// By default, space is estimated from the longest tick label.
+    pub fn space(mut self, space: u16) -> Self {
+        self.space = Some(space);
+        self
+    }
+
+    /// Set tick mark length and label offsets
+    pub fn tick_style(mut self, style: TickStyle) -> Self {
+        self.tick_style = style;
+        self
+    }
+
+    /// Check if tick labels are rotated
+    fn is_rotated(&self) -> bool {
+        self.tick_rotation != 0.0 && self.edge.is_horizontal()
+    }
 }
 
 impl<'a> Axis<'a> {
     /// Create a new axis
-    pub fn new(name: &'a str, edge: Edge, ticks: Vec<Tick>) -> Self {
+    pub fn new(opts: AxisOpts<'a>, ticks: Vec<Tick>) -> Self {
         Self {
-            edge,
+            opts,
             ticks,
-            name,
+            zero: None,
+            offset_text: None,
             label: Label::new(),
+            inline: false,
+            precision: 0,
             rect: Rect::default(),
+            name_rect: Rect::default(),
         }
     }
 
+    /// Set normalized position of zero line
+    pub fn zero_line(mut self, zero: Option<f64>) -> Self {
+        self.zero = zero.map(|z| Tick::new(z, ""));
+        self
+    }
+
+    /// Set offset text, shown once at the end of the axis
+    ///
+    /// This is the exponent shared by tick labels in scientific notation.
+    pub(crate) fn offset_text(mut self, text: Option<String>) -> Self {
+        self.offset_text = text;
+        self
+    }
+
+    /// Use inline presentational attributes
+    pub(crate) fn set_inline(&mut self) {
+        self.inline = true;
+    }
+
+    /// Set decimal places of grid and tick line positions
+    pub(crate) fn set_precision(&mut self, decimals: u8) {
+        self.precision = decimals;
+    }
+
+    /// Format class attribute, with inline attributes if enabled
+    fn class_fmt(&self, f: &mut fmt::Formatter, class: &str) -> fmt::Result {
+        write!(f, " class='{class}'")?;
+        if self.inline {
+            write!(f, "{}", inline_attrs(class))?;
+        }
+        Ok(())
+    }
+
+    /// Get axis options
+    pub fn opts(&self) -> &AxisOpts<'a> {
+        &self.opts
+    }
+
     /// Split axis area from rectangle
     pub fn split(&mut self, mut area: Rect) -> Rect {
-        (area, self.rect) = area.split(self.edge, self.space());
+        if self.opts.has_name_above() {
+            let name;
+            (area, name) = area.split(Edge::Top, NAME_SPACE);
+            (area, self.rect) = area.split(self.opts.edge, self.space());
+            self.name_rect =
+                Rect::new(self.rect.x, name.y, self.rect.width, name.height);
+            return area;
+        }
+        (area, self.rect) = area.split(self.opts.edge, self.space());
         area
     }
 
     /// Get the space required
     fn space(&self) -> u16 {
-        if self.name.is_empty() {
-            80
+        if let Some(space) = self.opts.space {
+            return space;
+        }
+        let mut space = 0;
+        if self.opts.tick_labels {
+            space += self.tick_label_space();
+        } else if self.opts.tick_lines {
+            space += self.opts.tick_style.len;
+        }
+        if !self.opts.name.is_empty() && !self.opts.has_name_above() {
+            space += NAME_SPACE;
+        }
+        space
+    }
+
+    /// Get the estimated width of the longest tick label line
+    fn tick_label_width(&self) -> f32 {
+        let chars = self
+            .ticks
+            .iter()
+            .map(Self::tick_label_chars)
+            .max()
+            .unwrap_or(0);
+        chars as f32 * self.opts.char_width
+    }
+
+    /// Get the number of characters in the longest line of a tick label
+    fn tick_label_chars(tick: &Tick) -> usize {
+        tick.lines().map(|l| l.chars().count()).max().unwrap_or(0)
+    }
+
+    /// Get the largest number of lines in a tick label
+    fn tick_label_lines(&self) -> usize {
+        self.ticks.iter().map(Tick::line_count).max().unwrap_or(1)
+    }
+
+    /// Get the estimated extent of a tick label along the axis
+    fn tick_label_extent(&self, tick: &Tick) -> f64 {
+        if self.opts.edge.is_horizontal() {
+            let chars = Self::tick_label_chars(tick);
+            f64::from(chars as f32 * self.opts.char_width)
         } else {
-            160
+            f64::from(CHAR_HEIGHT * tick.line_count() as f32)
         }
     }
 
-    /// Render the axis
+    /// Select which tick labels are shown
+    ///
+    /// While adjacent labels would overlap, every other label is dropped,
+    /// always keeping the first and last.
+    fn visible_tick_labels(&self, rect: Rect) -> Vec<bool> {
+        let edge = self.opts.edge;
+        let style = &self.opts.tick_style;
+        let pos = |tick: &Tick| {
+            if edge.is_horizontal() {
+                tick.x_pos(edge, rect, style.hlen())
+            } else {
+                tick.y_pos(edge, rect, style.vlen())
+            }
+        };
+        let mut shown: Vec<usize> = (0..self.ticks.len())
+            .filter(|i| !self.ticks[*i].text().is_empty())
+            .collect();
+        while shown.len() > 2 {
+            let overlap = shown.windows(2).any(|w| {
+                let (a, b) = (&self.ticks[w[0]], &self.ticks[w[1]]);
+                let gap = (pos(b) - pos(a)).abs();
+                gap < (self.tick_label_extent(a) + self.tick_label_extent(b))
+                    / 2.0
+            });
+            if !overlap {
+                break;
+            }
+            let last = shown[shown.len() - 1];
+            let mut thinned: Vec<usize> =
+                shown.iter().copied().step_by(2).collect();
+            if thinned.last() != Some(&last) {
+                // replace the final kept label to avoid crowding the last
+                if thinned.len() > 1 {
+                    thinned.pop();
+                }
+                thinned.push(last);
+            }
+            shown = thinned;
+        }
+        let mut visible = vec![false; self.ticks.len()];
+        for i in shown {
+            visible[i] = true;
+        }
+        visible
+    }
+
+    /// Get the space required for tick marks and labels
+    fn tick_label_space(&self) -> u16 {
+        let width = self.tick_label_width();
+        let style = &self.opts.tick_style;
+        let tick_space = style.horizontal_space();
+        if self.opts.is_rotated() {
+            let angle = self.opts.tick_rotation.to_radians();
+            let width = width * angle.sin().abs();
+            let lines = self.tick_label_lines() as f32;
+            let height = CHAR_HEIGHT * lines * angle.cos().abs();
+            let space = style.hlen() as f32 + width + height;
+            tick_space.max(space.ceil() as u16)
+        } else if self.opts.edge.is_horizontal() {
+            let mut extra = self.tick_label_lines().saturating_sub(1);
+            if self.offset_text.is_some() {
+                extra = extra.max(1);
+            }
+            tick_space + CHAR_HEIGHT as u16 * extra as u16
+        } else {
+            let space = style.hlen() as f32 + width;
+            style.len.max(space.ceil() as u16)
+        }
+    }
+
+    /// Render the axis line, tick marks and labels
     pub fn render(&self, f: &mut fmt::Formatter, area: Rect) -> fmt::Result {
-        match self.edge {
+        match self.opts.edge {
+            Edge::Bottom | Edge::Top => self.render_horizontal(f, area),
+            Edge::Left | Edge::Right => self.render_vertical(f, area),
+        }
+    }
+
+    /// Render grid lines and zero line across the plot area
+    pub fn render_grid(
+        &self,
+        f: &mut fmt::Formatter,
+        area: Rect,
+    ) -> fmt::Result {
+        match self.opts.edge {
             Edge::Bottom | Edge::Top => {
-                self.render_grid_horizontal(f, area)?;
-                self.render_horizontal(f, area)
+                if self.opts.grid {
+                    self.render_grid_horizontal(f, area)?;
+                }
+                if let Some(zero) = &self.zero {
+                    let x = zero.x_pos(self.opts.edge, area, 0);
+                    write!(f, "<path")?;
+                    self.class_fmt(f, "grid-zero")?;
+                    write!(f, " d='")?;
+                    point_dec_fmt(f, b'M', x, area.y.into(), self.precision)?;
+                    cmd_fmt(f, b'v', area.height.into())?;
+                    writeln!(f, "'/>")?;
+                }
             }
             Edge::Left | Edge::Right => {
-                self.render_grid_vertical(f, area)?;
-                self.render_vertical(f, area)
+                if self.opts.grid {
+                    self.render_grid_vertical(f, area)?;
+                }
+                if let Some(zero) = &self.zero {
+                    let y = zero.y_pos(self.opts.edge, area, 0);
+                    write!(f, "<path")?;
+                    self.class_fmt(f, "grid-zero")?;
+                    write!(f, " d='")?;
+                    point_dec_fmt(f, b'M', area.x.into(), y, self.precision)?;
+                    cmd_fmt(f, b'h', area.width.into())?;
+                    writeln!(f, "'/>")?;
+                }
             }
         }
+        Ok(())
     }
 
     /// Render horizontal grid lines
@@ -65,10 +561,13 @@ impl<'a> Axis<'a> {
         f: &mut fmt::Formatter,
         area: Rect,
     ) -> fmt::Result {
-        write!(f, "<path class='grid-x' d='")?;
+        write!(f, "<path")?;
+        self.class_fmt(f, "grid-x")?;
+        write!(f, " d='")?;
         for tick in self.ticks.iter() {
-            let x = tick.x(self.edge, area, 0);
-            write!(f, "M{x} {}v{}", area.y, area.height)?;
+            let x = tick.x_pos(self.opts.edge, area, 0);
+            point_dec_fmt(f, b'M', x, area.y.into(), self.precision)?;
+            cmd_fmt(f, b'v', area.height.into())?;
         }
         writeln!(f, "'/>")
     }
@@ -81,16 +580,25 @@ impl<'a> Axis<'a> {
     ) -> fmt::Result {
         let mut rect = self.rect;
         rect.intersect_horiz(&area);
-        if !self.name.is_empty() {
+        if !self.opts.name.is_empty() {
             let r;
-            (rect, r) = rect.split(self.edge, self.space() / 2);
-            let text = Text::new(self.edge).rect(r).class_name("axis");
+            (rect, r) = rect.split(self.opts.edge, NAME_SPACE);
+            let text = Text::new(self.opts.edge)
+                .rect(r)
+                .anchor(self.opts.name_anchor)
+                .class_name("axis")
+                .inline(self.inline);
             text.display(f)?;
-            writeln!(f, "{}", &self.name)?;
+            writeln!(f, "{}", escape(&self.opts.name))?;
             text.display_done(f)?;
         }
-        self.render_tick_lines(f, rect)?;
-        self.render_tick_labels(f, rect)
+        if self.opts.axis_line || self.opts.tick_lines {
+            self.render_tick_lines(f, rect)?;
+        }
+        if self.opts.tick_labels {
+            self.render_tick_labels(f, rect)?;
+        }
+        Ok(())
     }
 
     /// Render vertical grid lines
@@ -99,10 +607,13 @@ impl<'a> Axis<'a> {
         f: &mut fmt::Formatter,
         area: Rect,
     ) -> fmt::Result {
-        write!(f, "<path class='grid-y' d='")?;
+        write!(f, "<path")?;
+        self.class_fmt(f, "grid-y")?;
+        write!(f, " d='")?;
         for tick in self.ticks.iter() {
-            let y = tick.y(self.edge, area, 0);
-            write!(f, "M{} {y}h{}", area.x, area.width)?;
+            let y = tick.y_pos(self.opts.edge, area, 0);
+            point_dec_fmt(f, b'M', area.x.into(), y, self.precision)?;
+            cmd_fmt(f, b'h', area.width.into())?;
         }
         writeln!(f, "'/>")
     }
@@ -115,16 +626,34 @@ impl<'a> Axis<'a> {
     ) -> fmt::Result {
         let mut rect = self.rect;
         rect.intersect_vert(&area);
-        if !&self.name.is_empty() {
+        if self.opts.has_name_above() {
+            let text = Text::new(Edge::Top)
+                .rect(self.name_rect)
+                .anchor(self.opts.name_anchor)
+                .class_name("axis")
+                .inline(self.inline);
+            text.display(f)?;
+            writeln!(f, "{}", escape(&self.opts.name))?;
+            text.display_done(f)?;
+        } else if !&self.opts.name.is_empty() {
             let r;
-            (rect, r) = rect.split(self.edge, self.space() / 2);
-            let text = Text::new(self.edge).rect(r).class_name("axis");
+            (rect, r) = rect.split(self.opts.edge, NAME_SPACE);
+            let text = Text::new(self.opts.edge)
+                .rect(r)
+                .anchor(self.opts.name_anchor)
+                .class_name("axis")
+                .inline(self.inline);
             text.display(f)?;
-            writeln!(f, "{}", &self.name)?;
+            writeln!(f, "{}", escape(&self.opts.name))?;
             text.display_done(f)?;
         }
-        self.render_tick_lines(f, rect)?;
-        self.render_tick_labels(f, rect)
+        if self.opts.axis_line || self.opts.tick_lines {
+            self.render_tick_lines(f, rect)?;
+        }
+        if self.opts.tick_labels {
+            self.render_tick_labels(f, rect)?;
+        }
+        Ok(())
     }
 
     /// Render tick lines
@@ -133,7 +662,7 @@ impl<'a> Axis<'a> {
         f: &mut fmt::Formatter,
         rect: Rect,
     ) -> fmt::Result {
-        match self.edge {
+        match self.opts.edge {
             Edge::Bottom | Edge::Top => {
                 self.render_tick_lines_horizontal(f, rect)
             }
@@ -150,20 +679,32 @@ impl<'a> Axis<'a> {
         rect: Rect,
     ) -> fmt::Result {
         let x = rect.x;
-        let (y, height) = match self.edge {
-            Edge::Top => (rect.bottom(), Tick::LEN),
-            Edge::Bottom => (rect.y, -Tick::LEN),
+        let len = self.opts.tick_style.len();
+        let (y, height) = match self.opts.edge {
+            Edge::Top => (rect.bottom(), len),
+            Edge::Bottom => (rect.y, -len),
             _ => unreachable!(),
         };
-        write!(f, "<path class='axis-line' d='M{x} {y}h{}", rect.width)?;
-        for tick in self.ticks.iter() {
-            let x = tick.x(self.edge, rect, Tick::LEN);
-            let y = tick.y(self.edge, rect, Tick::LEN);
-            let y0 = y.min(y + height);
-            let h = y.max(y + height) - y0;
-            write!(f, "M{x} {y0}v{h}")?;
+        if self.opts.axis_line {
+            write!(f, "<path")?;
+            self.class_fmt(f, "axis-line")?;
+            writeln!(f, " d='M{x} {y}h{}'/>", rect.width)?;
         }
-        writeln!(f, "'/>")
+        if self.opts.tick_lines {
+            write!(f, "<path")?;
+            self.class_fmt(f, "axis-tick")?;
+            write!(f, " d='")?;
+            for tick in self.ticks.iter() {
+                let x = tick.x_pos(self.opts.edge, rect, len);
+                let y = tick.y(self.opts.edge, rect, len);
+                let y0 = y.min(y + height);
+                let h = y.max(y + height) - y0;
+                point_dec_fmt(f, b'M', x, y0.into(), self.precision)?;
+                cmd_fmt(f, b'v', h)?;
+            }
+            writeln!(f, "'/>")?;
+        }
+        Ok(())
     }
 
     /// Render vertical tick lines
@@ -172,21 +713,32 @@ impl<'a> Axis<'a> {
         f: &mut fmt::Formatter,
         rect: Rect,
     ) -> fmt::Result {
-        let (x, width) = match self.edge {
-            Edge::Left => (rect.right(), Tick::LEN),
-            Edge::Right => (rect.x, -Tick::LEN),
+        let len = self.opts.tick_style.len();
+        let (x, width) = match self.opts.edge {
+            Edge::Left => (rect.right(), len),
+            Edge::Right => (rect.x, -len),
             _ => unreachable!(),
         };
-        write!(f, "<path class='axis-line'")?;
-        write!(f, " d='M{x} {}v{}", rect.y, rect.height)?;
-        for tick in self.ticks.iter() {
-            let x = tick.x(self.edge, rect, Tick::LEN);
-            let y = tick.y(self.edge, rect, Tick::LEN);
-            let x0 = x.min(x + width);
-            let w = x.max(x + width) - x0;
-            write!(f, " M{x0} {y}h{w}")?;
+        if self.opts.axis_line {
+            write!(f, "<path")?;
+            self.class_fmt(f, "axis-line")?;
+            writeln!(f, " d='M{x} {}v{}'/>", rect.y, rect.height)?;
         }
-        writeln!(f, "'/>")
+        if self.opts.tick_lines {
+            write!(f, "<path")?;
+            self.class_fmt(f, "axis-tick")?;
+            write!(f, " d='")?;
+            for tick in self.ticks.iter() {
+                let x = tick.x(self.opts.edge, rect, len);
+                let y = tick.y_pos(self.opts.edge, rect, len);
+                let x0 = x.min(x + width);
+                let w = x.max(x + width) - x0;
+                point_dec_fmt(f, b'M', x0.into(), y, self.precision)?;
+                cmd_fmt(f, b'h', w)?;
+            }
+            writeln!(f, "'/>")?;
+        }
+        Ok(())
     }
 
     /// Render tick labels
@@ -195,14 +747,47 @@ impl<'a> Axis<'a> {
         f: &mut fmt::Formatter,
         rect: Rect,
     ) -> fmt::Result {
-        match self.edge {
+        match self.opts.edge {
             Edge::Bottom | Edge::Top => {
-                self.render_tick_labels_horizontal(f, rect)
+                self.render_tick_labels_horizontal(f, rect)?
             }
             Edge::Left | Edge::Right => {
-                self.render_tick_labels_vertical(f, rect)
+                self.render_tick_labels_vertical(f, rect)?
             }
         }
+        self.render_offset_text(f, rect)
+    }
+
+    /// Render offset text at the end of the axis
+    ///
+    /// This is one line beyond the tick labels of a horizontal axis, or
+    /// above the top tick label of a vertical axis.
+    fn render_offset_text(
+        &self,
+        f: &mut fmt::Formatter,
+        rect: Rect,
+    ) -> fmt::Result {
+        let Some(offset) = &self.offset_text else {
+            return Ok(());
+        };
+        let edge = self.opts.edge;
+        let style = &self.opts.tick_style;
+        let (end, anchor, line) = match edge {
+            Edge::Top => (Tick::new(1.0, ""), Anchor::End, -1.0),
+            Edge::Bottom => (Tick::new(1.0, ""), Anchor::End, 1.0),
+            Edge::Left => (Tick::new(0.0, ""), Anchor::End, -1.0),
+            Edge::Right => (Tick::new(0.0, ""), Anchor::Start, -1.0),
+        };
+        let x = end.x(edge, rect, style.hlen());
+        let y = end.y(edge, rect, style.vlen());
+        let dy = ((style.label_dy + line) * 100.0).round() / 100.0;
+        let text = Text::new(Edge::Top)
+            .anchor(anchor)
+            .class_name("tick")
+            .inline(self.inline);
+        text.display(f)?;
+        write!(f, "{}", Tspan::new(offset).x(x).y(y).dy(dy))?;
+        text.display_done(f)
     }
 
     /// Render horizontal tick labels
@@ -211,32 +796,371 @@ impl<'a> Axis<'a> {
         f: &mut fmt::Formatter,
         rect: Rect,
     ) -> fmt::Result {
-        let text = Text::new(Edge::Top).class_name("tick");
+        if self.opts.is_rotated() {
+            return self.render_tick_labels_rotated(f, rect);
+        }
+        let style = &self.opts.tick_style;
+        let text = Text::new(Edge::Top).class_name("tick").inline(self.inline);
         text.display(f)?;
-        for tick in &self.ticks {
-            let tspan = tick.tspan(self.edge, rect);
-            write!(f, "{tspan}")?;
+        let visible = self.visible_tick_labels(rect);
+        for (tick, _) in self.ticks.iter().zip(visible).filter(|(_, v)| *v) {
+            for tspan in tick.tspans(self.opts.edge, rect, style) {
+                write!(f, "{tspan}")?;
+            }
         }
         text.display_done(f)
     }
 
+    /// Render rotated horizontal tick labels
+    fn render_tick_labels_rotated(
+        &self,
+        f: &mut fmt::Formatter,
+        rect: Rect,
+    ) -> fmt::Result {
+        let deg = self.opts.tick_rotation;
+        let anchor = match (self.opts.edge, deg < 0.0) {
+            (Edge::Bottom, true) | (Edge::Top, false) => Anchor::End,
+            _ => Anchor::Start,
+        };
+        let style = &self.opts.tick_style;
+        for tick in self.ticks.iter().filter(|t| !t.text().is_empty()) {
+            let x = tick.x(self.opts.edge, rect, style.hlen());
+            let y = tick.y(self.opts.edge, rect, style.hlen());
+            write!(f, "<text")?;
+            self.class_fmt(f, "tick")?;
+            writeln!(f, " transform='rotate({deg} {x} {y})'{anchor}>")?;
+            for (i, line) in tick.lines().enumerate() {
+                let dy = ((style.label_dy + i as f32) * 100.0).round() / 100.0;
+                write!(f, "{}", Tspan::new(line).x(x).y(y).dy(dy))?;
+            }
+            writeln!(f, "</text>")?;
+        }
+        Ok(())
+    }
+
     /// Render vertical tick labels
     fn render_tick_labels_vertical(
         &self,
         f: &mut fmt::Formatter,
         rect: Rect,
     ) -> fmt::Result {
-        let anchor = match self.edge {
+        let anchor = match self.opts.edge {
             Edge::Left => Anchor::End,
             Edge::Right => Anchor::Start,
             _ => unreachable!(),
         };
-        let text = Text::new(Edge::Top).anchor(anchor).class_name("tick");
+        let text = Text::new(Edge::Top)
+            .anchor(anchor)
+            .class_name("tick")
+            .inline(self.inline);
         text.display(f)?;
-        for tick in &self.ticks {
-            let tspan = tick.tspan(self.edge, rect);
-            write!(f, "{tspan}")?;
+        let visible = self.visible_tick_labels(rect);
+        for (tick, _) in self.ticks.iter().zip(visible).filter(|(_, v)| *v) {
+            let style = &self.opts.tick_style;
+            for tspan in tick.tspans(self.opts.edge, rect, style) {
+                write!(f, "{tspan}")?;
+            }
         }
         text.display_done(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chart, Domain, Plot, TickFormat};
+
+    #[test]
+    fn no_grid() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .axis_opts(AxisOpts::new("X", Edge::Bottom).no_grid())
+            .axis("Y", Edge::Left)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(!svg.contains("grid-x"));
+        assert!(svg.contains("<path class='grid-y'"));
+        assert!(svg.contains("<path class='axis-line' d='M208 1300h1752"));
+    }
+
+    #[test]
+    fn grid_only() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .axis_opts(AxisOpts::new("X", Edge::Bottom).grid_only())
+            .axis("Y", Edge::Left)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(!svg.contains("\nX\n"));
+        assert_eq!(svg.matches("class='axis-line'").count(), 1);
+        assert_eq!(svg.matches("class='tick'").count(), 1);
+        assert!(svg.contains("<path class='grid-x' d='M208 40v1420"));
+        assert!(svg.contains("<desc>Axes: Y. Plots: A.</desc>"));
+    }
+
+    #[test]
+    fn rotation() {
+        let cats: Vec<String> =
+            (0..10).map(|i| format!("category-{i:03}")).collect();
+        let opts = AxisOpts::new("", Edge::Bottom).tick_rotation(-45.0);
+        let chart = Chart::new()
+            .domain(Domain::default().set_x_categories(&cats))
+            .axis_opts(opts.clone());
+        let svg = chart.to_string();
+        let xs: Vec<i32> = svg
+            .split("rotate(-45 ")
+            .skip(1)
+            .map(|s| s.split(' ').next().unwrap().parse().unwrap())
+            .collect();
+        assert_eq!(xs.len(), 10);
+        assert!(svg.contains("text-anchor='end'"));
+        // rotated labels must be separated by at least one line of text
+        let min_dx = CHAR_HEIGHT / 45f32.to_radians().sin();
+        assert!(xs.windows(2).all(|w| (w[1] - w[0]) as f32 >= min_dx));
+        let ticks = cats.iter().map(|c| Tick::new(0.5, c.as_str())).collect();
+        let axis = Axis::new(opts, ticks);
+        assert!(axis.space() as f32 >= 12.0 * CHAR_WIDTH * 0.7);
+    }
+
+    #[test]
+    fn escaped_name() {
+        let svg = Chart::new()
+            .axis("<ms> & count", Edge::Bottom)
+            .axis("<ms> & count", Edge::Left)
+            .to_string();
+        assert_eq!(svg.matches("\n&lt;ms&gt; &amp; count\n").count(), 2);
+        assert!(!svg.contains("<ms>"));
+    }
+
+    #[test]
+    fn no_tick_labels() {
+        let opts = AxisOpts::new("Y Axis", Edge::Left).no_tick_labels();
+        let chart = Chart::new().axis_opts(opts.clone());
+        let svg = chart.to_string();
+        assert!(svg.contains("class='axis-line'"));
+        assert!(svg.contains("class='axis-tick'"));
+        assert!(svg.contains("Y Axis"));
+        assert!(!svg.contains("class='tick'"));
+        let axis = Axis::new(opts, vec![]);
+        assert_eq!(axis.space(), NAME_SPACE + TickStyle::default().len);
+    }
+
+    #[test]
+    fn line_and_ticks() {
+        let opts = AxisOpts::new("", Edge::Bottom).no_line();
+        let svg = Chart::new().axis_opts(opts).to_string();
+        assert!(!svg.contains("class='axis-line'"));
+        assert!(svg.contains("class='axis-tick'"));
+        let opts = AxisOpts::new("", Edge::Left).no_ticks();
+        let svg = Chart::new().axis_opts(opts).to_string();
+        assert!(svg.contains("class='axis-line'"));
+        assert!(!svg.contains("class='axis-tick'"));
+        assert!(svg.contains("class='tick'"));
+    }
+
+    #[test]
+    fn zero_line() {
+        let data = [(-5.0, -3.0), (5.0, 7.0)];
+        let svg = Chart::new()
+            .domain(&data[..])
+            .axis_opts(AxisOpts::new("", Edge::Left).highlight_zero())
+            .to_string();
+        let zero = svg.find("grid-zero").unwrap();
+        assert!(svg.find("grid-y").unwrap() < zero);
+        assert!(svg.find("clip-path='url(#clip-chart)'").unwrap() > zero);
+        let data = [(1.0, 3.0), (5.0, 7.0)];
+        let svg = Chart::new()
+            .domain(&data[..])
+            .axis_opts(AxisOpts::new("", Edge::Bottom).highlight_zero())
+            .to_string();
+        assert!(!svg.contains("grid-zero"));
+    }
+
+    #[test]
+    fn space() {
+        let ticks = vec![Tick::new(0.0, "0"), Tick::new(1.0, "1000000")];
+        let axis = Axis::new(AxisOpts::new("", Edge::Left), ticks);
+        assert!(axis.space() as f32 > 7.0 * CHAR_WIDTH);
+        assert!(axis.space() > TickStyle::default().horizontal_space());
+        let ticks = vec![Tick::new(0.0, "0"), Tick::new(1.0, "5")];
+        let axis = Axis::new(AxisOpts::new("", Edge::Left), ticks);
+        assert!(axis.space() as f32 > CHAR_WIDTH);
+        assert!(axis.space() < TickStyle::default().horizontal_space());
+        let axis = Axis::new(AxisOpts::new("", Edge::Left).space(120), vec![]);
+        assert_eq!(axis.space(), 120);
+        let ticks = vec![Tick::new(0.0, "0"), Tick::new(1.0, "1000000")];
+        let opts = AxisOpts::new("", Edge::Left).char_width(40.0);
+        let axis = Axis::new(opts, ticks);
+        assert!(axis.space() as f32 > 7.0 * 40.0);
+    }
+
+    #[test]
+    fn offset_text() {
+        let data = [(0.0, 1e-7), (1.0, 1e-6)];
+        let chart = || {
+            Chart::new()
+                .domain(Domain::from(&data[..]))
+                .axis("", Edge::Bottom)
+                .axis("", Edge::Left)
+        };
+        let svg = chart().to_string();
+        assert_eq!(svg.matches("×10⁻⁶</tspan>").count(), 1);
+        assert!(svg.contains(">0.5</tspan>"));
+        assert!(!svg.contains("0.0000001"));
+        let domain = Domain::from(&data[..]).y_tick_format(TickFormat::Decimal);
+        let svg = Chart::new().domain(domain).axis("", Edge::Left).to_string();
+        assert!(!svg.contains("×10"));
+        assert!(svg.contains(">0.0000005</tspan>"));
+    }
+
+    #[test]
+    fn explicit_ticks() {
+        let data = [(0.0, 0.0), (120.0, 10.0)];
+        let opts = AxisOpts::new("", Edge::Bottom)
+            .ticks_at(&[0.0, 15.0, 30.0, 60.0, 120.0, 240.0]);
+        let svg = Chart::new()
+            .domain(Domain::from(&data[..]))
+            .axis_opts(opts)
+            .to_string();
+        for text in ["0", "15", "30", "60", "120"] {
+            assert!(svg.contains(&format!(">{text}</tspan>")));
+        }
+        assert!(!svg.contains(">240</tspan>"));
+        let opts = AxisOpts::new("", Edge::Left).ticks_labeled(&[
+            (0.0, "low"),
+            (10.0, "high"),
+            (-5.0, "none"),
+        ]);
+        let svg = Chart::new()
+            .domain(Domain::from(&data[..]))
+            .axis_opts(opts)
+            .to_string();
+        assert_eq!(svg.matches("</tspan>").count(), 2);
+        assert!(svg.contains(">low</tspan>"));
+        assert!(svg.contains(">high</tspan>"));
+    }
+
+    #[test]
+    fn ticks_from_data() {
+        let data = [(1.0, 2.0), (3.0, 4.0), (3.0, 5.0), (7.0, 6.0)];
+        let svg = Chart::new()
+            .domain(&data[..])
+            .axis_opts(AxisOpts::new("", Edge::Bottom).ticks_from_data())
+            .plot(crate::Plot::scatter("A", &data[..]))
+            .to_string();
+        assert_eq!(svg.matches("</tspan>").count(), 3);
+        assert!(svg.contains(">7</tspan>"));
+    }
+
+    #[test]
+    fn multi_line() {
+        let opts = AxisOpts::new("", Edge::Bottom)
+            .ticks_labeled(&[(0.0, "Jun 3\n2024"), (1.0, "Jun 4\n2024")]);
+        let svg = Chart::new().axis_opts(opts.clone()).to_string();
+        assert!(svg.contains("dy='0.33em'>Jun 3</tspan>"));
+        assert!(svg.contains("dy='1.33em'>2024</tspan>"));
+        let single = Axis::new(opts.clone(), vec![Tick::new(0.0, "Jun 3")]);
+        let double = Axis::new(opts, vec![Tick::new(0.0, "Jun 3\n2024")]);
+        assert_eq!(double.space(), single.space() + CHAR_HEIGHT as u16);
+        let rect = Rect::new(0, 0, 100, 100);
+        let style = TickStyle::default();
+        let tick = Tick::new(0.5, "a\nb").with_sub_text("c");
+        let dy = |edge| -> Vec<String> {
+            tick.tspans(edge, rect, &style)
+                .iter()
+                .map(|t| t.to_string())
+                .collect()
+        };
+        assert!(dy(Edge::Top)[0].contains("dy='-0.67em'>a"));
+        assert!(dy(Edge::Top)[1].contains("dy='0.33em'>b"));
+        assert!(dy(Edge::Top)[2].contains("dy='-1.67em'>c"));
+        assert!(dy(Edge::Left)[0].contains("dy='-0.67em'>a"));
+        assert!(dy(Edge::Left)[2].contains("dy='1.33em'>c"));
+    }
+
+    #[test]
+    fn name_anchor() {
+        let svg = Chart::new()
+            .axis_opts(
+                AxisOpts::new("X", Edge::Bottom).name_anchor(Anchor::End),
+            )
+            .axis_opts(AxisOpts::new("Y", Edge::Left).name_anchor(Anchor::End))
+            .to_string();
+        assert!(svg.contains("text-anchor='end'>\nX\n</text>"));
+        assert!(svg.contains("rotate(-90)' text-anchor='end'>\nY\n</text>"));
+    }
+
+    #[test]
+    fn name_horizontal() {
+        let opts = AxisOpts::new("Y Axis", Edge::Left);
+        let mut rotated = Axis::new(opts.clone(), vec![]);
+        let mut above = Axis::new(
+            opts.clone().name_horizontal().name_anchor(Anchor::Start),
+            vec![],
+        );
+        let area = Rect::new(0, 0, 1000, 1000);
+        let rest = rotated.split(area);
+        assert_eq!(rest.y, 0);
+        let rest_above = above.split(area);
+        assert_eq!(rest_above.y, i32::from(NAME_SPACE));
+        assert_eq!(rest_above.x + i32::from(NAME_SPACE), rest.x);
+        let svg = Chart::new()
+            .axis_opts(opts.name_horizontal().name_anchor(Anchor::Start))
+            .to_string();
+        assert!(!svg.contains("rotate(-90)"));
+        assert!(svg.contains("text-anchor='start'>\nY Axis\n</text>"));
+        let svg = Chart::new()
+            .axis_opts(
+                AxisOpts::new("<ms> & count", Edge::Left).name_horizontal(),
+            )
+            .to_string();
+        assert!(svg.contains(">\n&lt;ms&gt; &amp; count\n</text>"));
+    }
+
+    #[test]
+    fn tick_style() {
+        let style = TickStyle {
+            len: 8,
+            label_gap: 4,
+            label_dy: 0.5,
+        };
+        let opts = AxisOpts::new("", Edge::Left).tick_style(style);
+        let svg = Chart::new().axis_opts(opts.clone()).to_string();
+        assert!(svg.contains("h8'/>"));
+        assert!(svg.contains("dy='0.5em'"));
+        assert!(!svg.contains("dy='0.33em'"));
+        let axis = Axis::new(opts.no_tick_labels(), vec![]);
+        assert_eq!(axis.space(), 8);
+        let opts = AxisOpts::new("", Edge::Bottom).tick_style(style);
+        let axis = Axis::new(opts, vec![Tick::new(0.0, "0")]);
+        assert_eq!(axis.space(), 64);
+    }
+
+    #[test]
+    fn thin_labels() {
+        let rect = Rect::new(0, 0, 1000, 100);
+        let ticks = || {
+            (0..=10)
+                .map(|i| Tick::new(f64::from(i) / 10.0, format!("{i}")))
+                .collect::<Vec<_>>()
+        };
+        let opts = AxisOpts::new("", Edge::Bottom);
+        let axis = Axis::new(opts.clone(), ticks());
+        assert!(axis.visible_tick_labels(rect).iter().all(|v| *v));
+        let axis = Axis::new(opts.clone().char_width(120.0), ticks());
+        let visible = axis.visible_tick_labels(rect);
+        let shown: Vec<usize> =
+            (0..visible.len()).filter(|i| visible[*i]).collect();
+        assert_eq!(shown, [0, 2, 4, 6, 8, 10]);
+        let axis = Axis::new(opts.char_width(300.0), ticks());
+        let visible = axis.visible_tick_labels(rect);
+        let shown: Vec<usize> =
+            (0..visible.len()).filter(|i| visible[*i]).collect();
+        assert_eq!(shown, [0, 4, 10]);
+        let axis = Axis::new(AxisOpts::new("", Edge::Left), ticks());
+        let rect = Rect::new(0, 0, 100, 200);
+        let visible = axis.visible_tick_labels(rect);
+        assert!(visible[0] && visible[10]);
+        assert!(visible.iter().filter(|v| **v).count() < 11);
+    }
+}
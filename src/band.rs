@@ -0,0 +1,194 @@
+// band.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+use crate::chart::Layer;
+use crate::domain::BoundDomain;
+use crate::rect::{Edge, Rect};
+use crate::style::inline_attrs;
+use crate::text::{Anchor, Text, Tspan};
+use std::borrow::Cow;
+use std::fmt;
+
+/// Padding between a band edge and its label
+const LABEL_PAD: i32 = 8;
+
+/// Shaded band spanning the plot area
+///
+/// A vertical band covers a range of `X` values, and a horizontal band covers
+/// a range of `Y` values.
+///
+/// ```rust
+/// use splot::Band;
+///
+/// let window = Band::vertical(2.0, 3.0)
+///     .class_name("maintenance")
+///     .label("Maintenance");
+/// let normal = Band::horizontal(20.0, 80.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Band<'a> {
+    vertical: bool,
+    start: f64,
+    end: f64,
+    class_name: Option<Cow<'a, str>>,
+    label: Option<Cow<'a, str>>,
+    layer: Layer,
+}
+
+impl<'a> Band<'a> {
+    /// Create a vertical band from `x0` to `x1`
+    pub fn vertical(x0: f64, x1: f64) -> Self {
+        Band {
+            vertical: true,
+            start: x0,
+            end: x1,
+            class_name: None,
+            label: None,
+            layer: Layer::BelowPlots,
+        }
+    }
+
+    /// Create a horizontal band from `y0` to `y1`
+    pub fn horizontal(y0: f64, y1: f64) -> Self {
+        Band {
+            vertical: false,
+            ..Band::vertical(y0, y1)
+        }
+    }
+
+    /// Add a class name, after the `band` class
+    pub fn class_name<C>(mut self, class_name: C) -> Self
+    where
+        C: Into<Cow<'a, str>>,
+    {
+        self.class_name = Some(class_name.into());
+        self
+    }
+
+    /// Add a label, drawn inside the band
+    ///
+    /// Vertical bands are labeled at the top, and horizontal bands at the
+    /// left.
+    pub fn label<L>(mut self, label: L) -> Self
+    where
+        L: Into<Cow<'a, str>>,
+    {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the layer, which is below plots by default
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Check if the band is drawn on a layer
+    pub(crate) fn is_on(&self, layer: Layer) -> bool {
+        self.layer == layer
+    }
+
+    /// Get the visible band rectangle, clipped to the plot area
+    fn rect(&self, domain: &BoundDomain, area: Rect) -> Option<Rect> {
+        let (lo, hi) = if self.vertical {
+            (f64::from(area.x), f64::from(area.right()))
+        } else {
+            (f64::from(area.y), f64::from(area.bottom()))
+        };
+        let (a, b) = if self.vertical {
+            (domain.x_pos(self.start), domain.x_pos(self.end))
+        } else {
+            (domain.y_pos(self.start), domain.y_pos(self.end))
+        };
+        let start = a.min(b).max(lo).round();
+        let end = a.max(b).min(hi).round();
+        if end <= start {
+            return None;
+        }
+        let span = (end - start) as u16;
+        Some(if self.vertical {
+            Rect::new(start as i32, area.y, span, area.height)
+        } else {
+            Rect::new(area.x, start as i32, area.width, span)
+        })
+    }
+
+    /// Render the band
+    ///
+    /// Nothing is rendered if the band is entirely outside the domain.
+    pub(crate) fn render(
+        &self,
+        f: &mut fmt::Formatter,
+        domain: &BoundDomain,
+        area: Rect,
+        inline: bool,
+    ) -> fmt::Result {
+        let Some(rect) = self.rect(domain, area) else {
+            return Ok(());
+        };
+        write!(f, "<rect class='band")?;
+        if let Some(class_name) = &self.class_name {
+            write!(f, " {class_name}")?;
+        }
+        write!(f, "'")?;
+        if inline {
+            write!(f, "{}", inline_attrs("band"))?;
+        }
+        write!(f, " x='{}' y='{}'", rect.x, rect.y)?;
+        writeln!(f, " width='{}' height='{}'/>", rect.width, rect.height)?;
+        if let Some(label) = &self.label {
+            let (anchor, x, y, dy) = if self.vertical {
+                let x = rect.x + i32::from(rect.width) / 2;
+                (Anchor::Middle, x, rect.y, 1.33)
+            } else {
+                let y = rect.y + i32::from(rect.height) / 2;
+                (Anchor::Start, rect.x + LABEL_PAD, y, 0.33)
+            };
+            let text = Text::new(Edge::Top)
+                .anchor(anchor)
+                .class_name("band-label")
+                .inline(inline);
+            text.display(f)?;
+            write!(f, "{}", Tspan::new(label).x(x).y(y).dy(dy))?;
+            text.display_done(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chart, Domain};
+
+    #[test]
+    fn clipped() {
+        let domain = Domain::from_ranges(0..10, 0..100);
+        let bound = domain.bind(Rect::new(0, 0, 1000, 500));
+        let area = Rect::new(0, 0, 1000, 500);
+        let band = Band::vertical(2.0, 3.0);
+        assert_eq!(band.rect(&bound, area), Some(Rect::new(200, 0, 100, 500)));
+        let band = Band::vertical(8.0, 15.0);
+        assert_eq!(band.rect(&bound, area), Some(Rect::new(800, 0, 200, 500)));
+        let band = Band::vertical(12.0, 15.0);
+        assert_eq!(band.rect(&bound, area), None);
+        let band = Band::horizontal(80.0, 20.0);
+        assert_eq!(band.rect(&bound, area), Some(Rect::new(0, 100, 1000, 300)));
+    }
+
+    #[test]
+    fn render() {
+        let data = [(0.0, 0.0), (10.0, 100.0)];
+        let svg = Chart::new()
+            .domain(&data[..])
+            .band(Band::vertical(2.0, 3.0).class_name("maint").label("M"))
+            .hband(20.0, 80.0)
+            .to_string();
+        let band = svg.find("<rect class='band maint'").unwrap();
+        assert!(svg.contains("<rect class='band' x="));
+        assert!(svg.contains("<text class='band-label'"));
+        assert!(svg.contains(">M</tspan>"));
+        assert!(band < svg.find("<g clip-path").unwrap());
+    }
+}
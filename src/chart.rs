@@ -2,16 +2,31 @@
 //
 // Copyright (c) 2021-2024  Douglas P Lau
 //
-use crate::axis::Axis;
-use crate::domain::Domain;
-use crate::plot::Plot;
-use crate::point::IntoPoint;
+use crate::annotation::{render_annotations, Annotation};
+use crate::axis::{Axis, AxisOpts};
+use crate::band::Band;
+use crate::domain::{BoundDomain, Domain};
+use crate::error::ChartError;
+use crate::legend::{LegendEntry, MarkerShape};
+use crate::palette::Palette;
+use crate::plot::{Plot, PlotType, Stacks};
 use crate::rect::{Edge, Rect, ViewBox};
+use crate::save::write_atomic;
+use crate::style::{
+    inline_attrs, prefix_css, ClassPrefix, Stylesheet, DEFAULT_CSS,
+};
+use crate::text::{escape, indent, Text, Tspan};
+use crate::theme::Theme;
 use crate::title::Title;
+use std::borrow::Cow;
+use std::cell::{Ref, RefCell};
 use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Marker shapes
-const MARKERS: &[&str] = &[
+pub(crate) const MARKERS: &[&str] = &[
     "<circle r='1'/>",
     "<rect x='-1' y='-1' width='2' height='2'/>",
     "<path d='M0 -1 1 1 -1 1z'/>",
@@ -22,6 +37,21 @@ const MARKERS: &[&str] = &[
     "<path d='M-1 -1 0 -0.5 1 -1 0.5 0 1 1 0 0.5 -1 1 -0.5 0z'/>",
 ];
 
+/// Default outer margin
+const MARGIN: u16 = 40;
+
+/// Delay between starts of animated plots, in seconds
+const ANIMATE_STAGGER: f32 = 0.25;
+
+/// Maximum number of crosshair positions
+const MAX_CROSSHAIRS: usize = 300;
+
+/// Next chart number for unique element IDs
+pub(crate) static NEXT_UNIQUE: AtomicUsize = AtomicUsize::new(0);
+
+/// Clip outset to avoid cutting markers in half
+const MARKER_OUTSET: u16 = 13;
+
 /// Chart aspect ratio
 #[derive(Clone, Copy)]
 pub enum AspectRatio {
@@ -31,23 +61,74 @@ pub enum AspectRatio {
     Square,
     /// Tall rectangular aspect
     Portrait,
+    /// Custom width and height, in viewBox units
+    ///
+    /// The presets are 2000 units on their longest side.
+    Custom(u16, u16),
+}
+
+/// Layer for drawing, relative to plots
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layer {
+    /// Below plots, but above backgrounds
+    BelowPlots,
+    /// Above plots
+    AbovePlots,
+}
+
+/// Legend position, relative to a chart on a `Page`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LegendPos {
+    /// Above chart, with entries in one row
+    Top,
+    /// Below chart, with entries in one row
+    Bottom,
+    /// Left of chart, with entries in one column
+    Left,
+    /// Right of chart, with entries in one column
+    Right,
 }
 
 /// Chart for plotting data
 ///
 /// Multiple `Plot`s can be rendered in a single Chart, even with unrelated
 /// domains and axes.
-pub struct Chart<'a, P>
-where
-    P: IntoPoint,
-{
+///
+/// Parts can be added in any order.  Titles, axes and plots are laid out
+/// in that order, each taking space from the remaining area.
+pub struct Chart<'a> {
     stand_alone: bool,
+    stylesheet: Stylesheet<'a>,
+    inline: bool,
+    precision: u8,
+    background: bool,
+    plot_background: bool,
+    crosshairs: bool,
+    grid_on_top: bool,
+    legend_pos: Option<LegendPos>,
+    description: Option<Cow<'a, str>>,
+    render_size: Option<(Cow<'a, str>, Cow<'a, str>)>,
+    preserve_aspect_ratio: Option<Cow<'a, str>>,
     aspect_ratio: AspectRatio,
+    margin: [u16; 4],
+    domain: Domain,
+    axis_parts: Vec<(AxisOpts<'a>, Option<Domain>)>,
+    bands: Vec<Band<'a>>,
+    parts: RefCell<Parts<'a>>,
+    annotations: Vec<Annotation<'a>>,
+    palette: Option<Palette>,
+    id: usize,
+    id_prefix: Cow<'a, str>,
+    class_prefix: Cow<'a, str>,
+}
+
+/// Chart parts placed by layout
+struct Parts<'a> {
     titles: Vec<Title<'a>>,
+    shown_titles: usize,
     domain: Domain,
     axes: Vec<Axis<'a>>,
-    plots: Vec<Plot<'a, P>>,
-    num: u32,
+    plots: Vec<Plot<'a>>,
     area: Rect,
 }
 
@@ -58,179 +139,1620 @@ impl AspectRatio {
             AspectRatio::Landscape => Rect::new(0, 0, 2000, 1500),
             AspectRatio::Square => Rect::new(0, 0, 2000, 2000),
             AspectRatio::Portrait => Rect::new(0, 0, 1500, 2000),
+            AspectRatio::Custom(width, height) => {
+                Rect::new(0, 0, width, height)
+            }
+        }
+    }
+}
+
+impl LegendPos {
+    /// Get class name
+    pub(crate) fn class_name(self) -> &'static str {
+        match self {
+            LegendPos::Top => "legend-top",
+            LegendPos::Bottom => "legend-bottom",
+            LegendPos::Left => "legend-left",
+            LegendPos::Right => "legend-right",
         }
     }
 }
 
-impl<'a, P> Default for Chart<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> Default for Chart<'a> {
     fn default() -> Self {
         Self {
             stand_alone: true,
+            stylesheet: Stylesheet::default(),
+            inline: false,
+            precision: 0,
+            background: false,
+            plot_background: false,
+            crosshairs: false,
+            grid_on_top: false,
+            legend_pos: None,
+            description: None,
+            render_size: None,
+            preserve_aspect_ratio: None,
             aspect_ratio: AspectRatio::Landscape,
-            titles: vec![],
+            margin: [MARGIN; 4],
             domain: Domain::default(),
-            axes: vec![],
-            plots: vec![],
-            num: 0,
-            area: AspectRatio::Landscape.rect().inset(40),
+            axis_parts: vec![],
+            bands: vec![],
+            parts: RefCell::new(Parts {
+                titles: vec![],
+                shown_titles: 0,
+                domain: Domain::default(),
+                axes: vec![],
+                plots: vec![],
+                area: AspectRatio::Landscape.rect().inset(MARGIN),
+            }),
+            annotations: vec![],
+            palette: None,
+            id: 0,
+            id_prefix: Cow::Borrowed(""),
+            class_prefix: Cow::Borrowed(""),
         }
     }
 }
 
-impl<'a, P> Chart<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> Chart<'a> {
     /// Create a new chart
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set stand-alone flag
-    pub(crate) fn stand_alone(mut self, stand_alone: bool) -> Self {
+    /// Set whether the chart is a stand-alone SVG document (the default)
+    ///
+    /// When `false`, the chart is meant to be placed within an HTML document,
+    /// as with a `Page`.  Exactly two things are left out:
+    ///
+    /// - The `xmlns` attribute on the `<svg>` element
+    /// - The stylesheet element: `<link>`, or `<style>` with `embed_css`
+    ///
+    /// The legend is still rendered after the `<svg>` element.
+    pub fn stand_alone(mut self, stand_alone: bool) -> Self {
         self.stand_alone = stand_alone;
         self
     }
 
+    /// Set chart ID, to make element IDs unique on a `Page`
+    pub(crate) fn id(mut self, id: usize) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Set a prefix for element IDs (`clipPath` and `marker`)
+    ///
+    /// IDs are deterministic by default, but they collide when several
+    /// charts are placed into one HTML document outside of a `Page`.  A
+    /// fixed prefix keeps output stable for snapshot tests.
+    pub fn id_prefix<P>(mut self, prefix: P) -> Self
+    where
+        P: Into<Cow<'a, str>>,
+    {
+        self.id_prefix = prefix.into();
+        self
+    }
+
+    /// Give element IDs a prefix which is unique within this process
+    ///
+    /// The prefix is `splot-{n}-`, where `n` counts up from 0.
+    pub fn unique_ids(self) -> Self {
+        let n = NEXT_UNIQUE.fetch_add(1, Ordering::Relaxed);
+        self.id_prefix(format!("splot-{n}-"))
+    }
+
+    /// Set a prefix for every CSS class
+    ///
+    /// Generic class names like `title` and `tick` can collide with other
+    /// CSS when charts are inlined into a document.  The prefix applies to
+    /// all classes in the SVG and legend, including custom band classes,
+    /// and to embedded CSS.  Use a `Theme` with the same prefix for an
+    /// external stylesheet.  Classes of `Page` layout elements are not
+    /// prefixed.
+    pub fn class_prefix<P>(mut self, prefix: P) -> Self
+    where
+        P: Into<Cow<'a, str>>,
+    {
+        self.class_prefix = prefix.into();
+        self
+    }
+
+    /// Get marker ID for a plot, if not the default
+    fn marker_id(&self, i: usize) -> Option<String> {
+        let prefix = &self.id_prefix;
+        match self.id {
+            0 if prefix.is_empty() => None,
+            0 => Some(format!("{prefix}marker-{i}")),
+            id => Some(format!("{prefix}marker-{id}-{i}")),
+        }
+    }
+
+    /// Get element ID prefix for a plot
+    fn plot_id(&self, i: usize) -> String {
+        let prefix = &self.id_prefix;
+        match self.id {
+            0 => format!("{prefix}plot-{i}"),
+            id => format!("{prefix}plot-{id}-{i}"),
+        }
+    }
+
+    /// Get clip path ID
+    fn clip_id(&self) -> String {
+        let prefix = &self.id_prefix;
+        match self.id {
+            0 => format!("{prefix}clip-chart"),
+            id => format!("{prefix}clip-chart-{id}"),
+        }
+    }
+
+    /// Set legend position
+    pub(crate) fn legend_pos(mut self, pos: LegendPos) -> Self {
+        self.legend_pos = Some(pos);
+        self
+    }
+
+    /// Get legend position
+    pub(crate) fn legend_position(&self) -> Option<LegendPos> {
+        self.legend_pos
+    }
+
+    /// Embed the default stylesheet instead of linking to it
+    ///
+    /// This produces a self-contained SVG, which renders correctly even in
+    /// an `<img>` element.  It has no effect for charts on a `Page`.
+    pub fn embed_css(self) -> Self {
+        self.css(DEFAULT_CSS)
+    }
+
+    /// Embed custom CSS text instead of linking to the default stylesheet
+    pub fn css(mut self, css: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Embed(css.into());
+        self
+    }
+
+    /// Embed a stylesheet generated from a `Theme`
+    ///
+    /// The chart class prefix is used instead of the theme's prefix.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        let css = theme.class_prefix("").css();
+        self.stylesheet = Stylesheet::Embed(css.into());
+        self
+    }
+
+    /// Set the stylesheet link href
+    ///
+    /// The default is `./css/splot.css`.  An empty href suppresses the link.
+    pub fn css_href(mut self, href: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Link(href);
+        self
+    }
+
+    /// Add inline presentational attributes to all elements
+    ///
+    /// This is for SVG consumers which ignore CSS, such as some PDF
+    /// converters.  Attributes match the default (light) stylesheet.
+    pub fn inline_styles(mut self) -> Self {
+        self.inline = true;
+        self
+    }
+
+    /// Draw a background behind the whole chart
+    pub fn background(mut self) -> Self {
+        self.background = true;
+        self
+    }
+
+    /// Draw a background behind the plot area
+    pub fn plot_background(mut self) -> Self {
+        self.plot_background = true;
+        self
+    }
+
+    /// Draw grid and zero lines above plots, instead of below
+    ///
+    /// This keeps them visible over filled area and bar plots.
+    pub fn grid_on_top(mut self) -> Self {
+        self.grid_on_top = true;
+        self
+    }
+
+    /// Draw crosshair guides, shown on hover by CSS
+    ///
+    /// Each `X` position of the first plot gets a transparent target rect,
+    /// with a vertical guide line and value label shown while it is hovered.
+    /// Crosshairs are left out when there are more than 300 positions.
+    pub fn crosshairs(mut self) -> Self {
+        self.crosshairs = true;
+        self
+    }
+
+    /// Set a description for screen readers
+    ///
+    /// Without one, the description lists axis and plot names.
+    pub fn description<D>(mut self, description: D) -> Self
+    where
+        D: Into<Cow<'a, str>>,
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set rendered `width` and `height` of the root SVG element
+    ///
+    /// Values can be plain numbers (pixels) or any CSS length, such as
+    /// `"100%"`.  The internal coordinate system is unaffected.
+    pub fn render_size<W, H>(mut self, width: W, height: H) -> Self
+    where
+        W: Into<Cow<'a, str>>,
+        H: Into<Cow<'a, str>>,
+    {
+        self.render_size = Some((width.into(), height.into()));
+        self
+    }
+
+    /// Set `preserveAspectRatio` of the root SVG element
+    ///
+    /// For example, `"xMidYMid meet"` or `"none"`.
+    pub fn preserve_aspect_ratio<A>(mut self, value: A) -> Self
+    where
+        A: Into<Cow<'a, str>>,
+    {
+        self.preserve_aspect_ratio = Some(value.into());
+        self
+    }
+
+    /// Set decimal places of plot and grid line coordinates
+    ///
+    /// The default is 0, which rounds coordinates to whole units of the
+    /// viewBox.  One or two decimals avoid stair-stepping in dense series.
+    pub fn precision(mut self, decimals: u8) -> Self {
+        self.precision = decimals;
+        self
+    }
+
     /// Adjust the aspect ratio
     pub fn aspect_ratio(mut self, aspect: AspectRatio) -> Self {
-        assert!(self.axes.is_empty());
-        assert!(self.plots.is_empty());
         self.aspect_ratio = aspect;
-        self.area = self.aspect_ratio.rect().inset(40);
+        self
+    }
+
+    /// Get the drawing area within outer margins
+    fn outer_area(&self) -> Rect {
+        let [top, right, bottom, left] = self.margin;
+        self.aspect_ratio
+            .rect()
+            .inset_edges(top, right, bottom, left)
+    }
+
+    /// Set the outer margin on all edges
+    ///
+    /// The default is 40.
+    pub fn margin(self, margin: u16) -> Self {
+        self.margin_edges(margin, margin, margin, margin)
+    }
+
+    /// Set the outer margin of each edge
+    pub fn margin_edges(
+        mut self,
+        top: u16,
+        right: u16,
+        bottom: u16,
+        left: u16,
+    ) -> Self {
+        self.margin = [top, right, bottom, left];
         self
     }
 
     /// Set the domain
     ///
-    /// Panics if called after `axis` or `plot`.
+    /// Axes and plots without a secondary domain use it, even if they were
+    /// added earlier.
     pub fn domain<D>(mut self, domain: D) -> Self
     where
         D: Into<Domain>,
     {
-        assert!(self.axes.is_empty());
-        assert!(self.plots.is_empty());
         self.domain = domain.into();
         self
     }
 
     /// Add a chart title
-    ///
-    /// Panics if called after `axis` or `plot`.
     pub fn title<T>(mut self, title: T) -> Self
     where
         T: Into<Title<'a>>,
     {
-        assert!(self.axes.is_empty());
-        assert!(self.plots.is_empty());
-        let mut title = title.into();
-        self.area = title.split(self.area);
-        self.titles.push(title);
+        self.parts.get_mut().titles.push(title.into());
         self
     }
 
     /// Add an `Axis`
+    pub fn axis<N>(self, name: N, edge: Edge) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        self.axis_opts(AxisOpts::new(name, edge))
+    }
+
+    /// Add an `Axis` with options
+    ///
+    /// Grid lines are only drawn for the first axis of each orientation.
+    pub fn axis_opts(mut self, opts: AxisOpts<'a>) -> Self {
+        self.axis_parts.push((opts, None));
+        self
+    }
+
+    /// Add an `Axis` using ticks from a secondary domain
+    ///
+    /// Grid lines are not drawn for secondary axes.
+    pub fn axis_for<D, N>(mut self, domain: D, name: N, edge: Edge) -> Self
+    where
+        D: Into<Domain>,
+        N: Into<Cow<'a, str>>,
+    {
+        let opts = AxisOpts::new(name, edge).no_grid();
+        self.axis_parts.push((opts, Some(domain.into())));
+        self
+    }
+
+    /// Set a color palette
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Add a vertical `Band`, shading `X` values from `x0` to `x1`
+    pub fn vband(self, x0: f64, x1: f64) -> Self {
+        self.band(Band::vertical(x0, x1))
+    }
+
+    /// Add a horizontal `Band`, shading `Y` values from `y0` to `y1`
+    pub fn hband(self, y0: f64, y1: f64) -> Self {
+        self.band(Band::horizontal(y0, y1))
+    }
+
+    /// Add a shaded `Band`
     ///
-    /// Panics if called after `plot`.
-    pub fn axis(mut self, name: &'a str, edge: Edge) -> Self {
-        assert!(self.plots.is_empty());
-        let mut axis = self.domain.axis(name, edge);
-        self.area = axis.split(self.area);
-        self.axes.push(axis);
+    /// Bands are drawn behind grid lines and plots, clipped to the plot area.
+    pub fn band(mut self, band: Band<'a>) -> Self {
+        self.bands.push(band);
+        self
+    }
+
+    /// Add a text note at data coordinates
+    ///
+    /// Notes at the same point are stacked.
+    pub fn annotate<T>(self, x: f64, y: f64, text: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.annotation(Annotation::new(x, y, text))
+    }
+
+    /// Add an `Annotation`
+    ///
+    /// Annotations are drawn after plots, and are not clipped to the plot
+    /// area.
+    pub fn annotation(mut self, annotation: Annotation<'a>) -> Self {
+        self.annotations.push(annotation);
         self
     }
 
     /// Add a `Plot`
-    pub fn plot(mut self, mut plot: Plot<'a, P>) -> Self {
-        plot.num(self.num);
-        self.num = if self.num < 10 { self.num + 1 } else { 0 };
-        plot.bind_domain(self.domain.bind(self.area));
-        self.plots.push(plot);
+    pub fn plot(mut self, plot: Plot<'a>) -> Self {
+        self.parts.get_mut().plots.push(plot);
         self
     }
 
+    /// Add a `Plot` bound to a secondary domain
+    ///
+    /// Use the same domain with `axis_for` to label its axis.
+    pub fn plot_in<D>(self, domain: D, plot: Plot<'a>) -> Self
+    where
+        D: Into<Domain>,
+    {
+        self.plot(plot.domain(domain))
+    }
+
+    /// Check that all parts fit into the chart
+    ///
+    /// A chart which does not fit still renders, leaving out titles and
+    /// axes which would leave no plot area.
+    pub fn validate(&self) -> Result<(), ChartError> {
+        if self.aspect_ratio.rect().is_empty() {
+            return Err(ChartError::ZeroSize);
+        }
+        if self.outer_area().is_empty() {
+            return Err(ChartError::NoPlotArea);
+        }
+        let parts = self.layout();
+        if parts.shown_titles < parts.titles.len()
+            || parts.axes.len() < self.axis_parts.len()
+        {
+            return Err(ChartError::OverConstrained);
+        }
+        Ok(())
+    }
+
+    /// Lay out all parts in a single pass
+    ///
+    /// Titles and axes are split from the outer area, then plots are bound
+    /// to what remains.  The domain is extended to cover stacked plots and
+    /// the full width of bars, and to include zero for area and bar plots.
+    /// Categories are appended for indices past the end of the list.
+    /// Titles and axes which would leave no plot area are left out.
+    ///
+    /// This is done once for each rendering, after all parts are added.
+    fn layout(&self) -> Ref<'_, Parts<'a>> {
+        self.lay_out(&mut self.parts.borrow_mut());
+        self.parts.borrow()
+    }
+
+    /// Lay out parts
+    fn lay_out(&self, parts: &mut Parts<'a>) {
+        let mut stacks = Stacks::default();
+        for plot in parts.plots.iter() {
+            plot.total(&mut stacks);
+        }
+        for plot in parts.plots.iter_mut() {
+            plot.stack(&mut stacks);
+        }
+        parts.domain = match stacks.y_range() {
+            Some((lo, hi)) if stacks.is_normalized() => {
+                self.domain.fixed_y(lo, hi)
+            }
+            Some((lo, hi)) => self.domain.covering_y(lo, hi),
+            None => self.domain.clone(),
+        };
+        if parts
+            .plots
+            .iter()
+            .any(|plot| plot.own_domain().is_none() && plot.needs_zero())
+        {
+            parts.domain = parts.domain.covering_zero_y();
+        }
+        for plot in parts.plots.iter().filter(|p| p.own_domain().is_none()) {
+            if let Some(max) = plot.x_max() {
+                parts.domain = parts.domain.covering_x_index(max);
+            }
+            if let Some((lo, hi)) = plot.x_range() {
+                parts.domain = parts.domain.covering_x(lo, hi);
+            }
+        }
+        let mut area = self.outer_area();
+        parts.shown_titles = 0;
+        for title in parts.titles.iter_mut() {
+            if self.inline {
+                title.set_inline();
+            }
+            let rest = title.split(area);
+            if rest.is_empty() {
+                break;
+            }
+            area = rest;
+            parts.shown_titles += 1;
+        }
+        parts.axes.clear();
+        for (opts, domain) in &self.axis_parts {
+            let mut opts = opts.clone();
+            let horizontal = opts.edge().is_horizontal();
+            if parts.axes.iter().any(|a| {
+                a.opts().has_grid()
+                    && a.opts().edge().is_horizontal() == horizontal
+            }) {
+                opts = opts.no_grid();
+            }
+            if opts.has_ticks_from_data() {
+                let values = parts
+                    .plots
+                    .first()
+                    .map(|plot| plot.distinct_values(horizontal))
+                    .unwrap_or_default();
+                opts = opts.ticks_at(&values);
+            }
+            let domain = domain.as_ref().unwrap_or(&parts.domain);
+            let mut axis = domain.axis(opts);
+            if self.inline {
+                axis.set_inline();
+            }
+            axis.set_precision(self.precision);
+            let rest = axis.split(area);
+            if rest.is_empty() {
+                continue;
+            }
+            area = rest;
+            parts.axes.push(axis);
+        }
+        parts.area = area;
+        let mut animated = 0;
+        for i in 0..parts.plots.len() {
+            let marker = self.marker_id(i);
+            let element_id = self.plot_id(i);
+            let num = i % 11;
+            let plot = &mut parts.plots[i];
+            if let Some(palette) = &self.palette {
+                if let Some(color) = palette.color(i) {
+                    plot.set_color(color);
+                }
+            } else if self.inline {
+                // default stylesheet colors are by plot number
+                if let Some(color) = Palette::default().color(num) {
+                    plot.set_color(color);
+                }
+            }
+            if self.inline {
+                plot.set_inline();
+            }
+            plot.set_precision(self.precision);
+            if let Some(marker) = marker {
+                plot.set_marker(marker);
+            }
+            plot.num(num as u32);
+            plot.set_element_id(element_id);
+            if plot.is_animated() {
+                plot.set_animate_delay(ANIMATE_STAGGER * animated as f32);
+                animated += 1;
+            }
+            let domain = plot.own_domain().unwrap_or(&parts.domain);
+            plot.bind_domain(domain.bind(area));
+        }
+    }
+
+    /// Render the chart as indented SVG, for debugging
+    ///
+    /// Only indentation differs from the `Display` output.
+    pub fn to_string_pretty(&self) -> String {
+        indent(&self.to_string())
+    }
+
+    /// Write the chart to an `io::Write`
+    ///
+    /// Output is streamed through a small buffer, without rendering the whole
+    /// chart into a `String` first.
+    pub fn write_to<W: io::Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        write!(w, "{self}")?;
+        w.flush()
+    }
+
+    /// Save the chart as an SVG file
+    ///
+    /// Only the SVG element is written, without the HTML legend.  Parent
+    /// directories are created as needed, and the file is replaced
+    /// atomically.
+    pub fn save_svg<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        write_atomic(path, |file| {
+            let mut w = io::BufWriter::new(file);
+            write!(w, "{}", PrefixedChart(self, false))?;
+            w.flush()
+        })
+    }
+
+    /// Render the complete SVG element
+    fn svg_element(
+        &self,
+        f: &mut fmt::Formatter,
+        parts: &Parts<'a>,
+        stylesheet: Option<&Stylesheet>,
+    ) -> fmt::Result {
+        self.svg(f, parts)?;
+        if let Some(stylesheet) = stylesheet {
+            stylesheet.svg_fmt(f)?;
+        }
+        self.defs(f, parts)?;
+        self.body(f, parts)?;
+        writeln!(f, "</svg>")
+    }
+
     /// Render SVG element start
-    fn svg(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn svg(&self, f: &mut fmt::Formatter, parts: &Parts) -> fmt::Result {
         let view_box = ViewBox(self.aspect_ratio.rect());
         write!(f, "<svg")?;
         if self.stand_alone {
             write!(f, " xmlns='http://www.w3.org/2000/svg'")?;
         }
-        writeln!(f, " {view_box}>")
+        write!(f, " {view_box}")?;
+        if let Some((width, height)) = &self.render_size {
+            write!(f, " width='{}'", escape(width))?;
+            write!(f, " height='{}'", escape(height))?;
+        }
+        if let Some(value) = &self.preserve_aspect_ratio {
+            write!(f, " preserveAspectRatio='{}'", escape(value))?;
+        }
+        write!(f, " role='img'")?;
+        let label = self.aria_label(parts);
+        if !label.is_empty() {
+            write!(f, " aria-label='{}'", escape(&label))?;
+        }
+        writeln!(f, ">")?;
+        let desc = self.desc(parts);
+        if !desc.is_empty() {
+            writeln!(f, "<desc>{}</desc>", escape(&desc))?;
+        }
+        Ok(())
     }
 
-    /// Render link to CSS
-    fn link(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "<link")?;
-        write!(f, " xmlns='http://www.w3.org/1999/xhtml'")?;
-        write!(f, " type='text/css'")?;
-        write!(f, " rel='stylesheet'")?;
-        writeln!(f, " href='./css/splot.css' />")
+    /// Get accessible label from titles
+    fn aria_label(&self, parts: &Parts) -> String {
+        let titles: Vec<_> = parts.titles.iter().map(|t| t.text()).collect();
+        titles.join(", ")
+    }
+
+    /// Get accessible description
+    fn desc(&self, parts: &Parts) -> String {
+        if let Some(description) = &self.description {
+            return description.to_string();
+        }
+        let mut sentences = Vec::with_capacity(2);
+        let axes: Vec<_> = parts
+            .axes
+            .iter()
+            .map(|a| a.opts().name())
+            .filter(|n| !n.is_empty())
+            .collect();
+        if !axes.is_empty() {
+            sentences.push(format!("Axes: {}.", axes.join(", ")));
+        }
+        let plots: Vec<_> = parts.plots.iter().map(|p| p.name()).collect();
+        if !plots.is_empty() {
+            sentences.push(format!("Plots: {}.", plots.join(", ")));
+        }
+        sentences.join(" ")
+    }
+
+    /// Get clip rectangle for plots
+    fn clip_rect(&self, parts: &Parts) -> Rect {
+        if parts.plots.iter().any(|p| p.has_markers()) {
+            parts.area.outset(MARKER_OUTSET)
+        } else {
+            parts.area
+        }
     }
 
     /// Render defs element
-    fn defs(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn defs(&self, f: &mut fmt::Formatter, parts: &Parts) -> fmt::Result {
         writeln!(f, "<defs>")?;
-        for i in 0..self.plots.len() {
-            write!(f, "<marker id='marker-{i}'")?;
-            write!(f, " class='plot-{i}'")?;
+        for (i, plot) in parts.plots.iter().enumerate() {
+            match self.marker_id(i) {
+                Some(marker) => write!(f, "<marker id='{marker}'")?,
+                None => write!(f, "<marker id='marker-{i}'")?,
+            }
+            plot.class_fmt(f, "")?;
+            plot.color_fmt(f, "fill")?;
             write!(f, " viewBox='-1 -1 2 2'")?;
             writeln!(f, " markerWidth='5' markerHeight='5'>")?;
-            writeln!(f, "{}", MARKERS[i % MARKERS.len()])?;
+            writeln!(f, "{}", MarkerShape::from_index(i).svg())?;
             writeln!(f, "</marker>")?;
         }
-        writeln!(f, "<clipPath id='clip-chart'>")?;
-        writeln!(f, "{}", self.area)?;
+        writeln!(f, "<clipPath id='{}'>", self.clip_id())?;
+        writeln!(f, "{}", self.clip_rect(parts))?;
         writeln!(f, "</clipPath>")?;
         writeln!(f, "</defs>")
     }
 
+    /// Render a background rectangle
+    fn background_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        class: &str,
+        rect: Rect,
+    ) -> fmt::Result {
+        write!(f, "<rect class='{class}'")?;
+        if self.inline {
+            write!(f, "{}", inline_attrs(class))?;
+        }
+        write!(f, " x='{}' y='{}'", rect.x, rect.y)?;
+        writeln!(f, " width='{}' height='{}'/>", rect.width, rect.height)
+    }
+
     /// Render the chart "body"
-    fn body(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for title in &self.titles {
+    fn body(&self, f: &mut fmt::Formatter, parts: &Parts) -> fmt::Result {
+        if self.background {
+            self.background_fmt(f, "chart-bg", self.aspect_ratio.rect())?;
+        }
+        if self.plot_background {
+            self.background_fmt(f, "plot-bg", parts.area)?;
+        }
+        let domain = parts.domain.bind(parts.area);
+        self.bands_fmt(f, &domain, parts.area, Layer::BelowPlots)?;
+        for title in &parts.titles[..parts.shown_titles] {
             writeln!(f, "{title}")?;
         }
-        for axis in &self.axes {
-            axis.render(f, self.area)?;
+        for axis in &parts.axes {
+            if !self.grid_on_top {
+                axis.render_grid(f, parts.area)?;
+            }
+            axis.render(f, parts.area)?;
         }
-        writeln!(f, "<g clip-path='url(#clip-chart)'>")?;
-        for plot in self.plots.iter() {
+        render_annotations(
+            f,
+            &self.annotations,
+            &domain,
+            Layer::BelowPlots,
+            self.inline,
+        )?;
+        writeln!(f, "<g clip-path='url(#{})'>", self.clip_id())?;
+        for plot in parts.plots.iter() {
             writeln!(f, "{plot}")?;
         }
+        if self.crosshairs {
+            self.crosshairs_fmt(f, parts)?;
+        }
+        writeln!(f, "</g>")?;
+        if self.grid_on_top {
+            for axis in &parts.axes {
+                axis.render_grid(f, parts.area)?;
+            }
+        }
+        self.bands_fmt(f, &domain, parts.area, Layer::AbovePlots)?;
+        render_annotations(
+            f,
+            &self.annotations,
+            &domain,
+            Layer::AbovePlots,
+            self.inline,
+        )
+    }
+
+    /// Render bands on one layer
+    fn bands_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        domain: &BoundDomain,
+        area: Rect,
+        layer: Layer,
+    ) -> fmt::Result {
+        for band in self.bands.iter().filter(|b| b.is_on(layer)) {
+            band.render(f, domain, area, self.inline)?;
+        }
+        Ok(())
+    }
+
+    /// Render crosshair targets, guides and labels
+    fn crosshairs_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        parts: &Parts,
+    ) -> fmt::Result {
+        let Some(plot) = parts.plots.first() else {
+            return Ok(());
+        };
+        let mut positions = plot.x_positions();
+        positions.sort_by(|a, b| a.0.total_cmp(&b.0));
+        positions.dedup_by(|a, b| a.0.round() == b.0.round());
+        if positions.len() > MAX_CROSSHAIRS {
+            return Ok(());
+        }
+        let area = parts.area;
+        let (top, height) = (area.y, area.height);
+        let attrs = |class_name| {
+            if self.inline {
+                inline_attrs(class_name)
+            } else {
+                ""
+            }
+        };
+        writeln!(f, "<g class='crosshairs'>")?;
+        for (i, (x, text)) in positions.iter().enumerate() {
+            let left = match i {
+                0 => f64::from(area.x),
+                _ => (positions[i - 1].0 + x) / 2.0,
+            };
+            let right = match positions.get(i + 1) {
+                Some((next, _)) => (x + next) / 2.0,
+                None => f64::from(area.right()),
+            };
+            let (left, right) = (left.round() as i32, right.round() as i32);
+            let x = x.round() as i32;
+            write!(f, "<rect class='crosshair-target'")?;
+            write!(f, "{} x='{left}' y='{top}'", attrs("crosshair-target"))?;
+            writeln!(f, " width='{}' height='{height}'/>", right - left)?;
+            write!(f, "<path class='crosshair'{}", attrs("crosshair"))?;
+            writeln!(f, " d='M{x} {top}v{height}'/>")?;
+            let label = Text::new(Edge::Top)
+                .class_name("crosshair-label")
+                .inline(self.inline);
+            label.display(f)?;
+            let tspan = Tspan::new(text).x(x).y(top).dy(1.33);
+            write!(f, "{tspan}")?;
+            label.display_done(f)?;
+        }
         writeln!(f, "</g>")
     }
 
+    /// Render a legend swatch for one entry
+    fn swatch(
+        f: &mut fmt::Formatter,
+        plot: &Plot,
+        entry: &LegendEntry,
+    ) -> fmt::Result {
+        match entry.plot_type {
+            PlotType::Area => {
+                write!(f, "<path")?;
+                plot.class_fmt(f, "legend-area")?;
+                plot.color_fmt(f, "fill")?;
+                writeln!(f, " d='M0 5h60v20h-60z'/>")
+            }
+            PlotType::Bar => {
+                write!(f, "<path")?;
+                plot.class_fmt(f, "legend-bar")?;
+                plot.color_fmt(f, "fill")?;
+                writeln!(f, " d='M15 0h30v30h-30z'/>")
+            }
+            PlotType::Range => {
+                write!(f, "<path")?;
+                plot.class_fmt(f, "legend-range")?;
+                plot.color_fmt(f, "fill")?;
+                writeln!(f, " d='M0 8h60v14h-60z'/>")
+            }
+            PlotType::Line => {
+                if plot.has_band() {
+                    write!(f, "<path")?;
+                    plot.class_fmt(f, "legend-band")?;
+                    plot.color_fmt(f, "fill")?;
+                    writeln!(f, " d='M0 5h60v20h-60z'/>")?;
+                }
+                write!(f, "<path")?;
+                plot.class_fmt(f, "legend-line")?;
+                plot.color_fmt(f, "stroke")?;
+                plot.stroke_fmt(f)?;
+                writeln!(f, " d='M0 15h30h30'/>")
+            }
+            PlotType::Scatter => {
+                let shape = entry
+                    .marker
+                    .unwrap_or(MarkerShape::from_index(entry.index));
+                write!(f, "<g")?;
+                plot.class_fmt(f, "legend-marker")?;
+                plot.color_fmt(f, "fill")?;
+                writeln!(f, " transform='translate(30 15) scale(10)'>")?;
+                writeln!(f, "{}", shape.svg())?;
+                writeln!(f, "</g>")
+            }
+        }
+    }
+
+    /// Get plot indices in legend order
+    ///
+    /// Stacked plots are reversed, to match their visual order.
+    fn legend_order(plots: &[Plot]) -> Vec<usize> {
+        let mut stacked =
+            (0..plots.len()).rev().filter(|i| plots[*i].is_stacked());
+        (0..plots.len())
+            .map(|i| {
+                if plots[i].is_stacked() {
+                    stacked.next().unwrap_or(i)
+                } else {
+                    i
+                }
+            })
+            .collect()
+    }
+
+    /// Get legend entries, in legend order
+    ///
+    /// These are the entries of the HTML legend, for rendering a legend
+    /// some other way.  Plots with an empty name or hidden from the legend
+    /// are skipped.
+    pub fn legend_entries(&self) -> Vec<LegendEntry> {
+        Self::plot_entries(&self.layout().plots)
+    }
+
+    /// Get legend entries of plots, in legend order
+    fn plot_entries(plots: &[Plot]) -> Vec<LegendEntry> {
+        Self::legend_order(plots)
+            .into_iter()
+            .filter(|i| plots[*i].in_legend())
+            .map(|i| {
+                let plot = &plots[i];
+                let plot_type = plot.plot_type();
+                let marker = match plot_type {
+                    PlotType::Scatter => Some(MarkerShape::from_index(i)),
+                    PlotType::Line if plot.has_markers() => {
+                        Some(MarkerShape::from_index(i))
+                    }
+                    _ => None,
+                };
+                LegendEntry {
+                    name: plot.name().to_string(),
+                    index: i,
+                    plot_type,
+                    marker,
+                    color: plot.color().map(str::to_string),
+                }
+            })
+            .collect()
+    }
+
     /// Render the legend as an HTML fragment
-    fn legend(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn legend(f: &mut fmt::Formatter, parts: &Parts) -> fmt::Result {
         writeln!(f, "<div class='legend'>")?;
-        for (i, plot) in self.plots.iter().enumerate() {
+        for entry in Self::plot_entries(&parts.plots) {
             writeln!(f, "<div>")?;
             writeln!(f, "<svg width='20' height='10' viewBox='0 0 60 30'>")?;
-            write!(f, "<path class='plot-{i} legend-line'")?;
-            writeln!(f, " d='M0 15h30h30'/>")?;
+            Self::swatch(f, &parts.plots[entry.index], &entry)?;
             writeln!(f, "</svg>")?;
-            writeln!(f, "{}", plot.name())?;
+            writeln!(f, "{}", escape(&entry.name))?;
             writeln!(f, "</div>")?;
         }
         writeln!(f, "</div>")
     }
 }
 
-impl<'a, P> fmt::Display for Chart<'a, P>
-where
-    P: IntoPoint,
-{
+/// Stand-alone SVG of a chart, with embedded CSS and no legend
+///
+/// CSS is omitted when the chart uses inline styles.
+#[cfg(feature = "raster")]
+struct EmbeddedSvg<'c, 'a>(&'c Chart<'a>);
+
+#[cfg(feature = "raster")]
+impl<'c, 'a> fmt::Display for EmbeddedSvg<'c, 'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.svg(f)?;
-        if self.stand_alone {
-            self.link(f)?;
+        // inline attributes would be overridden by CSS rules
+        let parts = self.0.layout();
+        if self.0.inline {
+            return self.0.svg_element(f, &parts, None);
+        }
+        let stylesheet = match &self.0.stylesheet {
+            Stylesheet::Embed(css) => Stylesheet::Embed(css.clone()),
+            Stylesheet::Link(_) => Stylesheet::Embed(DEFAULT_CSS.into()),
+        };
+        self.0.svg_element(f, &parts, Some(&stylesheet))
+    }
+}
+
+#[cfg(feature = "raster")]
+impl<'a> Chart<'a> {
+    /// Render as stand-alone SVG, with embedded CSS and no legend
+    pub(crate) fn embedded_svg(&self) -> String {
+        EmbeddedSvg(self).to_string()
+    }
+}
+
+/// Chart with classes not yet prefixed, and whether to add the legend
+struct UnprefixedChart<'c, 'a>(&'c Chart<'a>, bool);
+
+impl<'c, 'a> fmt::Display for UnprefixedChart<'c, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chart = self.0;
+        let prefix = &chart.class_prefix;
+        let prefixed;
+        let stylesheet = match &chart.stylesheet {
+            Stylesheet::Embed(css) if !prefix.is_empty() => {
+                prefixed = Stylesheet::Embed(prefix_css(css, prefix).into());
+                &prefixed
+            }
+            stylesheet => stylesheet,
+        };
+        let stylesheet = chart.stand_alone.then_some(stylesheet);
+        let parts = chart.layout();
+        chart.svg_element(f, &parts, stylesheet)?;
+        if self.1 {
+            Chart::legend(f, &parts)?;
+        }
+        Ok(())
+    }
+}
+
+/// Chart with classes prefixed, and whether to add the legend
+struct PrefixedChart<'c, 'a>(&'c Chart<'a>, bool);
+
+impl<'c, 'a> fmt::Display for PrefixedChart<'c, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (chart, legend) = (self.0, self.1);
+        if chart.class_prefix.is_empty() {
+            write!(f, "{}", UnprefixedChart(chart, legend))
+        } else {
+            let mut writer = ClassPrefix::new(f, &chart.class_prefix);
+            fmt::Write::write_fmt(
+                &mut writer,
+                format_args!("{}", UnprefixedChart(chart, legend)),
+            )
         }
-        self.defs(f)?;
-        self.body(f)?;
-        writeln!(f, "</svg>")?;
-        self.legend(f)
+    }
+}
+
+impl<'a> fmt::Display for Chart<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", PrefixedChart(self, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secondary_domain() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let big = [(0.0, 100.0), (1.0, 300.0)];
+        let svg = Chart::new()
+            .domain(&data[..])
+            .plot(Plot::line("A", &data[..]))
+            .plot(Plot::line("B", &big[..]).domain(&big[..]))
+            .to_string();
+        assert!(svg.contains("plot-0 plot-line' d='M40 1460 1960 40'"));
+        assert!(svg.contains("plot-1 plot-line' d='M40 1460 1960 40'"));
+        let svg = Chart::new()
+            .domain(&data[..])
+            .plot(Plot::line("A", &data[..]))
+            .plot_in(&big[..], Plot::line("B", &big[..]))
+            .to_string();
+        assert!(svg.contains("plot-1 plot-line' d='M40 1460 1960 40'"));
+        let svg = Chart::new()
+            .domain(&data[..])
+            .axis_for(&big[..], "R", Edge::Right)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(svg.contains(">300</tspan>"));
+        assert!(!svg.contains("grid-y"));
+    }
+
+    #[test]
+    fn palette() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .palette(Palette::default())
+            .plot(Plot::line("A", &data[..]))
+            .plot(Plot::area("B", &data[..]))
+            .to_string();
+        assert!(
+            svg.contains("<marker id='marker-0' class='plot-0' fill='#4E79A7'")
+        );
+        assert!(svg.contains("class='plot-0 plot-line' stroke='#4E79A7'"));
+        assert!(svg.contains("class='plot-1 plot-area' fill='#F28E2C'"));
+        let svg = Chart::new().plot(Plot::line("A", &data[..])).to_string();
+        assert!(!svg.contains("stroke='#"));
+        let svg = Chart::new()
+            .palette(Palette::new(&["red' onload='x"]))
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(svg.contains(" stroke='red&apos; onload=&apos;x'"));
+    }
+
+    #[test]
+    fn custom_aspect() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .aspect_ratio(AspectRatio::Custom(4000, 800))
+            .title("Strip")
+            .axis("", Edge::Bottom)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(svg.contains("viewBox='0 0 4000 800'"));
+        assert!(
+            svg.contains("<rect x='40' y='140' width='3920' height='540'/>")
+        );
+    }
+
+    #[test]
+    fn background() {
+        let chart = || Chart::new().axis("", Edge::Left);
+        let svg = chart().to_string();
+        assert!(!svg.contains("-bg"));
+        let svg = chart().background().plot_background().to_string();
+        let chart_bg = svg.find("<rect class='chart-bg' x='0' y='0'").unwrap();
+        let plot_bg = svg.find("<rect class='plot-bg'").unwrap();
+        let axis = svg.find("class='grid-y'").unwrap();
+        assert!(svg.find("</defs>").unwrap() < chart_bg);
+        assert!(chart_bg < plot_bg && plot_bg < axis);
+    }
+
+    #[test]
+    fn owned_names() {
+        fn chart(host: &str) -> Chart<'static> {
+            Chart::new()
+                .title(format!("Latency p99 on {host}"))
+                .axis(format!("{host} (ms)"), Edge::Left)
+                .plot(Plot::line_owned(host.to_string(), [(0.0, 1.0)]))
+        }
+        let svg = chart("db1").to_string();
+        assert!(svg.contains("Latency p99 on db1"));
+        assert!(svg.contains("db1 (ms)"));
+        assert!(svg.contains("db1\n</div>"));
+    }
+
+    #[test]
+    fn call_order() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .margin(20)
+            .palette(Palette::default())
+            .title("Title")
+            .domain(&data[..])
+            .axis("X", Edge::Bottom)
+            .axis_for(&data[..], "Y", Edge::Right)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        let reordered = Chart::new()
+            .plot(Plot::line("A", &data[..]))
+            .axis("X", Edge::Bottom)
+            .domain(&data[..])
+            .axis_for(&data[..], "Y", Edge::Right)
+            .title("Title")
+            .palette(Palette::default())
+            .margin(20)
+            .to_string();
+        assert_eq!(svg, reordered);
+    }
+
+    #[test]
+    fn include_zero() {
+        let data = [(0.0, 95.0), (1.0, 100.0)];
+        let render = |plot: Plot| {
+            Chart::new()
+                .margin(0)
+                .aspect_ratio(AspectRatio::Custom(100, 100))
+                .domain(&data[..])
+                .plot(plot)
+                .to_string()
+        };
+        let svg = render(Plot::area("A", &data[..]));
+        assert!(svg.contains("d='M0 100 0 5 100 0 100 100'"));
+        let svg = render(Plot::bar("B", &data[..]));
+        assert!(svg.contains("d='M5 100 5 5 45 5 45 100zM55 100 55 0 95 0"));
+        let svg = render(Plot::bar("B", &data[..]).without_zero());
+        assert!(svg.contains("d='M5 100 5 100 45 100 45 100zM55 100 55 0"));
+        let svg = render(Plot::area("A", &data[..]).without_zero());
+        assert!(svg.contains("d='M0 100 0 100 100 0 100 100'"));
+        let svg = render(Plot::line("L", &data[..]));
+        assert!(svg.contains("d='M0 100 100 0'"));
+    }
+
+    #[test]
+    fn categories() {
+        let data = [(0.0, 10.0), (1.0, 7.0), (2.0, 4.0)];
+        let svg = Chart::new()
+            .domain(Domain::default().set_x_categories(&["apples", "oranges"]))
+            .axis("", Edge::Bottom)
+            .plot(Plot::bar("Fruit", &data[..]))
+            .to_string();
+        assert!(svg.contains(">oranges</tspan>"));
+        assert!(svg.contains(">2</tspan>"));
+        let far = [(0.0, 10.0), (1.7e9, 7.0)];
+        let svg = Chart::new()
+            .domain(Domain::default().set_x_categories(&["apples", "oranges"]))
+            .axis("", Edge::Bottom)
+            .plot(Plot::bar("Fruit", &far[..]))
+            .to_string();
+        assert_eq!(svg.matches("<tspan").count(), 2);
+    }
+
+    #[test]
+    fn accessibility() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .title("Rock & <Roll>")
+            .axis("X", Edge::Bottom)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert_eq!(svg.matches("role='img'").count(), 1);
+        assert_eq!(
+            svg.matches(" aria-label='Rock &amp; &lt;Roll&gt;'").count(),
+            1
+        );
+        assert_eq!(svg.matches("<desc>Axes: X. Plots: A.</desc>").count(), 1);
+        let svg = Chart::new()
+            .title("Title")
+            .description("It's \"quoted\"")
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert_eq!(svg.matches("<desc>").count(), 1);
+        assert!(svg.contains("<desc>It&apos;s &quot;quoted&quot;</desc>"));
+    }
+
+    #[test]
+    fn render_size() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .render_size("100%", "300")
+            .preserve_aspect_ratio("xMidYMid meet")
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(svg.contains(
+            "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 2000 1500' \
+            width='100%' height='300' preserveAspectRatio='xMidYMid meet'"
+        ));
+    }
+
+    #[test]
+    fn margin() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = Chart::new()
+            .margin_edges(0, 200, 10, 0)
+            .plot(Plot::line("A", &data[..]));
+        assert_eq!(chart.layout().area, Rect::new(0, 0, 1800, 1490));
+        let chart = Chart::new().margin(0).aspect_ratio(AspectRatio::Square);
+        assert_eq!(chart.layout().area, Rect::new(0, 0, 2000, 2000));
+    }
+
+    #[test]
+    fn unsigned_points() {
+        let data = [(3u64, 7u64), (5, 2), (u64::MAX, 9)];
+        let svg = Chart::new()
+            .domain(&data[..2])
+            .axis("X", Edge::Bottom)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(svg.contains("<path class='plot-0 plot-line'"));
+    }
+
+    #[test]
+    fn mixed_points() {
+        use crate::point::Point;
+        let data: Vec<(i64, f32)> =
+            vec![(1_700_000_000, 1.5), (1_700_000_060, 2.5)];
+        let svg = Chart::new()
+            .domain(&data[..])
+            .axis("X", Edge::Bottom)
+            .plot(Plot::line("A", &data[..]))
+            .to_string();
+        assert!(svg.contains("<path class='plot-0 plot-line'"));
+        let pt: Point = (&3u8, &0.5f64).into();
+        assert_eq!(pt, Point { x: 3.0, y: 0.5 });
+    }
+
+    #[test]
+    fn optional_points() {
+        let data = [(0, Some(1.0)), (1, None), (2, Some(3.0)), (3, Some(2.0))];
+        let chart = Chart::new()
+            .domain(&data[..])
+            .plot(Plot::line("A", &data[..]))
+            .plot(Plot::scatter("B", &data[..]));
+        let rect = Rect::new(0, 0, 100, 100);
+        let present = [(0.0, 1.0), (2.0, 3.0), (3.0, 2.0)];
+        let a = chart.domain.bind(rect);
+        let b = Domain::from(&present[..]).bind(rect);
+        assert_eq!(a.y_map(3.0), b.y_map(3.0));
+        assert_eq!(a.y_map(1.0), b.y_map(1.0));
+        let svg = chart.to_string();
+        let line = svg.split("plot-line").nth(1).unwrap();
+        let d = line
+            .split("d='")
+            .nth(1)
+            .unwrap()
+            .split('\'')
+            .next()
+            .unwrap();
+        assert_eq!(d.matches('M').count(), 2);
+        let scatter = svg.split("plot-scatter").nth(1).unwrap();
+        let d = scatter.split("d='").nth(1).unwrap().split('\'').next();
+        assert_eq!(d.unwrap().split_whitespace().count(), 6);
+    }
+
+    #[test]
+    fn swatches() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let svg = Chart::new()
+            .plot(Plot::area("A", &data[..]))
+            .plot(Plot::line("B", &data[..]))
+            .plot(Plot::scatter("C", &data[..]))
+            .to_string();
+        let legend = &svg[svg.find("<div class='legend'>").unwrap()..];
+        assert!(
+            legend.contains("class='plot-0 legend-area' d='M0 5h60v20h-60z'")
+        );
+        assert!(legend.contains("class='plot-1 legend-line' d='M0 15h30h30'"));
+        assert!(legend.contains("<g class='plot-2 legend-marker'"));
+        assert!(legend.contains(MARKERS[2]));
+        let band = [(0.0, 1.0, 0.5, 1.5), (1.0, 2.0, 1.5, 2.5)];
+        let svg = Chart::new()
+            .plot(Plot::line_with_band("D", &band[..]))
+            .to_string();
+        let legend = &svg[svg.find("<div class='legend'>").unwrap()..];
+        let band = legend.find("class='plot-0 legend-band'").unwrap();
+        assert!(band < legend.find("class='plot-0 legend-line'").unwrap());
+    }
+
+    #[test]
+    fn legend_entries() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = Chart::new()
+            .palette(Palette::colorblind())
+            .plot(Plot::area("A", &data[..]))
+            .plot(Plot::line("B", &data[..]).markers())
+            .plot(Plot::scatter("C", &data[..]));
+        let entries = chart.legend_entries();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].name, "A");
+        assert_eq!(entries[0].plot_type, PlotType::Area);
+        assert_eq!(entries[0].marker, None);
+        assert_eq!(entries[0].color.as_deref(), Some("#E69F00"));
+        assert_eq!(entries[1].marker, Some(MarkerShape::Square));
+        assert_eq!(entries[2].index, 2);
+        assert_eq!(entries[2].plot_type, PlotType::Scatter);
+        assert_eq!(entries[2].marker, Some(MarkerShape::TriangleUp));
+        assert_eq!(Chart::new().legend_entries(), vec![]);
+        let svg = Chart::new()
+            .plot(Plot::line("<b> & c", &data[..]))
+            .to_string();
+        assert!(svg.contains("&lt;b&gt; &amp; c\n</div>"));
+    }
+
+    #[test]
+    fn validate() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = || {
+            Chart::new()
+                .title("Title")
+                .axis("X", Edge::Bottom)
+                .plot(Plot::line("A", &data[..]))
+        };
+        assert_eq!(chart().validate(), Ok(()));
+        let zero = chart().aspect_ratio(AspectRatio::Custom(800, 0));
+        assert_eq!(zero.validate(), Err(ChartError::ZeroSize));
+        assert!(zero.to_string().contains("viewBox='0 0 800 0'"));
+        let margin = chart().margin_edges(0, 1000, 0, 1000);
+        assert_eq!(margin.validate(), Err(ChartError::NoPlotArea));
+    }
+
+    #[test]
+    fn over_constrained() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let mut chart = Chart::new().aspect_ratio(AspectRatio::Portrait);
+        for i in 0..30 {
+            chart = chart.title(format!("Title {i}"));
+        }
+        let chart = chart
+            .axis("Top", Edge::Top)
+            .axis("Left", Edge::Left)
+            .axis("Bottom", Edge::Bottom)
+            .axis("Right", Edge::Right)
+            .plot(Plot::line("A", &data[..]));
+        let shown_titles = chart.layout().shown_titles;
+        assert!(!chart.layout().area.is_empty());
+        assert!(shown_titles < 30);
+        let svg = chart.to_string();
+        assert!(!svg.contains("6553"));
+        let titles = svg.matches("<text class='title'").count();
+        assert!(titles > 0);
+        assert_eq!(titles, shown_titles);
+        assert_eq!(chart.validate(), Err(ChartError::OverConstrained));
+    }
+
+    #[test]
+    fn layers() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = || {
+            Chart::new()
+                .domain(&data[..])
+                .axis("", Edge::Left)
+                .plot(Plot::area("A", &data[..]))
+        };
+        let svg = chart().to_string();
+        let plots = svg.find("<g clip-path").unwrap();
+        assert!(svg.find("class='grid-y'").unwrap() < plots);
+        let svg = chart()
+            .grid_on_top()
+            .band(Band::vertical(0.2, 0.4).layer(Layer::AbovePlots))
+            .annotation(
+                Annotation::new(0.5, 1.5, "note").layer(Layer::BelowPlots),
+            )
+            .to_string();
+        let plots = svg.find("<g clip-path").unwrap();
+        assert!(svg.find("class='grid-y'").unwrap() > plots);
+        assert!(svg.find("<rect class='band'").unwrap() > plots);
+        assert!(svg.find("class='annotation'").unwrap() < plots);
+    }
+
+    #[test]
+    fn class_prefix() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = || {
+            Chart::new()
+                .title("Title")
+                .axis("X", Edge::Bottom)
+                .plot(Plot::line("A", &data[..]))
+        };
+        let plain = chart().to_string();
+        let svg = chart().class_prefix("splot-").to_string();
+        assert_eq!(
+            plain.matches("class='").count(),
+            svg.matches("class='splot-").count()
+        );
+        assert!(svg.contains("class='splot-plot-0 splot-plot-line'"));
+        assert!(svg.contains("<div class='splot-legend'>"));
+        assert!(svg.contains("<text class='splot-title'"));
+        let svg = chart().class_prefix("splot-").embed_css().to_string();
+        assert!(svg.contains(".splot-tick {"));
+        assert!(!svg.contains(".tick {"));
+    }
+
+    #[test]
+    fn legend_hidden() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = Chart::new()
+            .plot(Plot::line("", &data[..]))
+            .plot(Plot::line("B", &data[..]).hide_from_legend())
+            .plot(Plot::scatter("C", &data[..]));
+        let entries = chart.legend_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].index, 2);
+        let svg = chart.to_string();
+        let legend = &svg[svg.find("<div class='legend'>").unwrap()..];
+        assert_eq!(legend.matches("<svg ").count(), 1);
+        assert!(legend.contains("<g class='plot-2 legend-marker'"));
+        assert!(svg.contains("<path class='plot-1 plot-line'"));
+    }
+
+    #[test]
+    fn precision() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (3.0, 2.0)];
+        let chart = |decimals| {
+            Chart::new()
+                .precision(decimals)
+                .domain(&data[..])
+                .axis("X", Edge::Bottom)
+                .axis("Y", Edge::Left)
+                .plot(Plot::line("A", &data[..]))
+                .to_string()
+        };
+        assert_eq!(
+            chart(0),
+            Chart::new()
+                .domain(&data[..])
+                .axis("X", Edge::Bottom)
+                .axis("Y", Edge::Left)
+                .plot(Plot::line("A", &data[..]))
+                .to_string()
+        );
+        let svg = chart(1);
+        assert!(svg.contains("d='M228.0 1300.0 805.3 670.0 1960.0 40.0'"));
+        assert!(svg.contains("d='M228.0 40.0v1260M516.7 40.0v1260M805.3 "));
+        assert!(svg.contains("d='M228.0 1300.0v20M516.7 1300.0v20M805.3 "));
+        let late = Chart::new()
+            .domain(&data[..])
+            .axis("X", Edge::Bottom)
+            .axis("Y", Edge::Left)
+            .plot(Plot::line("A", &data[..]))
+            .precision(1)
+            .to_string();
+        assert_eq!(svg, late);
+    }
+
+    #[test]
+    fn stand_alone() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = || Chart::new().plot(Plot::line("A", &data[..]));
+        let svg = chart().to_string();
+        let inner = chart().stand_alone(false).to_string();
+        assert!(svg.starts_with("<svg xmlns='http://www.w3.org/2000/svg' "));
+        assert!(svg.contains("<link"));
+        assert!(inner.starts_with("<svg viewBox="));
+        assert!(!inner.contains("<link"));
+        let link = svg.find("<link").unwrap();
+        let end = link + svg[link..].find('\n').unwrap() + 1;
+        let svg = format!("{}{}", &svg[..link], &svg[end..]);
+        let svg = svg.replace(" xmlns='http://www.w3.org/2000/svg'", "");
+        assert_eq!(svg, inner);
+    }
+
+    #[test]
+    fn pretty() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = Chart::new()
+            .embed_css()
+            .title("T")
+            .axis("X", Edge::Bottom)
+            .plot(Plot::line("A", &data[..]).markers().tooltips());
+        let pretty = chart.to_string_pretty();
+        assert!(pretty.contains("\n  <defs>\n    <marker "));
+        assert!(pretty.contains("\n      <circle class='plot-point'"));
+        assert!(pretty.contains("\n  </defs>\n"));
+        assert!(pretty.contains("\n.plot-0 {\n  --color"));
+        let trim = |s: &str| -> String {
+            s.split_inclusive('\n').map(str::trim_start).collect()
+        };
+        assert_eq!(trim(&pretty), trim(&chart.to_string()));
+    }
+
+    #[test]
+    fn save_svg() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let path = std::env::temp_dir().join("splot-chart-save.svg");
+        Chart::new()
+            .plot(Plot::line("A & B", &data[..]))
+            .save_svg(&path)
+            .unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(!svg.contains("<div"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn write_to() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = Chart::new()
+            .title("T")
+            .axis("X", Edge::Bottom)
+            .plot(Plot::line("A", &data[..]));
+        let mut buf = Vec::new();
+        chart.write_to(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), chart.to_string());
+    }
+
+    #[test]
+    fn interactive() {
+        let data = [(0.0, 1.0), (f64::NAN, f64::NAN), (1.0, 2.0)];
+        let svg = Chart::new()
+            .id_prefix("cpu-")
+            .plot(Plot::line("A", &data[..]).interactive())
+            .plot(Plot::scatter("B", &data[..]).interactive())
+            .plot(Plot::line("C", &data[..]))
+            .to_string();
+        assert!(svg.contains("<g id='cpu-plot-0' class='plot-0 series-hover'>"));
+        assert!(svg.contains("<path id='cpu-plot-0-path' class='plot-0"));
+        assert!(svg.contains("<circle id='cpu-plot-0-pt-0' class='plot-point'"));
+        assert!(svg.contains("<circle id='cpu-plot-0-pt-2' class='plot-point'"));
+        assert!(!svg.contains("pt-1'"));
+        assert!(svg.contains("<path id='cpu-plot-1-pt-2' class='plot-1"));
+        assert!(!svg.contains("cpu-plot-2"));
+        assert_eq!(svg.matches("series-hover").count(), 2);
+        let html = crate::Page::new()
+            .chart(Chart::new().plot(Plot::line("A", &data[..]).interactive()))
+            .chart(Chart::new().plot(Plot::line("A", &data[..]).interactive()))
+            .to_string();
+        assert!(html.contains("id='plot-0-path'"));
+        assert!(html.contains("id='plot-1-0-path'"));
+    }
+
+    #[test]
+    fn crosshairs() {
+        let data = [(0.0, 1.0), (1.0, 2.0), (3.0, 2.0)];
+        let svg = Chart::new()
+            .margin(0)
+            .aspect_ratio(AspectRatio::Custom(300, 100))
+            .domain(&data[..])
+            .plot(Plot::line("A", &data[..]))
+            .crosshairs()
+            .to_string();
+        assert!(svg.contains(
+            "<rect class='crosshair-target' x='50' y='0' width='150' \
+            height='100'/>\n<path class='crosshair' d='M100 0v100'/>\n\
+            <text class='crosshair-label' text-anchor='middle'>\n\
+            <tspan x='100' y='0' dy='1.33em'>(1 2)</tspan>"
+        ));
+        assert_eq!(svg.matches("<rect class='crosshair-target'").count(), 3);
+        let many: Vec<_> = (0..400).map(|i| (f64::from(i), 1.0)).collect();
+        let svg = Chart::new()
+            .plot(Plot::line("A", &many[..]))
+            .crosshairs()
+            .to_string();
+        assert!(!svg.contains("crosshair"));
+    }
+
+    #[test]
+    fn animate() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = || Chart::new().plot(Plot::line("A", &data[..]).dashed());
+        let svg = chart().to_string();
+        assert!(!svg.contains("<animate"));
+        assert!(!svg.contains("pathLength"));
+        let svg = chart()
+            .plot(Plot::line("B", &data[..]).animate(2.0))
+            .plot(Plot::line("C", &data[..]).animate(1.5).tooltips())
+            .to_string();
+        assert_eq!(svg.matches("<animate ").count(), 2);
+        assert_eq!(svg.matches("pathLength='1'").count(), 2);
+        assert!(svg.contains(
+            "<path class='plot-1 plot-line' pathLength='1' \
+            stroke-dasharray='1' stroke-dashoffset='1'"
+        ));
+        assert!(svg.contains(
+            "'><animate attributeName='stroke-dashoffset' from='1' to='0' \
+            dur='2s' begin='0s' fill='freeze'/></path>"
+        ));
+        assert!(svg.contains(
+            "'><title>C</title><animate attributeName='stroke-dashoffset' \
+            from='1' to='0' dur='1.5s' begin='0.25s' fill='freeze'/></path>"
+        ));
+        let svg = Chart::new()
+            .plot(Plot::line("<D>", &data[..]).animate(1.0).tooltips())
+            .to_string();
+        assert!(svg.contains("'><title>&lt;D&gt;</title><animate "));
     }
 }
@@ -2,10 +2,13 @@
 //
 // Copyright (c) 2021-2024  Douglas P Lau
 //
-use crate::axis::Axis;
-use crate::point::IntoPoint;
+use crate::axis::{Axis, AxisOpts};
+use crate::format::{NumFormat, NumberFormat, TickFormat};
+use crate::point::{BandPoint, IntoPoint, Point, RangePoint};
 use crate::rect::{Edge, Rect};
-use crate::scale::Scale;
+use crate::scale::{Categorical, Scale};
+use crate::text::Tick;
+use std::ops::Range;
 
 /// Data domain in two dimensions
 ///
@@ -17,6 +20,8 @@ use crate::scale::Scale;
 pub struct Domain {
     x_scale: Scale,
     y_scale: Scale,
+    x_format: NumFormat,
+    y_format: NumFormat,
 }
 
 /// Domain bound to a rectangle
@@ -33,11 +38,95 @@ where
     fn from(data: &[P]) -> Self {
         let x_scale = Scale::from_data(data, |pt| pt.x);
         let y_scale = Scale::from_data(data, |pt| pt.y);
-        Domain { x_scale, y_scale }
+        Domain {
+            x_scale,
+            y_scale,
+            ..Default::default()
+        }
     }
 }
 
 impl Domain {
+    /// Create a domain from an iterator of points
+    pub fn from_data<I, P>(data: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: IntoPoint,
+    {
+        let data: Vec<Point> = data.into_iter().map(|pt| pt.into()).collect();
+        Domain::from(&data[..])
+    }
+
+    /// Create a domain from explicit `X` and `Y` ranges
+    ///
+    /// ```rust
+    /// use splot::Domain;
+    ///
+    /// let domain = Domain::from_ranges(0..24, 0.0..100.0);
+    /// ```
+    pub fn from_ranges<X, Y>(x: Range<X>, y: Range<Y>) -> Self
+    where
+        X: Into<f64>,
+        Y: Into<f64>,
+    {
+        Domain::default().set_x_range(x).set_y_range(y)
+    }
+
+    /// Create a domain from `X` and `Y` scales
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_scales(x_scale: Scale, y_scale: Scale) -> Self {
+        Domain {
+            x_scale,
+            y_scale,
+            ..Default::default()
+        }
+    }
+
+    /// Create a domain from band data, including low and high values
+    pub fn from_band_data<I, B>(data: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Into<BandPoint>,
+    {
+        Domain::from_data(data.into_iter().flat_map(|pt| {
+            let pt = pt.into();
+            [(pt.x, pt.y), (pt.x, pt.lo), (pt.x, pt.hi)]
+        }))
+    }
+
+    /// Create a domain from range data, including all starts and ends
+    pub fn from_range_data<I, R>(data: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<RangePoint>,
+    {
+        Domain::from_data(data.into_iter().flat_map(|pt| {
+            let pt = pt.into();
+            [(pt.start, pt.row), (pt.end, pt.row)]
+        }))
+    }
+
+    /// Create a domain with a temporal `X` scale
+    ///
+    /// `X` values are Unix timestamps (seconds), as produced from `chrono`
+    /// types with the `chrono` feature.  Ticks are formatted as dates /
+    /// times.
+    pub fn from_time_data<P>(data: &[P]) -> Self
+    where
+        P: IntoPoint,
+    {
+        Domain::from(data).time_x()
+    }
+
+    /// Create a domain from records, with a point accessor
+    pub fn from_data_with<T, F, P>(records: &[T], map: F) -> Self
+    where
+        F: Fn(&T) -> P,
+        P: IntoPoint,
+    {
+        Domain::from_data(records.iter().map(map))
+    }
+
     /// Adjust domain to include a set of points
     pub fn including<P>(mut self, data: &[P]) -> Self
     where
@@ -66,25 +155,345 @@ impl Domain {
         self
     }
 
+    /// Set `X` domain to a range of values
+    ///
+    /// An empty range (start equal to end) is expanded, as with a single
+    /// data point.
+    pub fn set_x_range<X>(mut self, x: Range<X>) -> Self
+    where
+        X: Into<f64>,
+    {
+        self.x_scale = Scale::from_range(x.start.into(), x.end.into());
+        self
+    }
+
+    /// Set `Y` domain to a range of values
+    ///
+    /// An empty range (start equal to end) is expanded, as with a single
+    /// data point.
+    pub fn set_y_range<Y>(mut self, y: Range<Y>) -> Self
+    where
+        Y: Into<f64>,
+    {
+        self.y_scale = Scale::from_range(y.start.into(), y.end.into());
+        self
+    }
+
+    /// Create a union with another domain
+    ///
+    /// The result covers both domains, keeping tick label formats of `self`.
+    pub fn union(&self, rhs: &Domain) -> Domain {
+        Domain {
+            x_scale: self.x_scale.union(rhs.x_scale.clone()),
+            y_scale: self.y_scale.union(rhs.y_scale.clone()),
+            x_format: self.x_format.clone(),
+            y_format: self.y_format.clone(),
+        }
+    }
+
+    /// Set `X` domain to a list of categories
+    ///
+    /// Each category is mapped to an evenly spaced band.  Data points address
+    /// categories by index, starting from 0.  Indices past the end are
+    /// appended as categories named by index, when the domain includes their
+    /// points or a `Chart` plots them.
+    pub fn set_x_categories<S>(mut self, categories: &[S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.x_scale = Scale::Categorical(Categorical::new(categories));
+        self
+    }
+
+    /// Get the `X` value of a category by name
+    ///
+    /// Returns `None` for an unknown category.
+    pub fn x_category(&self, name: &str) -> Option<f64> {
+        self.x_scale.category(name).map(|i| i as f64)
+    }
+
+    /// Adjust `X` tick spacing to approximately `count` intervals
+    pub fn with_x_ticks(mut self, count: usize) -> Self {
+        self.x_scale = self.x_scale.with_ticks(count);
+        self
+    }
+
+    /// Adjust `Y` tick spacing to approximately `count` intervals
+    pub fn with_y_ticks(mut self, count: usize) -> Self {
+        self.y_scale = self.y_scale.with_ticks(count);
+        self
+    }
+
+    /// Set explicit `X` tick spacing
+    pub fn x_tick_step(mut self, step: f64) -> Self {
+        self.x_scale = self.x_scale.with_step(step);
+        self
+    }
+
+    /// Set explicit `Y` tick spacing
+    pub fn y_tick_step(mut self, step: f64) -> Self {
+        self.y_scale = self.y_scale.with_step(step);
+        self
+    }
+
+    /// Set the number of decimal digits for `X` tick labels
+    ///
+    /// By default, this is derived from the tick spacing.
+    pub fn x_precision(mut self, digits: usize) -> Self {
+        self.x_format.precision = Some(digits);
+        self
+    }
+
+    /// Set the number of decimal digits for `Y` tick labels
+    ///
+    /// By default, this is derived from the tick spacing.
+    pub fn y_precision(mut self, digits: usize) -> Self {
+        self.y_format.precision = Some(digits);
+        self
+    }
+
+    /// Set the `X` tick label format
+    pub fn x_tick_format(mut self, tick_format: TickFormat) -> Self {
+        self.x_format.tick_format = tick_format;
+        self
+    }
+
+    /// Set the `Y` tick label format
+    pub fn y_tick_format(mut self, tick_format: TickFormat) -> Self {
+        self.y_format.tick_format = tick_format;
+        self
+    }
+
+    /// Set the `X` tick label separators
+    pub fn x_number_format(mut self, number: NumberFormat) -> Self {
+        self.x_format.number = number;
+        self
+    }
+
+    /// Set the `Y` tick label separators
+    pub fn y_number_format(mut self, number: NumberFormat) -> Self {
+        self.y_format.number = number;
+        self
+    }
+
+    /// Set the tick label separators for both `X` and `Y`
+    ///
+    /// These also apply to point labels.
+    pub fn number_format(self, number: NumberFormat) -> Self {
+        self.x_number_format(number).y_number_format(number)
+    }
+
+    /// Create a domain from data, padded by 5% on all sides
+    pub fn from_data_padded<P>(data: &[P]) -> Self
+    where
+        P: IntoPoint,
+    {
+        Domain::from(data).pad(0.05)
+    }
+
+    /// Pad both dimensions by a fraction of the span on each end
+    ///
+    /// This keeps points from sitting on the plot border.
+    pub fn pad(self, fraction: f64) -> Self {
+        self.pad_x(fraction).pad_y(fraction)
+    }
+
+    /// Pad `X` dimension by a fraction of the span on each end
+    pub fn pad_x(mut self, fraction: f64) -> Self {
+        self.x_scale = self.x_scale.padded(fraction);
+        self
+    }
+
+    /// Pad `Y` dimension by a fraction of the span on each end
+    pub fn pad_y(mut self, fraction: f64) -> Self {
+        self.y_scale = self.y_scale.padded(fraction);
+        self
+    }
+
+    /// Extend the `X` domain to include zero
+    pub fn include_zero_x(mut self) -> Self {
+        self.x_scale = self.x_scale.including(0.0);
+        self
+    }
+
+    /// Extend the `Y` domain to include zero
+    ///
+    /// Area and bar plots in a `Chart` do this by default; see
+    /// `Plot::without_zero`.
+    pub fn include_zero_y(mut self) -> Self {
+        self.y_scale = self.y_scale.including(0.0);
+        self
+    }
+
+    /// Format `X` tick labels as percentages
+    ///
+    /// Values are multiplied by 100 for display only; a ratio of `0.25` is
+    /// labeled `25%`.
+    pub fn percent_x(mut self) -> Self {
+        self.x_format.percent = true;
+        self.x_scale = self.x_scale.percent_spacing();
+        self
+    }
+
+    /// Format `Y` tick labels as percentages
+    ///
+    /// Values are multiplied by 100 for display only; a ratio of `0.25` is
+    /// labeled `25%`.
+    pub fn percent_y(mut self) -> Self {
+        self.y_format.percent = true;
+        self.y_scale = self.y_scale.percent_spacing();
+        self
+    }
+
+    /// Flip the `X` direction, so values increase to the left
+    pub fn flip_x(mut self) -> Self {
+        self.x_scale = self.x_scale.inverted();
+        self
+    }
+
+    /// Flip the `Y` direction, so values increase downward
+    pub fn flip_y(mut self) -> Self {
+        self.y_scale = self.y_scale.inverted();
+        self
+    }
+
+    /// Use a logarithmic `X` scale
+    ///
+    /// Call this after setting the `X` domain data.  Values which are zero or
+    /// negative are clamped to the start of the scale.
+    pub fn log_x(mut self) -> Self {
+        self.x_scale = self.x_scale.log();
+        self
+    }
+
+    /// Use a logarithmic `Y` scale
+    ///
+    /// Call this after setting the `Y` domain data.  Values which are zero or
+    /// negative are clamped to the start of the scale.
+    pub fn log_y(mut self) -> Self {
+        self.y_scale = self.y_scale.log();
+        self
+    }
+
+    /// Use a temporal `X` scale
+    ///
+    /// Values are interpreted as Unix timestamps (seconds).  Call this after
+    /// setting the `X` domain data.
+    pub fn time_x(mut self) -> Self {
+        self.x_scale = self.x_scale.temporal();
+        self
+    }
+
+    /// Set the tick format of a temporal `X` scale
+    ///
+    /// The format uses `strftime`-like specifiers, such as `"%H:%M"`.
+    /// A newline splits labels into lines, such as `"%b %e\n%Y"`.
+    pub fn time_format(mut self, format: &str) -> Self {
+        self.x_scale.time_format(format);
+        self
+    }
+
+    /// Add a second line to temporal `X` tick labels on larger boundaries
+    ///
+    /// For example, ticks at midnight also show the date.
+    pub fn time_boundaries(mut self) -> Self {
+        self.x_scale.time_boundaries();
+        self
+    }
+
     /// Get axis on one edge
-    pub(crate) fn axis<'a>(&self, name: &'a str, edge: Edge) -> Axis<'a> {
-        match edge {
-            Edge::Bottom | Edge::Top => {
-                Axis::new(name, edge, self.x_scale.ticks())
-            }
+    pub(crate) fn axis<'a>(&self, opts: AxisOpts<'a>) -> Axis<'a> {
+        let (scale, format) = match opts.edge() {
+            Edge::Bottom | Edge::Top => (self.x_scale.clone(), &self.x_format),
             Edge::Left | Edge::Right => {
-                Axis::new(name, edge, self.y_scale.inverted().ticks())
+                (self.y_scale.inverted(), &self.y_format)
             }
+        };
+        let zero = if opts.has_zero_line() {
+            scale.zero()
+        } else {
+            None
+        };
+        if let Some(explicit) = opts.explicit_ticks() {
+            let ticks = explicit
+                .iter()
+                .filter_map(|(value, text)| {
+                    let norm = scale.normalize(*value);
+                    (-1e-9..=1.0 + 1e-9).contains(&norm).then(|| {
+                        let text = match text {
+                            Some(text) => text.clone(),
+                            None => format.format(*value),
+                        };
+                        Tick::new(norm, text)
+                    })
+                })
+                .collect();
+            return Axis::new(opts, ticks).zero_line(zero);
+        }
+        Axis::new(opts, scale.ticks(format))
+            .zero_line(zero)
+            .offset_text(scale.offset_text(format))
+    }
+
+    /// Extend the `Y` scale to cover a range, if needed
+    pub(crate) fn covering_y(&self, lo: f64, hi: f64) -> Domain {
+        let mut domain = self.clone();
+        let (start, stop) = self.y_scale.extent();
+        if start.min(stop) > lo || start.max(stop) < hi {
+            domain.y_scale = self.y_scale.union(Scale::from_range(lo, hi));
         }
+        domain
+    }
+
+    /// Extend the `X` scale to cover a range, if needed
+    pub(crate) fn covering_x(&self, lo: f64, hi: f64) -> Domain {
+        let mut domain = self.clone();
+        let (start, stop) = self.x_scale.extent();
+        if start.min(stop) > lo || start.max(stop) < hi {
+            domain.x_scale = self.x_scale.union(Scale::from_range(lo, hi));
+        }
+        domain
+    }
+
+    /// Extend a categorical `X` scale to cover an index
+    pub(crate) fn covering_x_index(&self, index: f64) -> Domain {
+        let mut domain = self.clone();
+        domain.x_scale = self.x_scale.covering_index(index);
+        domain
+    }
+
+    /// Extend the `Y` scale to include zero, if needed
+    ///
+    /// Percent tick spacing is kept.
+    pub(crate) fn covering_zero_y(&self) -> Domain {
+        let (start, stop) = self.y_scale.extent();
+        if start.min(stop) <= 0.0 && start.max(stop) >= 0.0 {
+            return self.clone();
+        }
+        let mut domain = self.clone().include_zero_y();
+        if self.y_format.percent {
+            domain.y_scale = domain.y_scale.percent_spacing();
+        }
+        domain
+    }
+
+    /// Fix the `Y` scale to a range, keeping percent tick spacing
+    pub(crate) fn fixed_y(&self, lo: f64, hi: f64) -> Domain {
+        let mut domain = self.clone();
+        domain.y_scale = Scale::from_range(lo, hi);
+        if self.y_format.percent {
+            domain.y_scale = domain.y_scale.percent_spacing();
+        }
+        domain
     }
 
     /// Normalize an `X` value
-    fn x_norm(&self, x: f32) -> f32 {
+    fn x_norm(&self, x: f64) -> f64 {
         self.x_scale.normalize(x)
     }
 
     /// Normalize a `Y` value
-    fn y_norm(&self, y: f32) -> f32 {
+    fn y_norm(&self, y: f64) -> f64 {
         self.y_scale.inverted().normalize(y)
     }
 
@@ -98,20 +507,56 @@ impl Domain {
 }
 
 impl BoundDomain {
+    /// Format an `X` value as text
+    pub fn x_text(&self, x: f64) -> String {
+        self.domain.x_format.format(x)
+    }
+
+    /// Format a `Y` value as text
+    pub fn y_text(&self, y: f64) -> String {
+        self.domain.y_format.format(y)
+    }
+
+    /// Get the `X` values at the left and right of the bound rectangle
+    ///
+    /// These are in ascending order, even if the scale is flipped.
+    pub fn x_extent(&self) -> (f64, f64) {
+        let (start, stop) = self.domain.x_scale.extent();
+        (start.min(stop), start.max(stop))
+    }
+
     /// Map an `X` value
-    pub fn x_map(&self, x: f32) -> i32 {
-        let rx = self.rect.x as f32;
-        let rw = f32::from(self.rect.width);
-        let mx = rx + rw * self.domain.x_norm(x);
-        mx.round() as i32
+    pub fn x_map(&self, x: f64) -> i32 {
+        self.x_pos(x).round() as i32
+    }
+
+    /// Map an `X` value, without rounding
+    pub fn x_pos(&self, x: f64) -> f64 {
+        let rx = self.rect.x as f64;
+        let rw = f64::from(self.rect.width);
+        rx + rw * self.domain.x_norm(x)
     }
 
     /// Map a `Y` value
-    pub fn y_map(&self, y: f32) -> i32 {
-        let ry = self.rect.y as f32;
-        let rh = f32::from(self.rect.height);
-        let my = ry + rh * self.domain.y_norm(y);
-        my.round() as i32
+    pub fn y_map(&self, y: f64) -> i32 {
+        self.y_pos(y).round() as i32
+    }
+
+    /// Map a `Y` value, without rounding
+    pub fn y_pos(&self, y: f64) -> f64 {
+        let ry = self.rect.y as f64;
+        let rh = f64::from(self.rect.height);
+        ry + rh * self.domain.y_norm(y)
+    }
+
+    /// Get the `Y` position of the area baseline, without rounding
+    ///
+    /// This is the position of zero, clamped to the bound rectangle, so that
+    /// areas fill toward zero without extending past the plot area.
+    pub fn y_baseline(&self) -> f64 {
+        let ry = self.rect.y as f64;
+        let rh = f64::from(self.rect.height);
+        ry + rh * self.domain.y_norm(0.0).clamp(0.0, 1.0)
     }
 }
 
@@ -120,14 +565,106 @@ mod tests {
     use super::*;
     use crate::scale::Numeric;
 
+    #[test]
+    fn categories() {
+        let domain = Domain::default().set_x_categories(&["apples", "oranges"]);
+        assert_eq!(domain.x_category("oranges"), Some(1.0));
+        assert_eq!(domain.x_category("pears"), None);
+        let bound = domain.bind(Rect::new(0, 0, 400, 100));
+        assert_eq!(bound.x_map(0.0), 100);
+        assert_eq!(bound.x_map(1.0), 300);
+        let domain = domain.including(&[(3.0, 1.0)]);
+        assert_eq!(domain.x_category("oranges"), Some(1.0));
+        assert_eq!(domain.x_category("2"), Some(2.0));
+        assert_eq!(domain.x_category("3"), Some(3.0));
+        let bound = domain.bind(Rect::new(0, 0, 400, 100));
+        assert_eq!(bound.x_map(3.0), 350);
+    }
+
+    #[test]
+    fn baseline() {
+        let rect = Rect::new(0, 100, 400, 200);
+        let domain = Domain::default().set_y(&[(0.0, 40.0), (0.0, 80.0)]);
+        assert_eq!(domain.bind(rect).y_baseline(), 300.0);
+        let domain = Domain::default().set_y(&[(0.0, -80.0), (0.0, -40.0)]);
+        assert_eq!(domain.bind(rect).y_baseline(), 100.0);
+        let domain = Domain::default().set_y(&[(0.0, -50.0), (0.0, 50.0)]);
+        assert_eq!(domain.bind(rect).y_baseline(), 200.0);
+    }
+
+    #[test]
+    fn ranges() {
+        let rect = Rect::new(0, 0, 100, 100);
+        let corners = [(0.0, 0.0), (24.0, 100.0)];
+        let domain = Domain::from_ranges(0..24, 0.0..100.0);
+        let bound = domain.bind(rect);
+        let expected = Domain::from(&corners[..]).bind(rect);
+        assert_eq!(bound.x_map(12.0), expected.x_map(12.0));
+        assert_eq!(bound.y_map(50.0), expected.y_map(50.0));
+        let domain = Domain::default().set_x_range(5.0..5.0);
+        let point = Domain::from(&[(5.0, 0.0)][..]);
+        assert_eq!(domain.bind(rect).x_map(5.0), point.bind(rect).x_map(5.0));
+        let a = Domain::from_ranges(0..10, 0..10);
+        let b = Domain::from_ranges(5..20, -10..5);
+        let bound = a.union(&b).bind(rect);
+        let expected = Domain::from_ranges(0..20, -10..10).bind(rect);
+        assert_eq!(bound.x_map(20.0), expected.x_map(20.0));
+        assert_eq!(bound.y_map(-10.0), expected.y_map(-10.0));
+    }
+
     #[test]
     fn test() {
         let data = [(45.0, 150.0), (90.0, 200.0)];
         let domain = Domain::from(&data[..]);
-        let ticks = Numeric::new(45.0, 90.0).ticks();
+        let ticks = Numeric::new(45.0, 90.0).ticks(&NumFormat::default());
         assert_eq!(
-            domain.axis("", Edge::Bottom),
-            Axis::new("", Edge::Bottom, ticks)
+            domain.axis(AxisOpts::new("", Edge::Bottom)),
+            Axis::new(AxisOpts::new("", Edge::Bottom), ticks)
         );
     }
+
+    #[test]
+    fn number_format() {
+        let data = [(0.0, 0.0), (5000.0, 1.5)];
+        let domain = Domain::from(&data[..]).number_format(NumberFormat::de());
+        let svg = crate::Chart::new()
+            .domain(domain)
+            .axis("", Edge::Bottom)
+            .axis("", Edge::Left)
+            .to_string();
+        assert!(svg.contains(">5.000</tspan>"));
+        assert!(svg.contains(">1,50</tspan>"));
+        let rect = Rect::new(0, 0, 100, 100);
+        let domain = Domain::default().number_format(NumberFormat::en());
+        assert_eq!(domain.bind(rect).x_text(1_234_567.891), "1,234,567.891");
+    }
+
+    #[test]
+    fn flip() {
+        let data = [(0.0, 0.0), (10.0, 10.0)];
+        let rect = Rect::new(0, 0, 100, 100);
+        let domain = Domain::from(&data[..]);
+        let bound = domain.bind(rect);
+        assert_eq!(bound.x_map(0.0), 0);
+        assert_eq!(bound.y_map(0.0), 100);
+        assert_eq!(bound.y_map(10.0), 0);
+        let bound = domain.clone().flip_y().bind(rect);
+        assert_eq!(bound.y_map(0.0), 0);
+        assert_eq!(bound.y_map(10.0), 100);
+        let bound = domain.flip_x().bind(rect);
+        assert_eq!(bound.x_map(0.0), 100);
+        assert_eq!(bound.x_map(10.0), 0);
+    }
+
+    #[test]
+    fn timestamp_precision() {
+        let data: Vec<_> = (0..3600).map(|i| (1_717_000_000 + i, 1)).collect();
+        let domain = Domain::from(&data[..]);
+        let bound = domain.bind(Rect::new(0, 0, 10_000, 100));
+        let xs: Vec<_> = data
+            .iter()
+            .map(|pt| bound.x_map(Point::from(*pt).x))
+            .collect();
+        assert!(xs.windows(2).all(|w| w[0] < w[1]));
+    }
 }
@@ -0,0 +1,35 @@
+// error.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+use std::fmt;
+
+/// Error building a `Chart`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChartError {
+    /// Aspect ratio with zero width or height
+    ZeroSize,
+    /// Margins leave no area for plots
+    NoPlotArea,
+    /// Titles or axes left out, for lack of space
+    OverConstrained,
+    /// Domain with a non-finite start, stop or spacing
+    NonFiniteDomain,
+    /// No domain, and no data to compute one
+    NoData,
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            ChartError::ZeroSize => "aspect ratio has zero size",
+            ChartError::NoPlotArea => "margins leave no plot area",
+            ChartError::OverConstrained => "titles or axes do not fit",
+            ChartError::NonFiniteDomain => "domain is not finite",
+            ChartError::NoData => "no domain or data",
+        };
+        write!(f, "{msg}")
+    }
+}
+
+impl std::error::Error for ChartError {}
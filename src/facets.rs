@@ -0,0 +1,425 @@
+// facets.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+use crate::axis::{Axis, AxisOpts, NAME_SPACE};
+use crate::chart::{AspectRatio, NEXT_UNIQUE};
+use crate::domain::Domain;
+use crate::legend::MarkerShape;
+use crate::plot::Plot;
+use crate::rect::{Edge, Rect, ViewBox};
+use crate::style::{Stylesheet, DEFAULT_CSS};
+use crate::text::{escape, indent, Text};
+use crate::title::Title;
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::Ordering;
+
+/// Space between columns
+const GUTTER: u16 = 40;
+
+/// One cell of a facet grid
+struct Cell<'a> {
+    title: Title<'a>,
+    axes: Vec<Axis<'a>>,
+    plots: Vec<Plot<'a>>,
+    area: Rect,
+}
+
+/// Grid of small charts sharing one domain ("small multiples")
+///
+/// All cells are rendered in a single SVG.  Axis tick labels are only drawn
+/// on the bottom row and left column, and axis names once for the grid.
+pub struct Facets<'a> {
+    stylesheet: Stylesheet<'a>,
+    id_prefix: Cow<'a, str>,
+    aspect_ratio: AspectRatio,
+    domain: Domain,
+    rows: u16,
+    cols: u16,
+    x_name: Cow<'a, str>,
+    y_name: Cow<'a, str>,
+    cells: Vec<Cell<'a>>,
+}
+
+impl<'a> Cell<'a> {
+    /// Render the cell
+    fn render(
+        &self,
+        f: &mut fmt::Formatter,
+        prefix: &str,
+        i: usize,
+    ) -> fmt::Result {
+        writeln!(f, "{}", self.title)?;
+        for axis in &self.axes {
+            axis.render_grid(f, self.area)?;
+            axis.render(f, self.area)?;
+        }
+        writeln!(f, "<g clip-path='url(#{prefix}clip-facet-{i})'>")?;
+        for plot in &self.plots {
+            writeln!(f, "{plot}")?;
+        }
+        writeln!(f, "</g>")
+    }
+}
+
+impl<'a> Facets<'a> {
+    /// Create a new facet grid
+    ///
+    /// Panics if `rows` or `cols` is zero.
+    pub fn new<D>(domain: D, rows: u16, cols: u16) -> Self
+    where
+        D: Into<Domain>,
+    {
+        assert!(rows > 0 && cols > 0);
+        Facets {
+            stylesheet: Stylesheet::default(),
+            id_prefix: Cow::Borrowed(""),
+            aspect_ratio: AspectRatio::Landscape,
+            domain: domain.into(),
+            rows,
+            cols,
+            x_name: Cow::Borrowed(""),
+            y_name: Cow::Borrowed(""),
+            cells: Vec::new(),
+        }
+    }
+
+    /// Embed the default stylesheet instead of linking to it
+    pub fn embed_css(self) -> Self {
+        self.css(DEFAULT_CSS)
+    }
+
+    /// Embed custom CSS text instead of linking to the default stylesheet
+    pub fn css(mut self, css: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Embed(css.into());
+        self
+    }
+
+    /// Set the stylesheet link href
+    ///
+    /// The default is `./css/splot.css`.  An empty href suppresses the link.
+    pub fn css_href(mut self, href: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Link(href);
+        self
+    }
+
+    /// Set a prefix for element IDs (`clipPath` and `marker`)
+    ///
+    /// This keeps IDs from colliding when several grids are placed into one
+    /// HTML document.
+    ///
+    /// Panics if called after `cell`.
+    pub fn id_prefix<P>(mut self, prefix: P) -> Self
+    where
+        P: Into<Cow<'a, str>>,
+    {
+        assert!(self.cells.is_empty());
+        self.id_prefix = prefix.into();
+        self
+    }
+
+    /// Give element IDs a prefix which is unique within this process
+    ///
+    /// The prefix is `splot-{n}-`, shared with `Chart::unique_ids`.
+    ///
+    /// Panics if called after `cell`.
+    pub fn unique_ids(self) -> Self {
+        let n = NEXT_UNIQUE.fetch_add(1, Ordering::Relaxed);
+        self.id_prefix(format!("splot-{n}-"))
+    }
+
+    /// Adjust the overall aspect ratio
+    ///
+    /// Panics if called after `cell`.
+    pub fn aspect_ratio(mut self, aspect: AspectRatio) -> Self {
+        assert!(self.cells.is_empty());
+        self.aspect_ratio = aspect;
+        self
+    }
+
+    /// Set the `X` axis name, drawn below the bottom row
+    ///
+    /// Panics if called after `cell`.
+    pub fn x_axis<N>(mut self, name: N) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        assert!(self.cells.is_empty());
+        self.x_name = name.into();
+        self
+    }
+
+    /// Set the `Y` axis name, drawn beside the left column
+    ///
+    /// Panics if called after `cell`.
+    pub fn y_axis<N>(mut self, name: N) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+    {
+        assert!(self.cells.is_empty());
+        self.y_name = name.into();
+        self
+    }
+
+    /// Add a cell, filling the grid by rows
+    ///
+    /// Panics if the grid is already full.
+    pub fn cell<T>(mut self, title: T, mut plots: Vec<Plot<'a>>) -> Self
+    where
+        T: Into<Title<'a>>,
+    {
+        let i = self.cells.len() as u16;
+        assert!(i < self.rows * self.cols);
+        let (row, col) = (i / self.cols, i % self.cols);
+        let bottom = row + 1 == self.rows;
+        let left = col == 0;
+        let mut title = title.into();
+        let mut area = title.split(self.cell_rect(row, col));
+        if bottom {
+            area.height += self.x_margin();
+        }
+        if left {
+            area.x -= i32::from(self.y_margin());
+            area.width += self.y_margin();
+        }
+        let mut axes = vec![
+            self.domain.axis(self.x_opts(bottom)),
+            self.domain.axis(self.y_opts(left)),
+        ];
+        for axis in axes.iter_mut() {
+            area = axis.split(area);
+        }
+        for (num, plot) in plots.iter_mut().enumerate() {
+            plot.num(num as u32 % 11);
+            if !self.id_prefix.is_empty() {
+                plot.set_marker(format!("{}marker-{num}", self.id_prefix));
+            }
+            let domain = plot.own_domain().unwrap_or(&self.domain);
+            plot.bind_domain(domain.bind(area));
+        }
+        self.cells.push(Cell {
+            title,
+            axes,
+            plots,
+            area,
+        });
+        self
+    }
+
+    /// Render the grid as indented SVG, for debugging
+    ///
+    /// Only indentation differs from the `Display` output.
+    pub fn to_string_pretty(&self) -> String {
+        indent(&self.to_string())
+    }
+
+    /// Write the grid to an `io::Write`
+    ///
+    /// Output is streamed through a small buffer, without rendering the whole
+    /// grid into a `String` first.
+    pub fn write_to<W: io::Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        write!(w, "{self}")?;
+        w.flush()
+    }
+
+    /// Get options for the bottom axis of a cell
+    fn x_opts(&self, outer: bool) -> AxisOpts<'a> {
+        match outer {
+            true => AxisOpts::new("", Edge::Bottom),
+            false => AxisOpts::new("", Edge::Bottom).grid_only(),
+        }
+    }
+
+    /// Get options for the left axis of a cell
+    fn y_opts(&self, outer: bool) -> AxisOpts<'a> {
+        match outer {
+            true => AxisOpts::new("", Edge::Left),
+            false => AxisOpts::new("", Edge::Left).grid_only(),
+        }
+    }
+
+    /// Get the rectangle within outer margins
+    fn outer_rect(&self) -> Rect {
+        self.aspect_ratio.rect().inset(40)
+    }
+
+    /// Get the rectangle within outer margins, excluding axis names
+    fn grid_rect(&self) -> Rect {
+        let mut rect = self.outer_rect();
+        if !self.x_name.is_empty() {
+            (rect, _) = rect.split(Edge::Bottom, NAME_SPACE);
+        }
+        if !self.y_name.is_empty() {
+            (rect, _) = rect.split(Edge::Left, NAME_SPACE);
+        }
+        rect
+    }
+
+    /// Get the space reserved below the bottom row for the `X` axis
+    fn x_margin(&self) -> u16 {
+        let grid = self.grid_rect();
+        let mut axis = self.domain.axis(self.x_opts(true));
+        grid.height - axis.split(grid).height
+    }
+
+    /// Get the space reserved beside the left column for the `Y` axis
+    fn y_margin(&self) -> u16 {
+        let grid = self.grid_rect();
+        let mut axis = self.domain.axis(self.y_opts(true));
+        grid.width - axis.split(grid).width
+    }
+
+    /// Get the rectangle of one cell, excluding outer axis margins
+    ///
+    /// Every cell gets the same size, so that plot areas line up.
+    fn cell_rect(&self, row: u16, col: u16) -> Rect {
+        let (grid, _) = self.grid_rect().split(Edge::Bottom, self.x_margin());
+        let (grid, _) = grid.split(Edge::Left, self.y_margin());
+        let width = grid.width / self.cols;
+        let height = grid.height / self.rows;
+        let x = grid.x + i32::from(col * width);
+        let y = grid.y + i32::from(row * height);
+        let (rect, _) =
+            Rect::new(x, y, width, height).split(Edge::Right, GUTTER);
+        rect
+    }
+
+    /// Render axis names, centered on the plot areas
+    fn names(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let grid = self.grid_rect();
+        let (plots, _) = grid.split(Edge::Bottom, self.x_margin());
+        let (plots, _) = plots.split(Edge::Left, self.y_margin());
+        let (plots, _) = plots.split(Edge::Right, GUTTER);
+        if !self.x_name.is_empty() {
+            let rect =
+                Rect::new(plots.x, grid.bottom(), plots.width, NAME_SPACE);
+            let text = Text::new(Edge::Bottom).rect(rect).class_name("axis");
+            text.display(f)?;
+            writeln!(f, "{}", escape(&self.x_name))?;
+            text.display_done(f)?;
+        }
+        if !self.y_name.is_empty() {
+            // below the titles of the top row
+            let y = self.cells.first().map_or(plots.y, |c| c.area.y);
+            let height = (plots.bottom() - y) as u16;
+            let x = grid.x - i32::from(NAME_SPACE);
+            let rect = Rect::new(x, y, NAME_SPACE, height);
+            let text = Text::new(Edge::Left).rect(rect).class_name("axis");
+            text.display(f)?;
+            writeln!(f, "{}", escape(&self.y_name))?;
+            text.display_done(f)?;
+        }
+        Ok(())
+    }
+
+    /// Render defs element
+    fn defs(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "<defs>")?;
+        let prefix = &self.id_prefix;
+        let widest = self.cells.iter().max_by_key(|c| c.plots.len());
+        if let Some(cell) = widest {
+            for (i, plot) in cell.plots.iter().enumerate() {
+                write!(f, "<marker id='{prefix}marker-{i}'")?;
+                plot.class_fmt(f, "")?;
+                plot.color_fmt(f, "fill")?;
+                write!(f, " viewBox='-1 -1 2 2'")?;
+                writeln!(f, " markerWidth='5' markerHeight='5'>")?;
+                writeln!(f, "{}", MarkerShape::from_index(i).svg())?;
+                writeln!(f, "</marker>")?;
+            }
+        }
+        for (i, cell) in self.cells.iter().enumerate() {
+            writeln!(f, "<clipPath id='{prefix}clip-facet-{i}'>")?;
+            writeln!(f, "{}", cell.area)?;
+            writeln!(f, "</clipPath>")?;
+        }
+        writeln!(f, "</defs>")
+    }
+}
+
+impl<'a> fmt::Display for Facets<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let view_box = ViewBox(self.aspect_ratio.rect());
+        writeln!(
+            f,
+            "<svg xmlns='http://www.w3.org/2000/svg' {view_box} role='img'>"
+        )?;
+        self.stylesheet.svg_fmt(f)?;
+        self.defs(f)?;
+        self.names(f)?;
+        for (i, cell) in self.cells.iter().enumerate() {
+            cell.render(f, &self.id_prefix, i)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        let a = [(0.0, 1.0), (1.0, 2.0)];
+        let b = [(0.0, 2.0), (1.0, 0.0)];
+        let facets = Facets::new(&a[..], 2, 3)
+            .x_axis("X")
+            .y_axis("Y")
+            .cell("A", vec![Plot::line("A", &a[..])])
+            .cell("B", vec![Plot::line("B", &b[..])])
+            .cell("C", vec![Plot::scatter("C", &a[..])])
+            .cell("D", vec![Plot::line("D", &b[..])]);
+        let cells = &facets.cells;
+        assert_eq!(cells.len(), 4);
+        for cell in &cells[1..] {
+            assert_eq!(cell.area.width, cells[0].area.width);
+            assert_eq!(cell.area.height, cells[0].area.height);
+        }
+        // cells in one row share plot area top edge
+        assert_eq!(cells[0].area.y, cells[2].area.y);
+        // cells in one column share plot area left edge
+        assert_eq!(cells[0].area.x, cells[3].area.x);
+        let svg = facets.to_string();
+        for i in 0..4 {
+            let id = format!("clip-facet-{i}");
+            assert_eq!(svg.matches(&id).count(), 2);
+        }
+        // axis names only on outer edges
+        assert_eq!(svg.matches("\nX\n").count(), 1);
+        assert_eq!(svg.matches("\nY\n").count(), 1);
+        let svg = Facets::new(&a[..], 1, 2)
+            .x_axis("X & Z")
+            .cell("A", vec![Plot::line("A", &a[..])])
+            .cell("B", vec![Plot::line("B", &b[..])])
+            .to_string();
+        assert_eq!(svg.matches("\nX &amp; Z\n").count(), 1);
+    }
+
+    #[test]
+    fn id_prefix() {
+        let a = [(0.0, 1.0), (1.0, 2.0)];
+        let facets = || {
+            Facets::new(&a[..], 1, 2)
+                .unique_ids()
+                .cell("A", vec![Plot::line("A", &a[..]).markers()])
+                .cell("B", vec![Plot::line("B", &a[..])])
+                .to_string()
+        };
+        let (one, two) = (facets(), facets());
+        let id = |svg: &str| {
+            let start = svg.find("<clipPath id='").unwrap() + 14;
+            let len = svg[start..].find('\'').unwrap();
+            svg[start..start + len].to_string()
+        };
+        let (id1, id2) = (id(&one), id(&two));
+        assert_ne!(id1, id2);
+        assert!(id1.ends_with("clip-facet-0"));
+        assert!(one.contains(&format!("clip-path='url(#{id1})'")));
+        let marker = id1.replace("clip-facet-0", "marker-0");
+        assert!(one.contains(&format!("<marker id='{marker}'")));
+        assert!(one.contains(&format!("marker-mid='url(#{marker})'")));
+    }
+}
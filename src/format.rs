@@ -0,0 +1,462 @@
+// format.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Number formatting for tick labels
+//!
+
+/// SI prefixes, from 10⁻²⁴ to 10²⁴
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E",
+    "Z", "Y",
+];
+
+/// Superscript digits, for exponents
+const SUPERSCRIPTS: [char; 10] =
+    ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// Smallest magnitude formatted as plain decimal numbers
+const PLAIN_MIN: f64 = 1e-4;
+
+/// Largest magnitude formatted as plain decimal numbers (exclusive)
+const PLAIN_MAX: f64 = 1e6;
+
+/// Smallest tick spacing of mantissas for automatic scientific notation
+const SCI_SPACING_MIN: f64 = 1e-3;
+
+/// Tick label number format
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TickFormat {
+    /// Plain decimal numbers, switching to scientific notation for very
+    /// large or small magnitudes
+    #[default]
+    Plain,
+    /// Decimal numbers, never switching to scientific notation
+    Decimal,
+    /// Scientific notation, with one exponent shown at the end of the axis
+    Scientific,
+    /// SI prefixes, such as `1.5k`, `20M` or `3G`
+    Si,
+    /// Engineering notation, with exponents in multiples of 3 (`1.5e3`)
+    Engineering,
+}
+
+/// Decimal and digit group separators
+///
+/// ```rust
+/// use splot::{Domain, NumberFormat};
+///
+/// let domain = Domain::default().number_format(NumberFormat::de());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Decimal separator
+    pub decimal_sep: char,
+    /// Separator between digit groups, if any
+    pub group_sep: Option<char>,
+    /// Number of integer digits in each group
+    pub grouping: u8,
+}
+
+/// Number formatting settings
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NumFormat {
+    /// Tick format
+    pub tick_format: TickFormat,
+    /// Digits after the decimal point
+    pub precision: Option<usize>,
+    /// Format values as percentages
+    pub percent: bool,
+    /// Separators
+    pub number: NumberFormat,
+    /// Tick spacing, for deriving precision of scientific notation
+    pub spacing: Option<f64>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal_sep: '.',
+            group_sep: None,
+            grouping: 3,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// English style, such as `1,234.5`
+    pub fn en() -> Self {
+        NumberFormat {
+            group_sep: Some(','),
+            ..Default::default()
+        }
+    }
+
+    /// German style, such as `1.234,5`
+    pub fn de() -> Self {
+        NumberFormat {
+            decimal_sep: ',',
+            group_sep: Some('.'),
+            grouping: 3,
+        }
+    }
+
+    /// French style, such as `1 234,5` (with a narrow no-break space)
+    pub fn fr() -> Self {
+        NumberFormat {
+            decimal_sep: ',',
+            group_sep: Some('\u{202F}'),
+            grouping: 3,
+        }
+    }
+
+    /// Apply separators to a plain formatted number, such as `-1234.5`
+    fn apply(&self, text: &str) -> String {
+        if *self == NumberFormat::default() {
+            return text.to_string();
+        }
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(text) => ("-", text),
+            None => ("", text),
+        };
+        let (int, frac) = match text.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (text, None),
+        };
+        let mut out = String::with_capacity(text.len() * 2);
+        out.push_str(sign);
+        let grouping = usize::from(self.grouping.max(1));
+        for (i, c) in int.chars().enumerate() {
+            if let Some(sep) = self.group_sep {
+                if i > 0 && (int.len() - i) % grouping == 0 {
+                    out.push(sep);
+                }
+            }
+            out.push(c);
+        }
+        if let Some(frac) = frac {
+            out.push(self.decimal_sep);
+            out.push_str(frac);
+        }
+        out
+    }
+}
+
+/// Trim trailing zeros from a fixed-point number
+fn trim_zeros(mut text: String) -> String {
+    if text.contains('.') {
+        let len = text.trim_end_matches('0').trim_end_matches('.').len();
+        text.truncate(len);
+    }
+    if text == "-0" {
+        text.remove(0);
+    }
+    text
+}
+
+/// Format an exponent with superscript digits
+fn superscript(exp: i32) -> String {
+    let mut text = String::new();
+    if exp < 0 {
+        text.push('⁻');
+    }
+    for c in exp.unsigned_abs().to_string().bytes() {
+        text.push(SUPERSCRIPTS[usize::from(c - b'0')]);
+    }
+    text
+}
+
+/// Count the decimal digits needed to represent a tick spacing
+fn spacing_digits(spacing: f64) -> usize {
+    (0..12)
+        .find(|d| {
+            let v = spacing * 10_f64.powi(*d);
+            v.round() >= 1.0 && (v - v.round()).abs() < 1e-4 * v
+        })
+        .unwrap_or(6) as usize
+}
+
+impl NumFormat {
+    /// Derive precision from tick spacing, unless already set
+    ///
+    /// This keeps plain tick labels on one axis uniform, such as "0.50" and
+    /// "0.75" with a spacing of 0.25.
+    pub fn with_spacing(&self, spacing: f64) -> NumFormat {
+        let mut fmt = self.clone();
+        if fmt.precision.is_none() {
+            let spacing = if fmt.percent {
+                spacing * 100.0
+            } else {
+                spacing
+            };
+            match fmt.tick_format {
+                TickFormat::Plain | TickFormat::Decimal => {
+                    fmt.precision = Some(spacing_digits(spacing));
+                    fmt.spacing = Some(spacing);
+                }
+                TickFormat::Scientific => fmt.spacing = Some(spacing),
+                _ => (),
+            }
+        }
+        fmt
+    }
+
+    /// Get the exponent for scientific notation of a set of values
+    ///
+    /// Plain format switches to scientific notation when the largest
+    /// magnitude is outside of `1e-4` to `1e6`, unless digits are grouped.
+    pub fn sci_exponent(&self, values: &[f64]) -> Option<i32> {
+        if self.percent {
+            return None;
+        }
+        let max = values.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+        let sci = match self.tick_format {
+            TickFormat::Scientific => max > 0.0,
+            TickFormat::Plain => {
+                self.number.group_sep.is_none()
+                    && max > 0.0
+                    && !(PLAIN_MIN..PLAIN_MAX).contains(&max)
+            }
+            _ => false,
+        };
+        // nudge to avoid rounding 1e-6 down to 0.99999e-6
+        let exp = (max.log10() + 1e-9).floor() as i32;
+        // mantissas with many digits are no shorter than plain numbers
+        let short = match self.spacing {
+            Some(spacing) => spacing / 10_f64.powi(exp) >= SCI_SPACING_MIN,
+            None => true,
+        };
+        (sci && (short || self.tick_format == TickFormat::Scientific))
+            .then_some(exp)
+    }
+
+    /// Get offset text for a set of values, such as `×10⁻⁷`
+    ///
+    /// This is the exponent shared by values from `format_all`, if needed.
+    pub fn offset_text(&self, values: &[f64]) -> Option<String> {
+        self.sci_exponent(values)
+            .filter(|exp| *exp != 0)
+            .map(|exp| format!("×10{}", superscript(exp)))
+    }
+
+    /// Format a mantissa value
+    fn mantissa(&self, value: f64) -> String {
+        match self.precision {
+            Some(digits) => format!("{value:.digits$}"),
+            None => trim_zeros(format!("{value:.6}")),
+        }
+    }
+
+    /// Get the exponent (multiple of 3) for a set of values
+    fn exponent(values: &[f64]) -> i32 {
+        let max = values.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+        if max > 0.0 {
+            let exp = (max.log10() / 3.0).floor() as i32 * 3;
+            exp.clamp(-24, 24)
+        } else {
+            0
+        }
+    }
+
+    /// Format a single value
+    ///
+    /// Scientific notation includes the exponent, such as `1.5e-7`.
+    pub fn format(&self, value: f64) -> String {
+        let text = self.format_all(&[value]).pop().unwrap_or_default();
+        match self.sci_exponent(&[value]) {
+            Some(exp) if exp != 0 => format!("{text}e{exp}"),
+            _ => text,
+        }
+    }
+
+    /// Format a set of values consistently
+    ///
+    /// For SI and engineering formats, one exponent is chosen for all values,
+    /// based on the largest magnitude.  For scientific notation, only the
+    /// mantissas are formatted; the exponent is in `offset_text`.
+    pub fn format_all(&self, values: &[f64]) -> Vec<String> {
+        if self.percent {
+            let fmt = NumFormat {
+                percent: false,
+                ..self.clone()
+            };
+            // round to avoid artifacts such as "7.0000005%"
+            let values: Vec<f64> =
+                values.iter().map(|v| (v * 1e6).round() / 1e4).collect();
+            return fmt
+                .format_all(&values)
+                .into_iter()
+                .map(|text| format!("{text}%"))
+                .collect();
+        }
+        if let Some(exp) = self.sci_exponent(values) {
+            let div = 10_f64.powi(exp);
+            let fmt = NumFormat {
+                precision: match self.spacing {
+                    Some(spacing) => Some(spacing_digits(spacing / div)),
+                    None => self.precision,
+                },
+                ..self.clone()
+            };
+            return values
+                .iter()
+                .map(|v| fmt.number.apply(&fmt.mantissa(*v / div)))
+                .collect();
+        }
+        let exp = match self.tick_format {
+            TickFormat::Plain | TickFormat::Decimal => 0,
+            _ => Self::exponent(values),
+        };
+        let div = 10_f64.powi(exp);
+        values
+            .iter()
+            .map(|v| {
+                let text = match self.tick_format {
+                    TickFormat::Plain | TickFormat::Decimal
+                        if self.precision.is_none() =>
+                    {
+                        return self.number.apply(&format!("{v}"));
+                    }
+                    _ => self.number.apply(&self.mantissa(*v / div)),
+                };
+                match self.tick_format {
+                    _ if *v == 0.0 => text,
+                    TickFormat::Si => {
+                        let i = (exp / 3 + 8) as usize;
+                        format!("{text}{}", SI_PREFIXES[i])
+                    }
+                    TickFormat::Engineering if exp != 0 => {
+                        format!("{text}e{exp}")
+                    }
+                    _ => text,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn si() {
+        let fmt = NumFormat {
+            tick_format: TickFormat::Si,
+            ..Default::default()
+        };
+        assert_eq!(
+            fmt.format_all(&[0.0, 500_000.0, 1_000_000.0, 1_500_000.0]),
+            ["0", "0.5M", "1M", "1.5M"]
+        );
+        assert_eq!(fmt.format_all(&[0.001, 0.002]), ["1m", "2m"]);
+        let fmt = NumFormat {
+            tick_format: TickFormat::Si,
+            precision: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(fmt.format_all(&[2.0e9, 3.0e9]), ["2.0G", "3.0G"]);
+    }
+
+    #[test]
+    fn engineering() {
+        let fmt = NumFormat {
+            tick_format: TickFormat::Engineering,
+            ..Default::default()
+        };
+        assert_eq!(fmt.format_all(&[1500.0, 25_000.0]), ["1.5e3", "25e3"]);
+        assert_eq!(fmt.format_all(&[1.0, 2.0]), ["1", "2"]);
+    }
+
+    #[test]
+    fn percent() {
+        let fmt = NumFormat {
+            percent: true,
+            ..Default::default()
+        };
+        assert_eq!(fmt.format_all(&[0.0, 0.25, 0.5]), ["0%", "25%", "50%"]);
+        assert_eq!(fmt.format(0.1), "10%");
+    }
+
+    #[test]
+    fn separators() {
+        let value = 1_234_567.891;
+        let en = NumFormat {
+            number: NumberFormat::en(),
+            ..Default::default()
+        };
+        assert_eq!(en.format(value), "1,234,567.891");
+        assert_eq!(en.format(-value), "-1,234,567.891");
+        assert_eq!(en.format(123.0), "123");
+        let de = NumFormat {
+            number: NumberFormat::de(),
+            ..Default::default()
+        };
+        assert_eq!(de.format(value), "1.234.567,891");
+        let de = NumFormat {
+            precision: Some(1),
+            ..de
+        };
+        assert_eq!(de.format(value), "1.234.567,9");
+        assert_eq!(de.format(0.5), "0,5");
+        let de = NumFormat {
+            tick_format: TickFormat::Si,
+            precision: Some(2),
+            number: NumberFormat::de(),
+            ..Default::default()
+        };
+        assert_eq!(de.format_all(&[value, 5e6]), ["1,23M", "5,00M"]);
+        let de = NumFormat {
+            percent: true,
+            number: NumberFormat::de(),
+            ..Default::default()
+        };
+        assert_eq!(de.format(12.345), "1.234,5%");
+    }
+
+    #[test]
+    fn scientific() {
+        let fmt = NumFormat::default().with_spacing(1e-7);
+        let values = [1e-7, 5e-7, 1e-6];
+        assert_eq!(fmt.format_all(&values), ["0.1", "0.5", "1.0"]);
+        assert_eq!(fmt.offset_text(&values).unwrap(), "×10⁻⁶");
+        let fmt = NumFormat::default();
+        assert_eq!(fmt.format(1.5e-7), "1.5e-7");
+        assert_eq!(fmt.format(2.5e9), "2.5e9");
+        assert_eq!(fmt.format(0.001), "0.001");
+        assert_eq!(fmt.offset_text(&[0.001, 999_999.0]), None);
+        let fmt = NumFormat {
+            tick_format: TickFormat::Decimal,
+            ..Default::default()
+        };
+        assert_eq!(fmt.format(1e-7), "0.0000001");
+        assert_eq!(fmt.offset_text(&[1e-7]), None);
+        let fmt = NumFormat {
+            tick_format: TickFormat::Scientific,
+            ..Default::default()
+        }
+        .with_spacing(500.0);
+        let values = [0.0, 500.0, 1000.0, 1500.0];
+        assert_eq!(fmt.format_all(&values), ["0.0", "0.5", "1.0", "1.5"]);
+        assert_eq!(fmt.offset_text(&values).unwrap(), "×10³");
+    }
+
+    #[test]
+    fn spacing() {
+        let fmt = NumFormat::default().with_spacing(0.25);
+        assert_eq!(fmt.precision, Some(2));
+        assert_eq!(fmt.format_all(&[0.0, 0.25, 0.5]), ["0.00", "0.25", "0.50"]);
+        assert_eq!(NumFormat::default().with_spacing(10.0).precision, Some(0));
+        assert_eq!(NumFormat::default().with_spacing(0.1).precision, Some(1));
+        let fmt = NumFormat {
+            percent: true,
+            ..Default::default()
+        };
+        assert_eq!(fmt.with_spacing(0.05).precision, Some(0));
+        let fmt = NumFormat {
+            precision: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(fmt.with_spacing(0.5).precision, Some(3));
+    }
+}
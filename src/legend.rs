@@ -0,0 +1,71 @@
+// legend.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+use crate::chart::MARKERS;
+use crate::plot::PlotType;
+
+/// Shape of a plot marker
+///
+/// Markers are assigned by plot index, repeating after the last shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkerShape {
+    /// Circle
+    Circle,
+    /// Square
+    Square,
+    /// Triangle pointing up
+    TriangleUp,
+    /// Triangle pointing right
+    TriangleRight,
+    /// Triangle pointing down
+    TriangleDown,
+    /// Triangle pointing left
+    TriangleLeft,
+    /// Diamond
+    Diamond,
+    /// Four-pointed star
+    Star,
+}
+
+/// Entry in a chart legend
+///
+/// This has the data needed to render a legend outside of the chart, as
+/// returned by `Chart::legend_entries`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegendEntry {
+    /// Plot name
+    pub name: String,
+    /// Index of the plot in its chart
+    pub index: usize,
+    /// Type of plot
+    pub plot_type: PlotType,
+    /// Marker shape, for scatter plots and line plots with markers
+    pub marker: Option<MarkerShape>,
+    /// Plot color, when set from a palette
+    pub color: Option<String>,
+}
+
+impl MarkerShape {
+    /// All shapes, in order of assignment
+    const ALL: [MarkerShape; 8] = [
+        MarkerShape::Circle,
+        MarkerShape::Square,
+        MarkerShape::TriangleUp,
+        MarkerShape::TriangleRight,
+        MarkerShape::TriangleDown,
+        MarkerShape::TriangleLeft,
+        MarkerShape::Diamond,
+        MarkerShape::Star,
+    ];
+
+    /// Get the marker shape for a plot index
+    pub(crate) fn from_index(i: usize) -> Self {
+        Self::ALL[i % Self::ALL.len()]
+    }
+
+    /// Get SVG element of the shape, within a 2x2 box centered on 0
+    pub(crate) fn svg(self) -> &'static str {
+        MARKERS[self as usize]
+    }
+}
@@ -1,21 +1,60 @@
 #![doc = include_str!("../README.md")]
 #![forbid(unsafe_code)]
 
+mod annotation;
 mod axis;
+mod band;
 mod chart;
 mod domain;
+mod error;
+mod facets;
+mod format;
+mod legend;
 mod page;
+mod palette;
+mod path;
 mod plot;
 mod point;
+mod radar;
+#[cfg(feature = "raster")]
+mod raster;
 mod rect;
+mod sample;
+mod save;
 mod scale;
+#[cfg(feature = "serde")]
+mod spec;
+mod style;
+pub mod testing;
 mod text;
+mod theme;
+mod time;
 mod title;
 
-pub use chart::{AspectRatio, Chart};
+pub use annotation::Annotation;
+pub use axis::{AxisOpts, TickStyle};
+pub use band::Band;
+pub use chart::{AspectRatio, Chart, Layer, LegendPos};
 pub use domain::Domain;
-pub use page::Page;
-pub use plot::{Plot, PlotSettings};
-pub use point::{IntoPoint, Point};
+pub use error::ChartError;
+pub use facets::Facets;
+pub use format::{NumberFormat, TickFormat};
+pub use legend::{LegendEntry, MarkerShape};
+pub use page::{Direction, Layout, Page};
+pub use palette::Palette;
+pub use plot::{Plot, PlotSettings, PlotType};
+pub use point::{BandPoint, IntoPoint, LabeledPoint, Point, RangePoint};
+pub use radar::Radar;
+#[cfg(feature = "raster")]
+pub use raster::RenderError;
 pub use rect::Edge;
+#[cfg(feature = "raster")]
+pub use resvg::usvg::fontdb;
+#[cfg(feature = "serde")]
+pub use spec::{
+    AxisSpec, ChartSpec, DomainSpec, PlotKind, PlotSpec, ScaleSpec,
+};
+pub use style::{write_css, DEFAULT_CSS};
+pub use text::Anchor;
+pub use theme::Theme;
 pub use title::Title;
@@ -2,66 +2,505 @@
 //
 // Copyright (c) 2021-2024  Douglas P Lau
 //
-use crate::chart::Chart;
-use crate::point::IntoPoint;
+use crate::chart::{Chart, LegendPos};
+use crate::radar::Radar;
+use crate::save::write_atomic;
+use crate::style::{Stylesheet, DEFAULT_CSS};
+use crate::text::{escape, indent};
+use crate::theme::Theme;
+use std::borrow::Cow;
 use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Direction of a flexbox page layout
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// Charts stacked vertically
+    #[default]
+    Column,
+    /// Charts side by side
+    Row,
+}
+
+/// Layout of charts on a page
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Flexbox layout, optionally wrapping onto multiple lines
+    Flex {
+        /// Main axis direction
+        direction: Direction,
+        /// Wrap charts onto new lines
+        wrap: bool,
+    },
+    /// Grid layout with a number of columns (at least 1)
+    Grid(u16),
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::Flex {
+            direction: Direction::Column,
+            wrap: false,
+        }
+    }
+}
+
+/// Chart on a page
+enum Figure<'a> {
+    /// X/Y chart
+    Chart(Box<Chart<'a>>),
+    /// Radar chart
+    Radar(Radar<'a>),
+}
+
+impl<'a> fmt::Display for Figure<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Figure::Chart(chart) => write!(f, "{chart}"),
+            Figure::Radar(radar) => write!(f, "{radar}"),
+        }
+    }
+}
+
+impl<'a> Figure<'a> {
+    /// Get legend position
+    fn legend_position(&self) -> Option<LegendPos> {
+        match self {
+            Figure::Chart(chart) => chart.legend_position(),
+            Figure::Radar(_) => None,
+        }
+    }
+}
 
 /// Page to render charts as HTML
 ///
 /// A `Page` containing one or more `Chart`s can be rendered as HTML using the
 /// `Display` trait.  That is, using `println!`, or even `to_string()` is all
 /// that's needed.
-pub struct Page<'a, P>
-where
-    P: IntoPoint,
-{
-    charts: Vec<Chart<'a, P>>,
+pub struct Page<'a> {
+    charts: Vec<(Figure<'a>, Option<Cow<'a, str>>)>,
+    stylesheet: Stylesheet<'a>,
+    layout: Layout,
+    gap: Option<u16>,
+    title: Option<Cow<'a, str>>,
+    metas: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    stylesheets: Vec<Cow<'a, str>>,
+    scripts: Vec<Cow<'a, str>>,
 }
 
-impl<'a, P> Default for Page<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> Default for Page<'a> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<'a, P> Page<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> Page<'a> {
     /// Create a new page
     pub fn new() -> Self {
-        Page { charts: Vec::new() }
+        Page {
+            charts: Vec::new(),
+            stylesheet: Stylesheet::default(),
+            layout: Layout::default(),
+            gap: None,
+            title: None,
+            metas: Vec::new(),
+            stylesheets: Vec::new(),
+            scripts: Vec::new(),
+        }
+    }
+
+    /// Embed the default stylesheet instead of linking to it
+    pub fn embed_css(self) -> Self {
+        self.css(DEFAULT_CSS)
+    }
+
+    /// Embed custom CSS text instead of linking to the default stylesheet
+    pub fn css(mut self, css: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Embed(css.into());
+        self
+    }
+
+    /// Embed a stylesheet generated from a `Theme`
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.stylesheet = Stylesheet::Embed(theme.css().into());
+        self
+    }
+
+    /// Set the stylesheet link href
+    ///
+    /// The default is `./css/splot.css`.  An empty href suppresses the link.
+    pub fn css_href(mut self, href: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Link(href);
+        self
+    }
+
+    /// Set the document title
+    pub fn title<T>(mut self, title: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Add a `<meta>` element to the head
+    pub fn meta<N, C>(mut self, name: N, content: C) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        C: Into<Cow<'a, str>>,
+    {
+        self.metas.push((name.into(), content.into()));
+        self
+    }
+
+    /// Add a stylesheet link, after the chart stylesheet
+    pub fn stylesheet<H>(mut self, href: H) -> Self
+    where
+        H: Into<Cow<'a, str>>,
+    {
+        self.stylesheets.push(href.into());
+        self
+    }
+
+    /// Add a script to the head
+    pub fn script<S>(mut self, src: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.scripts.push(src.into());
+        self
+    }
+
+    /// Set the chart layout
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Lay out charts in a grid with a number of columns
+    ///
+    /// This is a shorthand for `layout(Layout::Grid(columns))`.  Zero
+    /// columns is treated as one.
+    pub fn columns(self, columns: u16) -> Self {
+        self.layout(Layout::Grid(columns))
+    }
+
+    /// Set the gap between charts, in pixels
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = Some(gap);
+        self
     }
 
     /// Add a `Chart`
-    pub fn chart(mut self, chart: Chart<'a, P>) -> Self {
-        self.charts.push(chart.stand_alone(false));
+    pub fn chart(self, chart: Chart<'a>) -> Self {
+        self.chart_entry(chart, None)
+    }
+
+    /// Add a `Chart`, with a caption below it
+    pub fn chart_captioned<C>(self, chart: Chart<'a>, caption: C) -> Self
+    where
+        C: Into<Cow<'a, str>>,
+    {
+        self.chart_entry(chart, Some(caption.into()))
+    }
+
+    /// Add a chart entry
+    fn chart_entry(
+        mut self,
+        chart: Chart<'a>,
+        caption: Option<Cow<'a, str>>,
+    ) -> Self {
+        let id = self.charts.len();
+        let chart = chart.stand_alone(false).id(id);
+        self.charts.push((Figure::Chart(Box::new(chart)), caption));
         self
     }
+
+    /// Add a `Radar` chart
+    pub fn radar(mut self, radar: Radar<'a>) -> Self {
+        let radar = radar.stand_alone(false);
+        self.charts.push((Figure::Radar(radar), None));
+        self
+    }
+
+    /// Add a `Chart`, with legend at a specified position
+    pub fn chart_with_legend(self, chart: Chart<'a>, pos: LegendPos) -> Self {
+        self.chart(chart.legend_pos(pos))
+    }
+
+    /// Render the page as indented HTML, for debugging
+    ///
+    /// Only indentation differs from the `Display` output.
+    pub fn to_string_pretty(&self) -> String {
+        indent(&self.to_string())
+    }
+
+    /// Write the page to an `io::Write`
+    ///
+    /// Output is streamed through a small buffer, without rendering the whole
+    /// page into a `String` first.
+    pub fn write_to<W: io::Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        write!(w, "{self}")?;
+        w.flush()
+    }
+
+    /// Save the page as an HTML file
+    ///
+    /// Parent directories are created as needed, and the file is replaced
+    /// atomically.
+    pub fn save_html<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        write_atomic(path, |file| self.write_to(file))
+    }
+
+    /// Save the page as `index.html` in a directory, along with assets
+    ///
+    /// If the page links to a stylesheet by relative path, the default
+    /// stylesheet is written there too.  Paths with `..` components are not
+    /// written, to stay within the directory.
+    pub fn save_with_assets<P>(&self, dir: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        if let Some(href) = self.stylesheet.relative_href() {
+            write_atomic(dir.join(href), |file| {
+                file.write_all(DEFAULT_CSS.as_bytes())
+            })?;
+        }
+        self.save_html(dir.join("index.html"))
+    }
 }
 
-impl<'a, P> fmt::Display for Page<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> Page<'a> {
+    /// Format head element contents
+    fn head_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "<meta charset='UTF-8'>")?;
+        if let Some(title) = &self.title {
+            writeln!(f, "<title>{}</title>", escape(title))?;
+        }
+        for (name, content) in &self.metas {
+            let (name, content) = (escape(name), escape(content));
+            writeln!(f, "<meta name='{name}' content='{content}'>")?;
+        }
+        self.stylesheet.html_fmt(f)?;
+        for href in &self.stylesheets {
+            let href = escape(href);
+            writeln!(f, "<link href='{href}' rel='stylesheet'/>")?;
+        }
+        for src in &self.scripts {
+            writeln!(f, "<script src='{}'></script>", escape(src))?;
+        }
+        writeln!(f, "</head>")
+    }
+
+    /// Format page div start, with layout styles
+    fn page_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut style = Vec::with_capacity(3);
+        match self.layout {
+            Layout::Flex { direction, wrap } => {
+                write!(f, "<div class='page'")?;
+                if direction == Direction::Row {
+                    style.push("flex-direction: row".to_string());
+                }
+                if wrap {
+                    style.push("flex-wrap: wrap".to_string());
+                }
+            }
+            Layout::Grid(columns) => {
+                write!(f, "<div class='page page-grid'")?;
+                let columns = columns.max(1);
+                style.push(format!(
+                    "grid-template-columns: repeat({columns}, 1fr)"
+                ));
+            }
+        }
+        if let Some(gap) = self.gap {
+            style.push(format!("gap: {gap}px"));
+        }
+        if !style.is_empty() {
+            write!(f, " style='{}'", style.join("; "))?;
+        }
+        writeln!(f, ">")
+    }
+}
+
+impl<'a> fmt::Display for Page<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "<html>")?;
         writeln!(f, "<head>")?;
-        writeln!(f, "<meta charset='UTF-8'>")?;
-        writeln!(f, "<link href='./css/splot.css' rel='stylesheet'/>")?;
-        writeln!(f, "</head>")?;
+        self.head_fmt(f)?;
         writeln!(f, "<body>")?;
-        writeln!(f, "<div class='page'>")?;
-        for chart in &self.charts {
-            writeln!(f, "<div class='chart'>")?;
+        self.page_fmt(f)?;
+        for (chart, caption) in &self.charts {
+            if caption.is_some() {
+                writeln!(f, "<div class='figure'>")?;
+            }
+            match chart.legend_position() {
+                Some(pos) => {
+                    writeln!(f, "<div class='chart {}'>", pos.class_name())?
+                }
+                None => writeln!(f, "<div class='chart'>")?,
+            }
             writeln!(f, "{chart}")?;
             writeln!(f, "</div>")?;
+            if let Some(caption) = caption {
+                writeln!(f, "<div class='caption'>{}</div>", escape(caption))?;
+                writeln!(f, "</div>")?;
+            }
         }
         writeln!(f, "</div>")?;
         writeln!(f, "</body>")?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Plot;
+
+    #[test]
+    fn legend_pos() {
+        let html = Page::new()
+            .chart(Chart::new())
+            .chart_with_legend(Chart::new(), LegendPos::Bottom)
+            .to_string();
+        assert!(html.contains("<div class='chart'>"));
+        assert!(html.contains("<div class='chart legend-bottom'>"));
+    }
+
+    #[test]
+    fn layout() {
+        let html = Page::new().to_string();
+        assert!(html.contains("<div class='page'>"));
+        let html = Page::new()
+            .columns(3)
+            .gap(16)
+            .chart_captioned(Chart::new(), "Figure 1: <CPU>")
+            .chart(Chart::new())
+            .to_string();
+        assert!(html.contains(
+            "<div class='page page-grid' \
+            style='grid-template-columns: repeat(3, 1fr); gap: 16px'>"
+        ));
+        assert_eq!(html.matches("<div class='figure'>").count(), 1);
+        assert!(html.contains(
+            "<div class='caption'>Figure 1: &lt;CPU&gt;</div>\n</div>"
+        ));
+        let html = Page::new().columns(0).to_string();
+        assert!(html.contains("grid-template-columns: repeat(1, 1fr)"));
+        let html = Page::new()
+            .layout(Layout::Flex {
+                direction: Direction::Row,
+                wrap: true,
+            })
+            .to_string();
+        assert!(html.contains(
+            "<div class='page' style='flex-direction: row; flex-wrap: wrap'>"
+        ));
+    }
+
+    #[test]
+    fn head() {
+        let html = Page::new().to_string();
+        assert!(html.starts_with(
+            "<html>\n<head>\n<meta charset='UTF-8'>\n\
+            <link href='./css/splot.css' rel='stylesheet'/>\n</head>\n"
+        ));
+        let html = Page::new()
+            .title("Q&A")
+            .meta("viewport", "width=device-width")
+            .stylesheet("site.css")
+            .script("app.js")
+            .to_string();
+        assert!(html.contains("<title>Q&amp;A</title>\n"));
+        assert!(html
+            .contains("<meta name='viewport' content='width=device-width'>\n"));
+        assert!(html.contains(
+            "stylesheet'/>\n<link href='site.css' rel='stylesheet'/>\n"
+        ));
+        assert!(html.contains("<script src='app.js'></script>\n</head>"));
+    }
+
+    #[test]
+    fn embed_css() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let html = Page::new()
+            .embed_css()
+            .chart(Chart::new().embed_css().plot(Plot::line("A", &data[..])))
+            .to_string();
+        assert!(html.contains("<style>"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains("href="));
+        assert!(!html.contains("src="));
+        assert!(!html.contains("@import"));
+    }
+
+    #[test]
+    fn unique_ids() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = || {
+            Chart::new()
+                .plot(Plot::line("A", &data[..]))
+                .plot(Plot::scatter("B", &data[..]))
+        };
+        let html = Page::new().chart(chart()).chart(chart()).to_string();
+        let mut ids: Vec<&str> = html
+            .split(" id='")
+            .skip(1)
+            .map(|s| &s[..s.find('\'').unwrap()])
+            .collect();
+        assert_eq!(ids.len(), 6);
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 6);
+        assert!(html.contains("clip-path='url(#clip-chart-1)'"));
+        assert!(html.contains("style='--marker: url(#marker-1-1)'"));
+    }
+
+    #[test]
+    fn mixed_point_types() {
+        let ints = [(0, 1), (1, 2)];
+        let floats = [(0.5, 1.5), (1.5, 0.5)];
+        let html = Page::new()
+            .chart(Chart::new().plot(Plot::line("Ints", &ints[..])))
+            .chart(Chart::new().plot(Plot::scatter("Floats", &floats[..])))
+            .to_string();
+        assert!(html.contains("Ints"));
+        assert!(html.contains("Floats"));
+    }
+
+    #[test]
+    fn write_to() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let page = Page::new()
+            .chart(Chart::new().plot(Plot::line("A", &data[..])))
+            .chart(Chart::new().plot(Plot::area("B", &data[..])));
+        let mut buf = Vec::new();
+        page.write_to(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), page.to_string());
+    }
+
+    #[test]
+    fn save_with_assets() {
+        let dir = std::env::temp_dir().join("splot-page-assets");
+        let _ = std::fs::remove_dir_all(&dir);
+        Page::new()
+            .chart(Chart::new())
+            .save_with_assets(&dir)
+            .unwrap();
+        let html = std::fs::read_to_string(dir.join("index.html")).unwrap();
+        assert!(html.contains("href='./css/splot.css'"));
+        let css = std::fs::read_to_string(dir.join("css/splot.css")).unwrap();
+        assert_eq!(css, DEFAULT_CSS);
+        std::fs::remove_dir_all(&dir).unwrap();
+        Page::new().embed_css().save_with_assets(&dir).unwrap();
+        assert!(!dir.join("css").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+// palette.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+
+/// Color palette for plots
+///
+/// When set on a `Chart`, colors are written directly as `fill` / `stroke`
+/// attributes, so the SVG renders correctly even without a stylesheet.
+/// Plots are assigned colors in order, cycling when there are more plots
+/// than colors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    colors: Vec<String>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::tableau10()
+    }
+}
+
+impl Palette {
+    /// Create a palette from a list of CSS colors
+    pub fn new<C>(colors: &[C]) -> Self
+    where
+        C: AsRef<str>,
+    {
+        let colors = colors.iter().map(|c| c.as_ref().to_string()).collect();
+        Palette { colors }
+    }
+
+    /// Tableau 10 palette (matches the default stylesheet)
+    pub fn tableau10() -> Self {
+        Self::new(&[
+            "#4E79A7", "#F28E2C", "#E15759", "#76B7B2", "#59A14F", "#EDC949",
+            "#AF7AA1", "#FF9DA7", "#9C755F", "#BAB0AB",
+        ])
+    }
+
+    /// Category 10 palette
+    pub fn category10() -> Self {
+        Self::new(&[
+            "#1F77B4", "#FF7F0E", "#2CA02C", "#D62728", "#9467BD", "#8C564B",
+            "#E377C2", "#7F7F7F", "#BCBD22", "#17BECF",
+        ])
+    }
+
+    /// Colorblind-safe palette (Okabe-Ito)
+    pub fn colorblind() -> Self {
+        Self::new(&[
+            "#E69F00", "#56B4E9", "#009E73", "#F0E442", "#0072B2", "#D55E00",
+            "#CC79A7", "#000000",
+        ])
+    }
+
+    /// Get color for a plot number
+    pub fn color(&self, num: usize) -> Option<&str> {
+        if self.colors.is_empty() {
+            None
+        } else {
+            Some(&self.colors[num % self.colors.len()])
+        }
+    }
+}
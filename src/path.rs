@@ -0,0 +1,149 @@
+// path.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Fast formatting of SVG path data
+//!
+//! Path data can contain millions of values, so these avoid `fmt` argument
+//! handling for each one.
+use std::fmt;
+
+/// Maximum length of a formatted `i32`
+const INT_LEN: usize = 11;
+
+/// Push an integer onto the end of a buffer, working backwards
+///
+/// Returns the new start position.
+fn push_int(buf: &mut [u8], mut pos: usize, value: i32) -> usize {
+    let mut n = value.unsigned_abs();
+    loop {
+        pos -= 1;
+        buf[pos] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        pos -= 1;
+        buf[pos] = b'-';
+    }
+    pos
+}
+
+/// Write ASCII bytes to a formatter
+fn write_ascii(f: &mut fmt::Formatter, buf: &[u8]) -> fmt::Result {
+    f.write_str(std::str::from_utf8(buf).map_err(|_| fmt::Error)?)
+}
+
+/// Write a path command (or separator) followed by one value
+pub(crate) fn cmd_fmt(
+    f: &mut fmt::Formatter,
+    cmd: u8,
+    value: i32,
+) -> fmt::Result {
+    let mut buf = [0; INT_LEN + 1];
+    let mut pos = push_int(&mut buf, INT_LEN + 1, value);
+    pos -= 1;
+    buf[pos] = cmd;
+    write_ascii(f, &buf[pos..])
+}
+
+/// Write a path command (or separator) followed by a point
+pub(crate) fn point_fmt(
+    f: &mut fmt::Formatter,
+    cmd: u8,
+    x: i32,
+    y: i32,
+) -> fmt::Result {
+    let mut buf = [0; INT_LEN * 2 + 2];
+    let mut pos = push_int(&mut buf, INT_LEN * 2 + 2, y);
+    pos -= 1;
+    buf[pos] = b' ';
+    pos = push_int(&mut buf, pos, x);
+    pos -= 1;
+    buf[pos] = cmd;
+    write_ascii(f, &buf[pos..])
+}
+
+/// Write one value, with decimals
+///
+/// With zero decimals, the value is rounded to an integer.
+pub(crate) fn dec_fmt(
+    f: &mut fmt::Formatter,
+    value: f64,
+    decimals: u8,
+) -> fmt::Result {
+    match usize::from(decimals) {
+        0 => {
+            let mut buf = [0; INT_LEN];
+            let pos = push_int(&mut buf, INT_LEN, value.round() as i32);
+            write_ascii(f, &buf[pos..])
+        }
+        d => write!(f, "{value:.d$}"),
+    }
+}
+
+/// Write a path command (or separator) followed by a point, with decimals
+///
+/// With zero decimals, values are rounded to integers.
+pub(crate) fn point_dec_fmt(
+    f: &mut fmt::Formatter,
+    cmd: u8,
+    x: f64,
+    y: f64,
+    decimals: u8,
+) -> fmt::Result {
+    match usize::from(decimals) {
+        0 => point_fmt(f, cmd, x.round() as i32, y.round() as i32),
+        d => write!(f, "{}{x:.d$} {y:.d$}", char::from(cmd)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Point(u8, i32, i32);
+
+    struct Decimal(f64, f64, u8);
+
+    struct Value(f64, u8);
+
+    impl fmt::Display for Value {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            dec_fmt(f, self.0, self.1)
+        }
+    }
+
+    impl fmt::Display for Decimal {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            point_dec_fmt(f, b'M', self.0, self.1, self.2)
+        }
+    }
+
+    impl fmt::Display for Point {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            point_fmt(f, self.0, self.1, self.2)?;
+            cmd_fmt(f, b'h', self.1)
+        }
+    }
+
+    #[test]
+    fn ints() {
+        for (x, y) in [(0, 0), (7, -3), (-1200, 45), (i32::MIN, i32::MAX)] {
+            let expected = format!("M{x} {y}h{x}");
+            assert_eq!(Point(b'M', x, y).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn decimals() {
+        assert_eq!(Decimal(1.25, -7.4, 0).to_string(), "M1 -7");
+        assert_eq!(Decimal(1.26, -7.5, 1).to_string(), "M1.3 -7.5");
+        assert_eq!(Decimal(1.0, 2.346, 2).to_string(), "M1.00 2.35");
+        assert_eq!(Value(-0.4, 0).to_string(), "0");
+        assert_eq!(Value(2.5, 0).to_string(), "3");
+        assert_eq!(Value(2.346, 1).to_string(), "2.3");
+    }
+}
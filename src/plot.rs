@@ -4,125 +4,1009 @@
 //
 //! Plot types
 //!
-use crate::domain::BoundDomain;
-use crate::point::{IntoPoint, Point};
+use crate::domain::{BoundDomain, Domain};
+use crate::path::{dec_fmt, point_dec_fmt};
+use crate::point::{BandPoint, IntoPoint, LabeledPoint, Point, RangePoint};
 use crate::rect::Edge;
-use crate::text::{Label, Text};
+use crate::sample::{lttb, moving_average};
+use crate::style::inline_attrs;
+use crate::text::{escape, Label, Text};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt;
 
-/// Plot settings
-pub struct PlotSettings<'a, P>
+/// Thickness of range bars, in viewBox units
+const RANGE_THICKNESS: f64 = 40.0;
+
+/// Bar width, as a fraction of the spacing between `X` values
+const BAR_FRACTION: f64 = 0.8;
+
+/// Line dash pattern
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum Dash {
+    /// Solid line
+    #[default]
+    Solid,
+    /// Dashed line
+    Dashed,
+    /// Dotted line
+    Dotted,
+}
+
+/// Running totals of stacked plots, by `X` value
+///
+/// Positive and negative values are stacked separately, away from zero.
+#[derive(Default)]
+pub(crate) struct Stacks {
+    /// Positive totals
+    pos: HashMap<u64, f64>,
+    /// Negative totals
+    neg: HashMap<u64, f64>,
+    /// Magnitude totals of normalized plots
+    totals: HashMap<u64, f64>,
+}
+
+impl Stacks {
+    /// Add the magnitude of a normalized point to its `X` total
+    fn add_total(&mut self, pt: Point) {
+        *self.totals.entry(pt.x.to_bits()).or_insert(0.0) += pt.y.abs();
+    }
+
+    /// Check if any normalized plots were totaled
+    pub(crate) fn is_normalized(&self) -> bool {
+        !self.totals.is_empty()
+    }
+
+    /// Push a point onto the stack at its `X` value
+    ///
+    /// A normalized point is divided by the total at its `X` value first,
+    /// or becomes zero if that total is zero.  Returns the base and top
+    /// values of the point.
+    fn push(&mut self, pt: Point, normalized: bool) -> (f64, f64) {
+        let y = if normalized {
+            match self.totals.get(&pt.x.to_bits()) {
+                Some(total) if *total > 0.0 => pt.y / total,
+                _ => 0.0,
+            }
+        } else {
+            pt.y
+        };
+        let totals = if y >= 0.0 {
+            &mut self.pos
+        } else {
+            &mut self.neg
+        };
+        let total = totals.entry(pt.x.to_bits()).or_insert(0.0);
+        let base = *total;
+        *total += y;
+        (base, *total)
+    }
+
+    /// Get the range of stacked values, including zero
+    ///
+    /// Returns `None` if nothing is stacked.
+    pub(crate) fn y_range(&self) -> Option<(f64, f64)> {
+        if self.pos.is_empty() && self.neg.is_empty() {
+            return None;
+        }
+        let hi = self.pos.values().fold(0.0, |a: f64, b| a.max(*b));
+        let lo = self.neg.values().fold(0.0, |a: f64, b| a.min(*b));
+        Some((lo, hi))
+    }
+}
+
+/// Selection of points to label
+#[derive(Default)]
+enum LabelPoints<'a> {
+    /// All points
+    #[default]
+    All,
+    /// First point
+    First,
+    /// Last point
+    Last,
+    /// Points with minimum and maximum `Y` values
+    Extremes,
+    /// Every Nth point, starting with the first
+    Every(usize),
+    /// Points matching a predicate
+    Filter(Box<dyn Fn(usize, Point) -> bool + 'a>),
+}
+
+impl<'a> LabelPoints<'a> {
+    /// Select points to label, by index
+    ///
+    /// Missing points are never selected.
+    fn select(&self, points: &[Point]) -> Vec<bool> {
+        let present = |i: &usize| !points[*i].is_missing();
+        let mut indices = (0..points.len()).filter(present);
+        let mut selected = vec![false; points.len()];
+        match self {
+            LabelPoints::All => indices.for_each(|i| selected[i] = true),
+            LabelPoints::First => {
+                if let Some(i) = indices.next() {
+                    selected[i] = true;
+                }
+            }
+            LabelPoints::Last => {
+                if let Some(i) = indices.next_back() {
+                    selected[i] = true;
+                }
+            }
+            LabelPoints::Extremes => {
+                let y = |i: &usize| points[*i].y;
+                let min = indices.clone().min_by(|a, b| y(a).total_cmp(&y(b)));
+                let max = indices.max_by(|a, b| y(a).total_cmp(&y(b)));
+                for i in min.into_iter().chain(max) {
+                    selected[i] = true;
+                }
+            }
+            LabelPoints::Every(n) => indices
+                .step_by((*n).max(1))
+                .for_each(|i| selected[i] = true),
+            LabelPoints::Filter(filter) => indices
+                .filter(|i| filter(*i, points[*i]))
+                .for_each(|i| selected[i] = true),
+        }
+        selected
+    }
+}
+
+/// Borrowed records with a mapping to points
+trait Mapped {
+    /// Get number of records
+    fn len(&self) -> usize;
+
+    /// Get a point by index
+    fn point(&self, i: usize) -> Point;
+}
+
+/// Record slice with point accessor closure
+struct Mapping<'a, T, F> {
+    records: &'a [T],
+    map: F,
+}
+
+impl<'a, T, F, Q> Mapped for Mapping<'a, T, F>
+where
+    F: Fn(&T) -> Q,
+    Q: IntoPoint,
+{
+    fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    fn point(&self, i: usize) -> Point {
+        (self.map)(&self.records[i]).into()
+    }
+}
+
+impl<P> Mapped for &[P]
 where
     P: IntoPoint,
 {
+    fn len(&self) -> usize {
+        <[P]>::len(self)
+    }
+
+    fn point(&self, i: usize) -> Point {
+        self[i].into()
+    }
+}
+
+/// Plot data values
+enum Data<'a> {
+    /// Borrowed data, converted to points while rendering
+    Borrowed(Box<dyn Mapped + 'a>),
+    /// Owned points
+    Owned(Vec<Point>),
+}
+
+/// Plot settings
+pub struct PlotSettings<'a> {
     /// Values name
-    name: &'a str,
+    name: Cow<'a, str>,
     /// Number within chart
     num: u32,
+    /// Plot-specific domain
+    own_domain: Option<Domain>,
     /// Domain bound to rectangle
     domain: BoundDomain,
     /// Data values
-    data: &'a [P],
+    data: Data<'a>,
     /// Label settings
     label: Option<Label>,
+    /// Line dash pattern
+    dash: Dash,
+    /// Line stroke width
+    stroke_width: Option<f32>,
+    /// Markers at data points
+    markers: bool,
+    /// Include automatic `plot-{num}` class
+    num_class: bool,
+    /// Custom class name
+    class_name: Option<&'a str>,
+    /// Inline style
+    style: Option<&'a str>,
+    /// Maximum number of points to render
+    max_points: Option<usize>,
+    /// Color from palette
+    color: Option<String>,
+    /// Use inline presentational attributes
+    inline: bool,
+    /// Decimal places of path coordinates
+    precision: u8,
+    /// Marker ID, if not `marker-{num}`
+    marker: Option<String>,
+    /// Hover tooltips
+    tooltips: bool,
+    /// Embed data values as `data-*` attributes
+    embed_data: bool,
+    /// Element IDs and hover structure
+    interactive: bool,
+    /// Leave out of the legend
+    hide_from_legend: bool,
+    /// Line draw animation duration and start delay, in seconds
+    animate: Option<(f32, f32)>,
+    /// Element ID prefix, if not `plot-{num}`
+    element_id: Option<String>,
+    /// Least-squares trend line
+    trend: bool,
+    /// Band around a line, as (`X`, low `Y`, high `Y`)
+    band: Option<Vec<(f64, f64, f64)>>,
+    /// Extend the chart domain to include zero (area and bar plots)
+    include_zero: bool,
+    /// Stack on previous stacked plots
+    stacked: bool,
+    /// Normalize stack to fractions of each `X` total
+    normalized: bool,
+    /// Base and top values of each point, when stacked
+    spans: Vec<(f64, f64)>,
+    /// Custom label text for each point
+    point_labels: Option<Vec<Option<String>>>,
+    /// Label text formatter
+    label_text: Option<Box<dyn Fn(Point) -> String + 'a>>,
+    /// Points to label
+    label_points: LabelPoints<'a>,
+}
+
+impl<'a> Data<'a> {
+    /// Get number of points
+    fn len(&self) -> usize {
+        match self {
+            Data::Borrowed(data) => data.len(),
+            Data::Owned(data) => data.len(),
+        }
+    }
+
+    /// Get an iterator of points
+    fn iter(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+        match self {
+            Data::Borrowed(data) => {
+                Box::new((0..data.len()).map(|i| data.point(i)))
+            }
+            Data::Owned(data) => Box::new(data.iter().copied()),
+        }
+    }
+}
+
+/// Type of plot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlotType {
+    /// Area plot
+    Area,
+    /// Bar plot
+    Bar,
+    /// Line plot
+    Line,
+    /// Range plot
+    Range,
+    /// Scatter plot
+    Scatter,
 }
 
 /// Plot for rendering data
-pub enum Plot<'a, P>
-where
-    P: IntoPoint,
-{
-    /// Stacked area plot
-    Area(PlotSettings<'a, P>),
+pub enum Plot<'a> {
+    /// Area plot
+    Area(PlotSettings<'a>),
+    /// Bar plot
+    Bar(PlotSettings<'a>),
     /// Line plot
-    Line(PlotSettings<'a, P>),
+    Line(PlotSettings<'a>),
+    /// Range plot
+    Range(PlotSettings<'a>),
     /// Scatter plot
-    Scatter(PlotSettings<'a, P>),
+    Scatter(PlotSettings<'a>),
 }
 
-impl<'a, P> PlotSettings<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> PlotSettings<'a> {
     /// Create new plot settings
-    fn new(name: &'a str, data: &'a [P]) -> Self {
+    fn new(name: Cow<'a, str>, data: Data<'a>) -> Self {
         PlotSettings {
             name,
             num: 0,
+            own_domain: None,
             domain: BoundDomain::default(),
             data,
             label: None,
+            dash: Dash::Solid,
+            stroke_width: None,
+            markers: false,
+            num_class: true,
+            class_name: None,
+            style: None,
+            max_points: None,
+            color: None,
+            inline: false,
+            precision: 0,
+            marker: None,
+            tooltips: false,
+            embed_data: false,
+            interactive: false,
+            hide_from_legend: false,
+            animate: None,
+            element_id: None,
+            trend: false,
+            band: None,
+            include_zero: true,
+            stacked: false,
+            normalized: false,
+            spans: Vec::new(),
+            point_labels: None,
+            label_text: None,
+            label_points: LabelPoints::All,
+        }
+    }
+
+    /// Get data points to render, downsampled if needed
+    fn points(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+        let data = self.data.iter();
+        match self.max_points {
+            Some(max_points) if max_points < self.data.len() => {
+                Box::new(lttb(data.collect(), max_points).into_iter())
+            }
+            _ => data,
+        }
+    }
+
+    /// Format class and style attributes
+    fn class_fmt(&self, f: &mut fmt::Formatter, kind: &str) -> fmt::Result {
+        let mut classes = Vec::with_capacity(3);
+        let num = format!("plot-{}", self.num);
+        if self.num_class {
+            classes.push(num.as_str());
+        }
+        if !kind.is_empty() {
+            classes.push(kind);
+        }
+        let class_name = self.class_name.map(escape);
+        if let Some(class_name) = &class_name {
+            classes.push(class_name);
+        }
+        if !classes.is_empty() {
+            write!(f, " class='{}'", classes.join(" "))?;
+        }
+        match (self.style.map(escape), &self.marker) {
+            (Some(style), Some(marker)) => {
+                write!(f, " style='{style}; --marker: url(#{marker})'")?
+            }
+            (Some(style), None) => write!(f, " style='{style}'")?,
+            (None, Some(marker)) => {
+                write!(f, " style='--marker: url(#{marker})'")?
+            }
+            (None, None) => (),
+        }
+        if self.inline {
+            self.inline_fmt(f, kind)?;
+        }
+        Ok(())
+    }
+
+    /// Format inline presentational attributes
+    ///
+    /// Colors are not included, since `color_fmt` writes them.
+    fn inline_fmt(&self, f: &mut fmt::Formatter, kind: &str) -> fmt::Result {
+        match kind {
+            "plot-bar" | "legend-bar" | "plot-range" | "legend-range" => {
+                return write!(f, " stroke='none'");
+            }
+            "plot-band" | "legend-band" => {
+                return write!(f, " stroke='none' fill-opacity='0.25'");
+            }
+            "plot-area" | "legend-area" => write!(f, " stroke='none'")?,
+            "plot-line" | "legend-line" => write!(f, " fill='none'")?,
+            "plot-scatter" => write!(f, " fill='none' stroke='none'")?,
+            "plot-trend" => write!(f, " fill='none'")?,
+            _ => return Ok(()),
+        }
+        if self.stroke_width.is_none() {
+            write!(f, " stroke-width='5'")?;
+        }
+        match kind {
+            "legend-area" | "plot-trend" => Ok(()),
+            "legend-line" => {
+                write!(f, " marker-mid='url(#{})'", self.marker_id())
+            }
+            _ => self.markers_fmt(f),
+        }
+    }
+
+    /// Get marker ID
+    fn marker_id(&self) -> String {
+        match &self.marker {
+            Some(marker) => marker.clone(),
+            None => format!("marker-{}", self.num),
+        }
+    }
+
+    /// Get element ID prefix
+    fn element_id(&self) -> String {
+        match &self.element_id {
+            Some(id) => id.clone(),
+            None => format!("plot-{}", self.num),
+        }
+    }
+
+    /// Format element ID attribute, if interactive
+    fn id_fmt(&self, f: &mut fmt::Formatter, suffix: &str) -> fmt::Result {
+        if self.interactive {
+            write!(f, " id='{}-{suffix}'", self.element_id())?;
+        }
+        Ok(())
+    }
+
+    /// Format marker attributes
+    fn markers_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let marker = self.marker_id();
+        write!(f, " marker-start='url(#{marker})'")?;
+        write!(f, " marker-mid='url(#{marker})'")?;
+        write!(f, " marker-end='url(#{marker})'")
+    }
+
+    /// Format color attribute
+    fn color_fmt(&self, f: &mut fmt::Formatter, attr: &str) -> fmt::Result {
+        match &self.color {
+            Some(color) => write!(f, " {attr}='{}'", escape(color)),
+            None => Ok(()),
+        }
+    }
+
+    /// Format stroke attributes
+    fn stroke_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.dash {
+            Dash::Solid => (),
+            Dash::Dashed => write!(f, " stroke-dasharray='20 12'")?,
+            Dash::Dotted => write!(f, " stroke-dasharray='4 8'")?,
+        }
+        if let Some(width) = self.stroke_width {
+            write!(f, " stroke-width='{width}'")?;
+        }
+        Ok(())
+    }
+
+    /// Fit a least-squares line through the data
+    ///
+    /// Returns `None` with fewer than two points or no `X` variance.
+    fn trend_coefficients(&self) -> Option<(f64, f64)> {
+        let (mut n, mut sx, mut sy) = (0.0, 0.0, 0.0);
+        for pt in self.data.iter().filter(|pt| !pt.is_missing()) {
+            n += 1.0;
+            sx += pt.x;
+            sy += pt.y;
+        }
+        if n < 2.0 {
+            return None;
+        }
+        let (mx, my) = (sx / n, sy / n);
+        let (mut sxx, mut sxy) = (0.0, 0.0);
+        for pt in self.data.iter().filter(|pt| !pt.is_missing()) {
+            sxx += (pt.x - mx) * (pt.x - mx);
+            sxy += (pt.x - mx) * (pt.y - my);
+        }
+        // relative to magnitude, so equal values with rounding error fail
+        if sxx <= f64::EPSILON * n * mx * mx {
+            return None;
+        }
+        let slope = sxy / sxx;
+        Some((slope, my - slope * mx))
+    }
+
+    /// Format a trend line across the `X` domain
+    fn trend_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((slope, intercept)) = self.trend_coefficients() else {
+            return Ok(());
+        };
+        write!(f, "<path")?;
+        self.class_fmt(f, "plot-trend")?;
+        self.color_fmt(f, "stroke")?;
+        match self.dash {
+            Dash::Dotted => write!(f, " stroke-dasharray='4 8'")?,
+            _ => write!(f, " stroke-dasharray='20 12'")?,
+        }
+        if let Some(width) = self.stroke_width {
+            write!(f, " stroke-width='{width}'")?;
+        }
+        write!(f, " d='")?;
+        let (x0, x1) = self.domain.x_extent();
+        for (cmd, x) in [(b'M', x0), (b' ', x1)] {
+            let y = slope * x + intercept;
+            let (x, y) = (self.domain.x_pos(x), self.domain.y_pos(y));
+            point_dec_fmt(f, cmd, x, y, self.precision)?;
+        }
+        writeln!(f, "'/>")
+    }
+
+    /// Add point magnitudes to totals, if normalized
+    fn total(&self, stacks: &mut Stacks) {
+        if self.normalized {
+            for pt in self.data.iter().filter(|pt| !pt.is_missing()) {
+                stacks.add_total(pt);
+            }
+        }
+    }
+
+    /// Stack points onto running totals
+    fn stack(&mut self, stacks: &mut Stacks) {
+        self.spans = self
+            .data
+            .iter()
+            .map(|pt| {
+                if pt.is_missing() {
+                    (f64::NAN, f64::NAN)
+                } else {
+                    stacks.push(pt, self.normalized)
+                }
+            })
+            .collect();
+    }
+
+    /// Get stacked points, with the `Y` position of each base
+    fn stacked_points(&self) -> impl Iterator<Item = (Point, f64)> + '_ {
+        self.data
+            .iter()
+            .zip(self.spans.iter())
+            .filter(|(pt, _)| !pt.is_missing())
+            .map(|(pt, (base, top))| {
+                let top = Point { x: pt.x, y: *top };
+                (top, self.domain.y_pos(*base))
+            })
+    }
+
+    /// Format a stacked area plot
+    ///
+    /// The top edge is drawn forward, then the base edge backward.
+    fn stacked_area_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<path")?;
+        self.id_fmt(f, "path")?;
+        self.class_fmt(f, "plot-area")?;
+        self.color_fmt(f, "fill")?;
+        self.stroke_fmt(f)?;
+        write!(f, " d='")?;
+        let points: Vec<_> = self.stacked_points().collect();
+        for (i, (pt, _)) in points.iter().enumerate() {
+            let x = self.domain.x_pos(pt.x);
+            let y = self.domain.y_pos(pt.y);
+            let cmd = if i == 0 { b'M' } else { b' ' };
+            point_dec_fmt(f, cmd, x, y, self.precision)?;
+        }
+        for (pt, base) in points.iter().rev() {
+            let x = self.domain.x_pos(pt.x);
+            point_dec_fmt(f, b' ', x, *base, self.precision)?;
+        }
+        if self.tooltips {
+            self.title_fmt(f)
+        } else {
+            writeln!(f, "' />")
+        }
+    }
+
+    /// Get the width of bars
+    ///
+    /// This is a fraction of the closest spacing between `X` values, or of
+    /// the domain width if there is only one.
+    fn bar_width(&self) -> f64 {
+        let mut xs: Vec<f64> = self
+            .data
+            .iter()
+            .filter(|pt| !pt.is_missing())
+            .map(|pt| self.domain.x_pos(pt.x))
+            .collect();
+        xs.sort_by(f64::total_cmp);
+        let gap = xs
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .filter(|gap| *gap > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        let gap = if gap.is_finite() {
+            gap
+        } else {
+            let (x0, x1) = self.domain.x_extent();
+            (self.domain.x_pos(x1) - self.domain.x_pos(x0)).abs()
+        };
+        gap * BAR_FRACTION
+    }
+
+    /// Get the `X` range covered by bars, if there are at least two
+    ///
+    /// This extends half a bar width past the first and last values.
+    fn bar_x_range(&self) -> Option<(f64, f64)> {
+        let mut xs: Vec<f64> = self
+            .data
+            .iter()
+            .filter(|pt| !pt.is_missing())
+            .map(|pt| pt.x)
+            .collect();
+        xs.sort_by(f64::total_cmp);
+        let gap = xs
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .filter(|gap| *gap > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        if gap.is_finite() {
+            let half = gap * BAR_FRACTION / 2.0;
+            Some((xs[0] - half, xs[xs.len() - 1] + half))
+        } else {
+            None
+        }
+    }
+
+    /// Format a bar plot
+    fn bar_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<path")?;
+        self.id_fmt(f, "path")?;
+        self.class_fmt(f, "plot-bar")?;
+        self.color_fmt(f, "fill")?;
+        write!(f, " d='")?;
+        let half = self.bar_width() / 2.0;
+        let points: Vec<(Point, f64)> = if self.stacked {
+            self.stacked_points().collect()
+        } else {
+            let base = self.domain.y_baseline();
+            self.data
+                .iter()
+                .filter(|pt| !pt.is_missing())
+                .map(|pt| (pt, base))
+                .collect()
+        };
+        for (pt, base) in points {
+            let x = self.domain.x_pos(pt.x);
+            let y = self.domain.y_pos(pt.y);
+            point_dec_fmt(f, b'M', x - half, base, self.precision)?;
+            point_dec_fmt(f, b' ', x - half, y, self.precision)?;
+            point_dec_fmt(f, b' ', x + half, y, self.precision)?;
+            point_dec_fmt(f, b' ', x + half, base, self.precision)?;
+            f.write_str("z")?;
+        }
+        if self.tooltips {
+            self.title_fmt(f)
+        } else {
+            writeln!(f, "' />")
+        }
+    }
+
+    /// Format a range plot
+    ///
+    /// Points are pairs of start and end, one horizontal bar per pair.
+    fn range_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<path")?;
+        self.id_fmt(f, "path")?;
+        self.class_fmt(f, "plot-range")?;
+        self.color_fmt(f, "fill")?;
+        write!(f, " d='")?;
+        let half = RANGE_THICKNESS / 2.0;
+        let points: Vec<Point> = self.data.iter().collect();
+        for pair in points.chunks_exact(2) {
+            if pair[0].is_missing() || pair[1].is_missing() {
+                continue;
+            }
+            let x0 = self.domain.x_pos(pair[0].x);
+            let x1 = self.domain.x_pos(pair[1].x);
+            let y = self.domain.y_pos(pair[0].y);
+            point_dec_fmt(f, b'M', x0, y - half, self.precision)?;
+            point_dec_fmt(f, b' ', x1, y - half, self.precision)?;
+            point_dec_fmt(f, b' ', x1, y + half, self.precision)?;
+            point_dec_fmt(f, b' ', x0, y + half, self.precision)?;
+            f.write_str("z")?;
+        }
+        if self.tooltips {
+            self.title_fmt(f)
+        } else {
+            writeln!(f, "' />")
         }
     }
 
     /// Format an area plot
     fn area_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "<path class='plot-{} plot-area' d='", self.num)?;
-        if let Some(pt) = self.data.first() {
-            let pt: Point = (*pt).into();
-            let x = self.domain.x_map(pt.x);
-            let y = self.domain.y_map(0.0);
-            write!(f, "M{x} {y}")?;
+        if self.stacked {
+            return self.stacked_area_fmt(f);
         }
+        write!(f, "<path")?;
+        self.id_fmt(f, "path")?;
+        self.class_fmt(f, "plot-area")?;
+        self.color_fmt(f, "fill")?;
+        self.stroke_fmt(f)?;
+        write!(f, " d='")?;
+        let base = self.domain.y_baseline();
+        // each run of present points is anchored to the baseline
+        let mut last = None;
         for pt in self.data.iter() {
-            let pt: Point = (*pt).into();
-            let x = self.domain.x_map(pt.x);
-            let y = self.domain.y_map(pt.y);
-            write!(f, " {x} {y}")?;
+            if pt.is_missing() {
+                if let Some(x) = last.take() {
+                    point_dec_fmt(f, b' ', x, base, self.precision)?;
+                }
+                continue;
+            }
+            let x = self.domain.x_pos(pt.x);
+            if last.is_none() {
+                point_dec_fmt(f, b'M', x, base, self.precision)?;
+            }
+            let y = self.domain.y_pos(pt.y);
+            point_dec_fmt(f, b' ', x, y, self.precision)?;
+            last = Some(x);
         }
-        if let Some(pt) = self.data.last() {
-            let pt: Point = (*pt).into();
-            let x = self.domain.x_map(pt.x);
-            let y = self.domain.y_map(0.0);
-            write!(f, " {x} {y}")?;
+        if let Some(x) = last {
+            point_dec_fmt(f, b' ', x, base, self.precision)?;
+        }
+        if self.tooltips {
+            self.title_fmt(f)
+        } else {
+            writeln!(f, "' />")
         }
-        writeln!(f, "' />")
+    }
+
+    /// Format a band around a line, as a closed path
+    fn band_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        band: &[(f64, f64, f64)],
+    ) -> fmt::Result {
+        write!(f, "<path")?;
+        self.class_fmt(f, "plot-band")?;
+        self.color_fmt(f, "fill")?;
+        write!(f, " d='")?;
+        let present = |(x, lo, hi): &&(f64, f64, f64)| {
+            !(x.is_nan() || lo.is_nan() || hi.is_nan())
+        };
+        for (i, (x, lo, _hi)) in band.iter().filter(present).enumerate() {
+            let x = self.domain.x_pos(*x);
+            let y = self.domain.y_pos(*lo);
+            let cmd = if i == 0 { b'M' } else { b' ' };
+            point_dec_fmt(f, cmd, x, y, self.precision)?;
+        }
+        for (x, _lo, hi) in band.iter().rev().filter(present) {
+            let x = self.domain.x_pos(*x);
+            let y = self.domain.y_pos(*hi);
+            point_dec_fmt(f, b' ', x, y, self.precision)?;
+        }
+        writeln!(f, "z'/>")
     }
 
     /// Format a line plot
     fn line_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "<path class='plot-{} plot-line' d='", self.num)?;
-        for (i, pt) in self.data.iter().enumerate() {
-            let pt = (*pt).into();
-            let x = self.domain.x_map(pt.x);
-            let y = self.domain.y_map(pt.y);
-            if i == 0 {
-                write!(f, "M{x} {y}")?;
-            } else {
-                write!(f, " {x} {y}")?;
+        if let Some(band) = &self.band {
+            self.band_fmt(f, band)?;
+        }
+        write!(f, "<path")?;
+        self.id_fmt(f, "path")?;
+        self.class_fmt(f, "plot-line")?;
+        self.color_fmt(f, "stroke")?;
+        if self.animate.is_some() {
+            // dash pattern is replaced by the animated one
+            write!(f, " pathLength='1' stroke-dasharray='1'")?;
+            write!(f, " stroke-dashoffset='1'")?;
+            if let Some(width) = self.stroke_width {
+                write!(f, " stroke-width='{width}'")?;
             }
+        } else {
+            self.stroke_fmt(f)?;
+        }
+        if self.markers && !self.inline {
+            self.markers_fmt(f)?;
+        }
+        if self.embed_data {
+            self.name_fmt(f)?;
+        }
+        write!(f, " d='")?;
+        let mut gap = true;
+        for pt in self.points() {
+            if pt.is_missing() {
+                gap = true;
+                continue;
+            }
+            let x = self.domain.x_pos(pt.x);
+            let y = self.domain.y_pos(pt.y);
+            let cmd = if gap { b'M' } else { b' ' };
+            point_dec_fmt(f, cmd, x, y, self.precision)?;
+            gap = false;
+        }
+        if let Some((duration, delay)) = self.animate {
+            write!(f, "'>")?;
+            if self.tooltips {
+                write!(f, "<title>{}</title>", escape(&self.name))?;
+            }
+            write!(f, "<animate attributeName='stroke-dashoffset'")?;
+            write!(f, " from='1' to='0' dur='{duration}s' begin='{delay}s'")?;
+            writeln!(f, " fill='freeze'/></path>")?;
+        } else if self.tooltips {
+            self.title_fmt(f)?;
+        } else {
+            writeln!(f, "'/>")?;
+        }
+        if (self.tooltips && self.markers)
+            || self.embed_data
+            || self.interactive
+        {
+            self.point_targets_fmt(f, self.interactive)?;
         }
-        writeln!(f, "'/>")?;
         self.labels_fmt(f)
     }
 
     /// Format a scatter plot
     fn scatter_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "<path class='plot-{} plot-scatter' d='", self.num)?;
-        for (i, pt) in self.data.iter().enumerate() {
-            let pt = (*pt).into();
-            let x = self.domain.x_map(pt.x);
-            let y = self.domain.y_map(pt.y);
-            if i == 0 {
-                write!(f, "M{x} {y}")?;
+        if self.embed_data || self.interactive {
+            self.scatter_points_fmt(f)?;
+        } else {
+            self.scatter_path_fmt(f)?;
+        }
+        if self.tooltips {
+            self.point_targets_fmt(f, false)?;
+        }
+        self.labels_fmt(f)
+    }
+
+    /// Format a scatter plot as one path
+    fn scatter_path_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<path")?;
+        self.id_fmt(f, "path")?;
+        self.class_fmt(f, "plot-scatter")?;
+        write!(f, " d='")?;
+        let data = self.points().filter(|pt| !pt.is_missing());
+        for (i, pt) in data.enumerate() {
+            let x = self.domain.x_pos(pt.x);
+            let y = self.domain.y_pos(pt.y);
+            let cmd = if i == 0 { b'M' } else { b' ' };
+            point_dec_fmt(f, cmd, x, y, self.precision)?;
+        }
+        writeln!(f, "' />")
+    }
+
+    /// Format a scatter plot as one path per point, with data attributes
+    fn scatter_points_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<g")?;
+        if self.embed_data {
+            self.name_fmt(f)?;
+        }
+        writeln!(f, ">")?;
+        for (n, pt) in self.points().enumerate() {
+            if pt.is_missing() {
+                continue;
+            }
+            let x = self.domain.x_pos(pt.x);
+            let y = self.domain.y_pos(pt.y);
+            write!(f, "<path")?;
+            self.id_fmt(f, &format!("pt-{n}"))?;
+            self.class_fmt(f, "plot-scatter")?;
+            if self.embed_data {
+                self.data_fmt(f, pt)?;
+            }
+            write!(f, " d='")?;
+            point_dec_fmt(f, b'M', x, y, self.precision)?;
+            writeln!(f, "h0' />")?;
+        }
+        writeln!(f, "</g>")
+    }
+
+    /// Format series name data attribute
+    fn name_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, " data-name='{}'", escape(&self.name))
+    }
+
+    /// Format data value attributes of one point
+    fn data_fmt(&self, f: &mut fmt::Formatter, pt: Point) -> fmt::Result {
+        let (x, y) = match &self.label {
+            Some(label) => (label.rounded(pt.x), label.rounded(pt.y)),
+            None => (pt.x.to_string(), pt.y.to_string()),
+        };
+        write!(f, " data-x='{x}' data-y='{y}'")
+    }
+
+    /// Close a path with a series name title
+    fn title_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "'><title>{}</title></path>", escape(&self.name))
+    }
+
+    /// Format transparent hover targets for each point
+    ///
+    /// These hold tooltips and data attributes, when enabled, and element
+    /// IDs if `ids` is set.
+    fn point_targets_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        ids: bool,
+    ) -> fmt::Result {
+        writeln!(f, "<g class='plot-points'>")?;
+        let attrs = if self.inline {
+            inline_attrs("plot-point")
+        } else {
+            ""
+        };
+        for (n, pt) in self.points().enumerate() {
+            if pt.is_missing() {
+                continue;
+            }
+            let x = self.domain.x_pos(pt.x);
+            let y = self.domain.y_pos(pt.y);
+            write!(f, "<circle")?;
+            if ids {
+                self.id_fmt(f, &format!("pt-{n}"))?;
+            }
+            write!(f, " class='plot-point'{attrs}")?;
+            if self.embed_data {
+                self.data_fmt(f, pt)?;
+            }
+            write!(f, " cx='")?;
+            dec_fmt(f, x, self.precision)?;
+            write!(f, "' cy='")?;
+            dec_fmt(f, y, self.precision)?;
+            write!(f, "' r='12'")?;
+            if self.tooltips {
+                let text = self.point_text(pt);
+                writeln!(f, "><title>{text}</title>")?;
+                writeln!(f, "</circle>")?;
             } else {
-                write!(f, " {x} {y}")?;
+                writeln!(f, "/>")?;
             }
         }
-        writeln!(f, "' />")?;
-        self.labels_fmt(f)
+        writeln!(f, "</g>")
+    }
+
+    /// Get text for a data point
+    fn point_text(&self, pt: Point) -> String {
+        format!(
+            "({} {})",
+            self.domain.x_text(pt.x),
+            self.domain.y_text(pt.y)
+        )
     }
 
     /// Format plot labels
     fn labels_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(label) = &self.label {
-            let text = Text::new(Edge::Top).class_name("plot-label");
+            let text = Text::new(Edge::Top)
+                .anchor(label.anchor())
+                .class_name("plot-label")
+                .inline(self.inline);
             text.display(f)?;
-            for pt in self.data.iter() {
-                let pt = (*pt).into();
+            if let Some(labels) = &self.point_labels {
+                // not downsampled, to keep labels matched with points
+                let points: Vec<_> = self.data.iter().collect();
+                let selected = self.label_points.select(&points);
+                for ((pt, lbl), sel) in points.iter().zip(labels).zip(selected)
+                {
+                    if let (true, Some(lbl)) = (sel, lbl) {
+                        let x = self.domain.x_map(pt.x);
+                        let y = self.domain.y_map(pt.y);
+                        label.display(f, x, y, &escape(lbl))?;
+                    }
+                }
+                return text.display_done(f);
+            }
+            let points: Vec<_> = self.points().collect();
+            let selected = self.label_points.select(&points);
+            for (pt, sel) in points.into_iter().zip(selected) {
+                if !sel {
+                    continue;
+                }
                 let x = self.domain.x_map(pt.x);
                 let y = self.domain.y_map(pt.y);
-                label.display(f, x, y, pt)?;
+                let text = if let Some(label_text) = &self.label_text {
+                    escape(&label_text(pt)).into_owned()
+                } else if label.has_precision() {
+                    format!("({} {})", label.rounded(pt.x), label.rounded(pt.y))
+                } else {
+                    self.point_text(pt)
+                };
+                label.display(f, x, y, &text)?;
             }
             text.display_done(f)?;
         }
@@ -130,59 +1014,330 @@ where
     }
 }
 
-impl<'a, P> fmt::Display for Plot<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> fmt::Display for Plot<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let settings = self.settings();
+        if settings.interactive {
+            write!(f, "<g id='{}'", settings.element_id())?;
+            writeln!(f, " class='plot-{} series-hover'>", settings.num)?;
+        }
         match self {
-            Plot::Area(p) => p.area_fmt(f),
-            Plot::Line(p) => p.line_fmt(f),
-            Plot::Scatter(p) => p.scatter_fmt(f),
+            Plot::Area(p) => p.area_fmt(f)?,
+            Plot::Bar(p) => p.bar_fmt(f)?,
+            Plot::Line(p) => p.line_fmt(f)?,
+            Plot::Range(p) => p.range_fmt(f)?,
+            Plot::Scatter(p) => p.scatter_fmt(f)?,
+        }
+        if settings.trend {
+            settings.trend_fmt(f)?;
+        }
+        if settings.interactive {
+            writeln!(f, "</g>")?;
         }
+        Ok(())
     }
 }
 
-impl<'a, P> Plot<'a, P>
-where
-    P: IntoPoint,
-{
+impl<'a> Plot<'a> {
     /// Create a new area plot
-    pub fn area(name: &'a str, data: &'a [P]) -> Self {
-        Plot::Area(PlotSettings::new(name, data))
+    ///
+    /// The area is filled down to zero, and the chart domain is extended to
+    /// include zero unless `without_zero` is used.
+    pub fn area<N, P>(name: N, data: &'a [P]) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        P: IntoPoint,
+    {
+        let data = Data::Borrowed(Box::new(data));
+        Plot::Area(PlotSettings::new(name.into(), data))
+    }
+
+    /// Create a new area plot from owned data
+    pub fn area_owned<N, I>(name: N, data: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator,
+        I::Item: IntoPoint,
+    {
+        let data = data.into_iter().map(|pt| pt.into()).collect();
+        Plot::Area(PlotSettings::new(name.into(), Data::Owned(data)))
+    }
+
+    /// Create a new area plot from records, with a point accessor
+    pub fn area_with<N, T, F, Q>(name: N, records: &'a [T], map: F) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        F: Fn(&T) -> Q + 'a,
+        Q: IntoPoint,
+    {
+        let data = Data::Borrowed(Box::new(Mapping { records, map }));
+        Plot::Area(PlotSettings::new(name.into(), data))
+    }
+
+    /// Create a new bar plot
+    ///
+    /// Bars are centered on `X` values, extending from zero to `Y` values.
+    /// The chart domain is extended to include zero unless `without_zero` is
+    /// used.  Points with `NaN` values are skipped.
+    pub fn bar<N, P>(name: N, data: &'a [P]) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        P: IntoPoint,
+    {
+        let data = Data::Borrowed(Box::new(data));
+        Plot::Bar(PlotSettings::new(name.into(), data))
+    }
+
+    /// Create a new bar plot from owned data
+    pub fn bar_owned<N, I>(name: N, data: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator,
+        I::Item: IntoPoint,
+    {
+        let data = data.into_iter().map(|pt| pt.into()).collect();
+        Plot::Bar(PlotSettings::new(name.into(), Data::Owned(data)))
     }
 
     /// Create a new line plot
-    pub fn line(name: &'a str, data: &'a [P]) -> Self {
-        Plot::Line(PlotSettings::new(name, data))
+    ///
+    /// Points with `NaN` values are treated as gaps in the line.
+    pub fn line<N, P>(name: N, data: &'a [P]) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        P: IntoPoint,
+    {
+        let data = Data::Borrowed(Box::new(data));
+        Plot::Line(PlotSettings::new(name.into(), data))
+    }
+
+    /// Create a new line plot from owned data
+    pub fn line_owned<N, I>(name: N, data: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator,
+        I::Item: IntoPoint,
+    {
+        let data = data.into_iter().map(|pt| pt.into()).collect();
+        Plot::Line(PlotSettings::new(name.into(), Data::Owned(data)))
+    }
+
+    /// Create a new line plot from records, with a point accessor
+    ///
+    /// Points are mapped while rendering, without an intermediate `Vec`.
+    pub fn line_with<N, T, F, Q>(name: N, records: &'a [T], map: F) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        F: Fn(&T) -> Q + 'a,
+        Q: IntoPoint,
+    {
+        let data = Data::Borrowed(Box::new(Mapping { records, map }));
+        Plot::Line(PlotSettings::new(name.into(), data))
+    }
+
+    /// Create a line plot of a centered moving average
+    ///
+    /// Each `Y` value is the mean of `window` points centered on it, using
+    /// fewer points near the edges.  `X` values are unchanged, so the plot
+    /// shares the domain of the raw data.
+    pub fn moving_average<N, P>(name: N, data: &[P], window: usize) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        P: IntoPoint,
+    {
+        let data: Vec<Point> = data.iter().map(|pt| (*pt).into()).collect();
+        Plot::line_owned(name, moving_average(&data, window, false))
+    }
+
+    /// Create a line plot of a trailing moving average
+    ///
+    /// Each `Y` value is the mean of up to `window` points ending with it.
+    pub fn trailing_average<N, P>(name: N, data: &[P], window: usize) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        P: IntoPoint,
+    {
+        let data: Vec<Point> = data.iter().map(|pt| (*pt).into()).collect();
+        Plot::line_owned(name, moving_average(&data, window, true))
     }
 
     /// Create a new scatter plot
-    pub fn scatter(name: &'a str, data: &'a [P]) -> Self {
-        Plot::Scatter(PlotSettings::new(name, data))
+    ///
+    /// Points with `NaN` values are skipped.
+    pub fn scatter<N, P>(name: N, data: &'a [P]) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        P: IntoPoint,
+    {
+        let data = Data::Borrowed(Box::new(data));
+        Plot::Scatter(PlotSettings::new(name.into(), data))
+    }
+
+    /// Create a new scatter plot from owned data
+    pub fn scatter_owned<N, I>(name: N, data: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator,
+        I::Item: IntoPoint,
+    {
+        let data = data.into_iter().map(|pt| pt.into()).collect();
+        Plot::Scatter(PlotSettings::new(name.into(), Data::Owned(data)))
+    }
+
+    /// Create a new scatter plot from records, with a point accessor
+    pub fn scatter_with<N, T, F, Q>(name: N, records: &'a [T], map: F) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        F: Fn(&T) -> Q + 'a,
+        Q: IntoPoint,
+    {
+        let data = Data::Borrowed(Box::new(Mapping { records, map }));
+        Plot::Scatter(PlotSettings::new(name.into(), data))
+    }
+
+    /// Create a new scatter plot with a custom label for each point
+    ///
+    /// The labels are displayed when `label` is enabled.  Points with no
+    /// label get no text.
+    pub fn scatter_labeled<N, I, L>(name: N, data: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator<Item = L>,
+        L: Into<LabeledPoint>,
+    {
+        let (data, labels): (Vec<_>, Vec<_>) = data
+            .into_iter()
+            .map(|pt| {
+                let pt = pt.into();
+                (Point { x: pt.x, y: pt.y }, pt.label)
+            })
+            .unzip();
+        let mut settings = PlotSettings::new(name.into(), Data::Owned(data));
+        settings.point_labels = Some(labels);
+        Plot::Scatter(settings)
+    }
+
+    /// Create a new line plot with a band, such as a confidence interval
+    ///
+    /// The band is filled from `lo` to `hi`, below the line.  Use
+    /// `Domain::from_band_data` for a domain which includes the band.
+    ///
+    /// ```rust
+    /// use splot::{Chart, Domain, Plot};
+    ///
+    /// let data = [(0.0, 5.0, 4.0, 6.5), (1.0, 6.0, 4.5, 7.0)];
+    /// let chart = Chart::new()
+    ///     .domain(Domain::from_band_data(&data))
+    ///     .plot(Plot::line_with_band("Model", &data));
+    /// ```
+    pub fn line_with_band<N, I, B>(name: N, data: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator<Item = B>,
+        B: Into<BandPoint>,
+    {
+        let (data, band): (Vec<_>, Vec<_>) = data
+            .into_iter()
+            .map(|pt| {
+                let pt = pt.into();
+                (Point { x: pt.x, y: pt.y }, (pt.x, pt.lo, pt.hi))
+            })
+            .unzip();
+        let mut settings = PlotSettings::new(name.into(), Data::Owned(data));
+        settings.band = Some(band);
+        Plot::Line(settings)
+    }
+
+    /// Create a new range plot, with horizontal bars such as a Gantt chart
+    ///
+    /// Each range is drawn from `start` to `end` at its `row` `Y` value.
+    /// Ranges with `end` before `start` are swapped.  Use
+    /// `Domain::from_range_data` for a domain which includes all ranges.
+    ///
+    /// ```rust
+    /// use splot::{Chart, Domain, Plot};
+    ///
+    /// let shifts = [(1.0, 0.0, 8.0), (2.0, 8.0, 16.0), (1.0, 16.0, 24.0)];
+    /// let chart = Chart::new()
+    ///     .domain(Domain::from_range_data(&shifts))
+    ///     .plot(Plot::ranges("On call", &shifts));
+    /// ```
+    pub fn ranges<N, I, R>(name: N, data: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator<Item = R>,
+        R: Into<RangePoint>,
+    {
+        let data = data
+            .into_iter()
+            .flat_map(|pt| {
+                let pt = pt.into();
+                let (start, end) = if pt.end < pt.start {
+                    (pt.end, pt.start)
+                } else {
+                    (pt.start, pt.end)
+                };
+                [
+                    Point {
+                        x: start,
+                        y: pt.row,
+                    },
+                    Point { x: end, y: pt.row },
+                ]
+            })
+            .collect();
+        Plot::Range(PlotSettings::new(name.into(), Data::Owned(data)))
     }
 
     /// Get plot settings
-    fn settings(&self) -> &PlotSettings<'a, P> {
+    fn settings(&self) -> &PlotSettings<'a> {
         match self {
             Plot::Area(p) => p,
+            Plot::Bar(p) => p,
             Plot::Line(p) => p,
+            Plot::Range(p) => p,
             Plot::Scatter(p) => p,
         }
     }
 
     /// Get plot settings mutably
-    fn settings_mut(&mut self) -> &mut PlotSettings<'a, P> {
+    fn settings_mut(&mut self) -> &mut PlotSettings<'a> {
         match self {
             Plot::Area(p) => p,
+            Plot::Bar(p) => p,
             Plot::Line(p) => p,
+            Plot::Range(p) => p,
             Plot::Scatter(p) => p,
         }
     }
 
     /// Get plot name
     pub(crate) fn name(&self) -> &str {
-        self.settings().name
+        &self.settings().name
+    }
+
+    /// Check if the plot is listed in the legend
+    ///
+    /// Plots with an empty name are not listed.
+    pub(crate) fn in_legend(&self) -> bool {
+        let settings = self.settings();
+        !settings.hide_from_legend && !settings.name.is_empty()
+    }
+
+    /// Get plot type
+    pub(crate) fn plot_type(&self) -> PlotType {
+        match self {
+            Plot::Area(_) => PlotType::Area,
+            Plot::Bar(_) => PlotType::Bar,
+            Plot::Line(_) => PlotType::Line,
+            Plot::Range(_) => PlotType::Range,
+            Plot::Scatter(_) => PlotType::Scatter,
+        }
+    }
+
+    /// Get color from palette
+    pub(crate) fn color(&self) -> Option<&str> {
+        self.settings().color.as_deref()
     }
 
     /// Set plot number
@@ -190,14 +1345,759 @@ where
         self.settings_mut().num = num;
     }
 
+    /// Get `X` positions of points, with their text
+    pub(crate) fn x_positions(&self) -> Vec<(f64, String)> {
+        let settings = self.settings();
+        settings
+            .points()
+            .filter(|pt| !pt.is_missing())
+            .map(|pt| (settings.domain.x_pos(pt.x), settings.point_text(pt)))
+            .collect()
+    }
+
+    /// Get distinct `X` (or `Y`) values of points, in ascending order
+    pub(crate) fn distinct_values(&self, x: bool) -> Vec<f64> {
+        let mut values: Vec<f64> = self
+            .settings()
+            .points()
+            .filter(|pt| !pt.is_missing())
+            .map(|pt| if x { pt.x } else { pt.y })
+            .collect();
+        values.sort_by(f64::total_cmp);
+        values.dedup();
+        values
+    }
+
+    /// Check if the plot is animated
+    pub(crate) fn is_animated(&self) -> bool {
+        matches!(self, Plot::Line(_)) && self.settings().animate.is_some()
+    }
+
+    /// Set animation start delay, in seconds
+    pub(crate) fn set_animate_delay(&mut self, delay: f32) {
+        if let Some((_, d)) = &mut self.settings_mut().animate {
+            *d = delay;
+        }
+    }
+
+    /// Set element ID prefix
+    pub(crate) fn set_element_id(&mut self, id: String) {
+        self.settings_mut().element_id = Some(id);
+    }
+
+    /// Get plot-specific domain
+    pub(crate) fn own_domain(&self) -> Option<&Domain> {
+        self.settings().own_domain.as_ref()
+    }
+
     /// Bind with domain
     pub(crate) fn bind_domain(&mut self, domain: BoundDomain) {
         self.settings_mut().domain = domain;
     }
 
+    /// Format class and style attributes
+    pub(crate) fn class_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        kind: &str,
+    ) -> fmt::Result {
+        self.settings().class_fmt(f, kind)
+    }
+
+    /// Format color attribute
+    pub(crate) fn color_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        attr: &str,
+    ) -> fmt::Result {
+        self.settings().color_fmt(f, attr)
+    }
+
+    /// Set color from palette
+    pub(crate) fn set_color(&mut self, color: &str) {
+        self.settings_mut().color = Some(color.to_string());
+    }
+
+    /// Set marker ID
+    pub(crate) fn set_marker(&mut self, marker: String) {
+        self.settings_mut().marker = Some(marker);
+    }
+
+    /// Use inline presentational attributes
+    pub(crate) fn set_inline(&mut self) {
+        self.settings_mut().inline = true;
+    }
+
+    /// Set decimal places of path coordinates
+    pub(crate) fn set_precision(&mut self, decimals: u8) {
+        self.settings_mut().precision = decimals;
+    }
+
+    /// Format stroke attributes
+    pub(crate) fn stroke_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.settings().stroke_fmt(f)
+    }
+
+    /// Draw plot with a dashed line
+    pub fn dashed(mut self) -> Self {
+        self.settings_mut().dash = Dash::Dashed;
+        self
+    }
+
+    /// Draw plot with a dotted line
+    pub fn dotted(mut self) -> Self {
+        self.settings_mut().dash = Dash::Dotted;
+        self
+    }
+
+    /// Set the line stroke width
+    pub fn stroke_width(mut self, width: f32) -> Self {
+        self.settings_mut().stroke_width = Some(width);
+        self
+    }
+
+    /// Check if plot has markers at data points
+    pub(crate) fn has_markers(&self) -> bool {
+        self.settings().markers
+    }
+
+    /// Get the largest `X` value
+    pub(crate) fn x_max(&self) -> Option<f64> {
+        self.settings()
+            .data
+            .iter()
+            .filter(|pt| !pt.is_missing())
+            .map(|pt| pt.x)
+            .reduce(f64::max)
+    }
+
+    /// Get the `X` range which must be in the chart domain
+    ///
+    /// For bar plots, this covers half a bar width past the first and last
+    /// values, so they are not clipped.
+    pub(crate) fn x_range(&self) -> Option<(f64, f64)> {
+        match self {
+            Plot::Bar(settings) => settings.bar_x_range(),
+            _ => None,
+        }
+    }
+
+    /// Check if the chart domain must include zero for the plot
+    pub(crate) fn needs_zero(&self) -> bool {
+        matches!(self, Plot::Area(_) | Plot::Bar(_))
+            && self.settings().include_zero
+    }
+
+    /// Check if the plot is stacked
+    pub(crate) fn is_stacked(&self) -> bool {
+        matches!(self, Plot::Area(_) | Plot::Bar(_)) && self.settings().stacked
+    }
+
+    /// Add to totals, if the plot is stacked and normalized
+    pub(crate) fn total(&self, stacks: &mut Stacks) {
+        if self.is_stacked() {
+            self.settings().total(stacks);
+        }
+    }
+
+    /// Stack onto running totals, if the plot is stacked
+    pub(crate) fn stack(&mut self, stacks: &mut Stacks) {
+        if self.is_stacked() {
+            self.settings_mut().stack(stacks);
+        }
+    }
+
+    /// Keep the chart domain from being extended to include zero
+    ///
+    /// Area and bar plots include zero by default, since they are misleading
+    /// when the axis starts at the data minimum.  Without it, they are filled
+    /// down to the edge of the domain.
+    pub fn without_zero(mut self) -> Self {
+        self.settings_mut().include_zero = false;
+        self
+    }
+
+    /// Stack on top of previous stacked plots in a `Chart`
+    ///
+    /// Each value is drawn from the running total at its `X` value, with
+    /// positive and negative values stacked separately.  This applies to
+    /// area and bar plots.
+    pub fn stacked(mut self) -> Self {
+        self.settings_mut().stacked = true;
+        self
+    }
+
+    /// Stack as fractions of the total at each `X` value
+    ///
+    /// Each value is divided by the sum of magnitudes of all normalized
+    /// plots at its `X` value, so the stack adds up to 1.  The `Y` domain
+    /// is fixed to 0..1; use [Domain::percent_y] to label it `0%` to `100%`.
+    /// Where the total is zero, the column is empty.
+    ///
+    /// [Domain::percent_y]: crate::Domain::percent_y
+    pub fn normalized(mut self) -> Self {
+        let settings = self.settings_mut();
+        settings.stacked = true;
+        settings.normalized = true;
+        self
+    }
+
+    /// Check if a line plot has a band
+    pub(crate) fn has_band(&self) -> bool {
+        self.settings().band.is_some()
+    }
+
+    /// Draw markers at data points of a line plot
+    pub fn markers(mut self) -> Self {
+        self.settings_mut().markers = true;
+        self
+    }
+
+    /// Use a plot-specific domain instead of the chart domain
+    ///
+    /// This allows a secondary scale, such as a right-hand `Y` axis
+    /// added with `Chart::axis_for`.
+    pub fn domain<D>(mut self, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
+        self.settings_mut().own_domain = Some(domain.into());
+        self
+    }
+
+    /// Add a custom class name
+    pub fn class(mut self, class_name: &'a str) -> Self {
+        self.settings_mut().class_name = Some(class_name);
+        self
+    }
+
+    /// Add an inline style
+    pub fn style(mut self, style: &'a str) -> Self {
+        self.settings_mut().style = Some(style);
+        self
+    }
+
+    /// Suppress the automatic `plot-{num}` class
+    pub fn no_num_class(mut self) -> Self {
+        self.settings_mut().num_class = false;
+        self
+    }
+
+    /// Downsample line or scatter data to a maximum number of points
+    ///
+    /// Largest-Triangle-Three-Buckets reduction is used, which preserves
+    /// the first and last points along with visual extremes.
+    pub fn downsample(mut self, max_points: usize) -> Self {
+        self.settings_mut().max_points = Some(max_points);
+        self
+    }
+
     /// Add labels to plot
-    pub fn label(mut self) -> Self {
-        self.settings_mut().label = Some(Label::new());
+    pub fn label(self) -> Self {
+        self.update_label(|label| label)
+    }
+
+    /// Update label settings, adding labels if needed
+    fn update_label<F>(mut self, update: F) -> Self
+    where
+        F: FnOnce(Label) -> Label,
+    {
+        let label = self.settings_mut().label.take().unwrap_or_default();
+        self.settings_mut().label = Some(update(label));
+        self
+    }
+
+    /// Add labels above points (the default)
+    pub fn label_above(self) -> Self {
+        self.update_label(Label::above)
+    }
+
+    /// Add labels centered on points
+    pub fn label_at(self) -> Self {
+        self.update_label(Label::at)
+    }
+
+    /// Add labels below points
+    pub fn label_below(self) -> Self {
+        self.update_label(Label::below)
+    }
+
+    /// Add labels starting at points
+    pub fn label_start(self) -> Self {
+        self.update_label(Label::start)
+    }
+
+    /// Add labels ending at points
+    pub fn label_end(self) -> Self {
+        self.update_label(Label::end)
+    }
+
+    /// Add labels, with values rounded to a number of decimal places
+    pub fn label_precision(self, digits: usize) -> Self {
+        self.update_label(|label| label.precision(digits))
+    }
+
+    /// Set which points get labels, adding labels if needed
+    fn label_points(mut self, points: LabelPoints<'a>) -> Self {
+        self.settings_mut().label_points = points;
+        self.label()
+    }
+
+    /// Label only the first point
+    pub fn label_first(self) -> Self {
+        self.label_points(LabelPoints::First)
+    }
+
+    /// Label only the last point, such as a "current value"
+    pub fn label_last(self) -> Self {
+        self.label_points(LabelPoints::Last)
+    }
+
+    /// Label only the points with minimum and maximum `Y` values
+    pub fn label_extremes(self) -> Self {
+        self.label_points(LabelPoints::Extremes)
+    }
+
+    /// Label every Nth point, starting with the first
+    pub fn label_every(self, n: usize) -> Self {
+        self.label_points(LabelPoints::Every(n))
+    }
+
+    /// Label only points matching a predicate
+    ///
+    /// The predicate is called with the index and value (in data units) of
+    /// each rendered point.  If the plot is downsampled, indices are within
+    /// the downsampled points.
+    pub fn label_filter<F>(self, filter: F) -> Self
+    where
+        F: Fn(usize, Point) -> bool + 'a,
+    {
+        self.label_points(LabelPoints::Filter(Box::new(filter)))
+    }
+
+    /// Add labels, with text from a formatter
+    ///
+    /// The formatter is called with each point in data units.
+    pub fn label_with<F>(mut self, label_text: F) -> Self
+    where
+        F: Fn(Point) -> String + 'a,
+    {
+        self.settings_mut().label_text = Some(Box::new(label_text));
+        self.label()
+    }
+
+    /// Draw a least-squares trend line through the data
+    ///
+    /// The line spans the `X` domain, with a `plot-trend` class.  It is
+    /// dashed, unless the plot is `dotted`.  Points with `NaN` values are
+    /// ignored, and no line is drawn if all `X` values are equal.
+    pub fn trend_line(mut self) -> Self {
+        self.settings_mut().trend = true;
         self
     }
+
+    /// Get the slope and intercept of a least-squares fit of the data
+    ///
+    /// Returns `None` with fewer than two points or if all `X` values are
+    /// equal.
+    pub fn trend_coefficients(&self) -> Option<(f64, f64)> {
+        self.settings().trend_coefficients()
+    }
+
+    /// Add hover tooltips
+    ///
+    /// Line and area paths get a `<title>` with the plot name.  Scatter
+    /// plots (and line plots with markers) also get a transparent circle
+    /// for each point, titled with its coordinates.  Output grows with the
+    /// number of points.
+    pub fn tooltips(mut self) -> Self {
+        self.settings_mut().tooltips = true;
+        self
+    }
+
+    /// Embed original data values as `data-*` attributes
+    ///
+    /// Line and scatter plots get `data-name` on the series, and `data-x` /
+    /// `data-y` on an element for each point.  Scatter points are rendered
+    /// as separate paths.
+    pub fn embed_data(mut self) -> Self {
+        self.settings_mut().embed_data = true;
+        self
+    }
+
+    /// Leave the plot out of the legend
+    ///
+    /// Plots with an empty name are always left out.  Other plots keep
+    /// their colors and markers.
+    pub fn hide_from_legend(mut self) -> Self {
+        self.settings_mut().hide_from_legend = true;
+        self
+    }
+
+    /// Animate a line plot, drawing itself over a duration in seconds
+    ///
+    /// An SVG `<animate>` element moves the stroke dash offset, replacing
+    /// any dash pattern.  On a `Chart`, each animated plot starts a little
+    /// after the previous one.  Other plot kinds are not animated.
+    pub fn animate(mut self, duration_secs: f32) -> Self {
+        self.settings_mut().animate = Some((duration_secs, 0.0));
+        self
+    }
+
+    /// Add element IDs and a hover structure, for CSS-only interactivity
+    ///
+    /// The plot is wrapped in a `series-hover` group with ID `plot-N`,
+    /// and its path has ID `plot-N-path`.  Each point of a line or scatter
+    /// plot gets an element with ID `plot-N-pt-I`, where `I` is the index
+    /// of the point.  On a `Chart`, IDs include its ID prefix.  The default
+    /// stylesheet has `:hover` rules to brighten the series and enlarge the
+    /// point under the pointer.
+    pub fn interactive(mut self) -> Self {
+        self.settings_mut().interactive = true;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Domain;
+    use crate::rect::Rect;
+
+    /// Render a plot bound to a 100 x 100 rectangle
+    fn render(mut plot: Plot, domain: &Domain) -> String {
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 100, 100)));
+        plot.to_string()
+    }
+
+    #[test]
+    fn dash() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let domain = Domain::from(&data[..]);
+        let plot = Plot::line("L", &data[..]).dashed().stroke_width(3.0);
+        let svg = render(plot, &domain);
+        assert!(svg.contains(" stroke-dasharray='20 12' stroke-width='3' d="));
+        let svg = render(Plot::line("L", &data[..]).dotted(), &domain);
+        assert!(svg.contains(" stroke-dasharray='4 8' d="));
+        let svg = render(Plot::line("L", &data[..]), &domain);
+        assert!(!svg.contains("stroke-"));
+    }
+
+    #[test]
+    fn markers() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let domain = Domain::from(&data[..]);
+        let svg = render(Plot::line("L", &data[..]).markers(), &domain);
+        assert!(svg.contains(" marker-start='url(#marker-0)'"));
+        assert!(svg.contains(" marker-mid='url(#marker-0)'"));
+        assert!(svg.contains(" marker-end='url(#marker-0)'"));
+        let svg = render(Plot::line("L", &data[..]), &domain);
+        assert!(!svg.contains("marker-"));
+    }
+
+    #[test]
+    fn owned() {
+        let data = (0..3).map(|i| (i as f32, (i * 2) as f32));
+        let domain = Domain::from_data(data.clone());
+        let svg = render(Plot::line_owned("Owned", data), &domain);
+        assert!(svg.contains(" d='M0 100 50 50 100 0'"));
+    }
+
+    #[test]
+    fn downsample() {
+        let data: Vec<(f32, f32)> = (0..1_000_000)
+            .map(|i| (i as f32, (i as f32 / 1000.0).sin()))
+            .collect();
+        let domain = Domain::from(&data[..]);
+        let mut plot = Plot::line("", &data).downsample(1000);
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 2000, 1500)));
+        let svg = plot.to_string();
+        let d = svg.split("d='").nth(1).unwrap().split('\'').next().unwrap();
+        let coords = d.split_whitespace().count() / 2;
+        assert!(coords <= 1000);
+    }
+
+    #[test]
+    fn area_missing() {
+        let data = [
+            (0.0, None),
+            (1.0, Some(2.0)),
+            (2.0, Some(4.0)),
+            (3.0, None),
+            (4.0, Some(3.0)),
+            (5.0, None),
+        ];
+        let domain = Domain::from(&data[..]).set_x(&[0.0, 5.0]);
+        let mut plot = Plot::area("", &data[..]);
+        plot.set_precision(1);
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 500, 400)));
+        let svg = plot.to_string();
+        let d = svg.split("d='").nth(1).unwrap().split('\'').next().unwrap();
+        assert!(!d.contains("NaN"));
+        assert!(d.starts_with("M100.0 400.0 100.0 "));
+        assert_eq!(d.matches('M').count(), 2);
+        assert!(d.contains(" 200.0 400.0M400.0 400.0 400.0 "));
+        assert!(d.ends_with(" 400.0 400.0"));
+    }
+
+    #[test]
+    fn downsample_gap() {
+        let data: Vec<(f32, Option<f32>)> = (0..1000)
+            .map(|i| (i as f32, (i != 500).then_some((i % 7) as f32)))
+            .collect();
+        let domain = Domain::from(&data[..]);
+        let mut plot = Plot::line("", &data).downsample(100);
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 1000, 1000)));
+        let svg = plot.to_string();
+        let d = svg.split("d='").nth(1).unwrap().split('\'').next().unwrap();
+        assert_eq!(d.matches('M').count(), 2);
+        assert!(!d.contains("NaN"));
+    }
+
+    #[test]
+    fn labels() {
+        let data = [(0.0, 1.0), (1.0, 2.5)];
+        let domain = Domain::from(&data[..]);
+        let rect = Rect::new(0, 0, 1000, 1000);
+        let render = |plot: Plot| {
+            let mut plot = plot;
+            plot.bind_domain(domain.bind(rect));
+            plot.to_string()
+        };
+        let svg = render(Plot::scatter("S", &data[..]).label());
+        assert!(svg.contains("text-anchor='middle'>"));
+        assert!(svg.contains("dy='-0.67em'>(0 1)</tspan>"));
+        let svg = render(Plot::scatter("S", &data[..]).label_below().label());
+        assert!(svg.contains("dy='1.33em'>(0 1)</tspan>"));
+        let svg = render(Plot::scatter("S", &data[..]).label_at().label_end());
+        assert!(svg.contains("text-anchor='end'>"));
+        assert!(svg.contains("dy='0.33em'>(0 1)</tspan>"));
+        let svg = render(Plot::scatter("S", &data[..]).label_precision(2));
+        assert!(svg.contains(">(1.00 2.50)</tspan>"));
+        let unit = "ms";
+        let svg = render(
+            Plot::line("L", &data[..])
+                .label_with(|pt| format!("{} <{unit}>", pt.y))
+                .label_below(),
+        );
+        assert!(svg.contains("dy='1.33em'>1 &lt;ms&gt;</tspan>"));
+        assert!(svg.contains(">2.5 &lt;ms&gt;</tspan>"));
+    }
+
+    #[test]
+    fn label_points() {
+        let data = [(0.0, 3.0), (1.0, 1.0), (2.0, f64::NAN), (3.0, 5.0)];
+        let domain = Domain::from(&data[..]);
+        let rect = Rect::new(0, 0, 1000, 1000);
+        let labels = |plot: Plot| {
+            let mut plot = plot.label_with(|pt| pt.x.to_string());
+            plot.bind_domain(domain.bind(rect));
+            let svg = plot.to_string();
+            svg.split("em'>")
+                .skip(1)
+                .map(|s| s[..s.find('<').unwrap()].to_string())
+                .collect::<Vec<_>>()
+        };
+        let line = || Plot::line("L", &data[..]);
+        assert_eq!(labels(line()), ["0", "1", "3"]);
+        assert_eq!(labels(line().label_first()), ["0"]);
+        assert_eq!(labels(line().label_last()), ["3"]);
+        assert_eq!(labels(line().label_extremes()), ["1", "3"]);
+        assert_eq!(labels(line().label_every(2)), ["0", "3"]);
+        let filter = line().label_filter(|i, pt| i > 0 && pt.y < 2.0);
+        assert_eq!(labels(filter), ["1"]);
+    }
+
+    #[test]
+    fn area_baseline() {
+        let rect = Rect::new(0, 100, 1000, 500);
+        let area = |data: &[(f64, f64)]| {
+            let domain = Domain::from(data);
+            let mut plot = Plot::area("A", data);
+            plot.bind_domain(domain.bind(rect));
+            plot.to_string()
+        };
+        let svg = area(&[(0.0, 40.0), (1.0, 80.0)]);
+        assert!(svg.contains("d='M0 600 0 600 1000 100 1000 600'"));
+        let svg = area(&[(0.0, -40.0), (1.0, -80.0)]);
+        assert!(svg.contains("d='M0 100 0 100 1000 600 1000 100'"));
+    }
+
+    #[test]
+    fn style() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let domain = Domain::from(&data[..]);
+        let mut plot = Plot::line("L", &data[..]).style("font: 'A&B'");
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 100, 100)));
+        let svg = plot.to_string();
+        assert!(svg.contains(" style='font: &apos;A&amp;B&apos;'"));
+        let mut plot = Plot::line("L", &data[..]).class("a' onload='b");
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 100, 100)));
+        let svg = plot.to_string();
+        assert!(
+            svg.contains(" class='plot-0 plot-line a&apos; onload=&apos;b'")
+        );
+    }
+
+    #[test]
+    fn tooltips() {
+        let data = [(0.0, 1.0), (1.0, 2.0), (2.0, 4.0)];
+        let domain = Domain::from(&data[..]);
+        let rect = Rect::new(0, 0, 1000, 1000);
+        let mut plot = Plot::scatter("S", &data[..]).tooltips();
+        plot.bind_domain(domain.bind(rect));
+        let svg = plot.to_string();
+        assert_eq!(svg.matches("<circle class='plot-point'").count(), 3);
+        assert!(svg.contains("<title>(1 2)</title>"));
+        let mut plot = Plot::line("L", &data[..]).tooltips();
+        plot.bind_domain(domain.bind(rect));
+        let svg = plot.to_string();
+        assert!(svg.contains("'><title>L</title></path>"));
+        assert!(!svg.contains("<circle"));
+        let mut plot = Plot::line("L", &data[..]);
+        plot.bind_domain(domain.bind(rect));
+        assert!(!plot.to_string().contains("<title>"));
+        let mut plot = Plot::area("A & <B>", &data[..]).tooltips();
+        plot.bind_domain(domain.bind(rect));
+        assert!(plot
+            .to_string()
+            .contains("<title>A &amp; &lt;B&gt;</title>"));
+        let mut plot = Plot::scatter("S", &data[..]).tooltips();
+        plot.set_precision(1);
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 999, 999)));
+        assert!(plot.to_string().contains(" cx='499.5' cy="));
+    }
+
+    #[test]
+    fn embed_data() {
+        let data = [(0.5, 1.0), (1.0, 2.25), (f32::NAN, 1.0)];
+        let domain = Domain::from(&data[..2]);
+        let rect = Rect::new(0, 0, 1000, 1000);
+        let mut plot = Plot::scatter("S'", &data[..]).embed_data();
+        plot.bind_domain(domain.bind(rect));
+        let svg = plot.to_string();
+        assert!(svg.contains("<g data-name='S&apos;'>"));
+        assert_eq!(svg.matches("<path class='plot-0 plot-scatter'").count(), 2);
+        assert!(svg.contains(" data-x='1' data-y='2.25'"));
+        let mut plot = Plot::line("L", &data[..]).embed_data();
+        plot.bind_domain(domain.bind(rect));
+        let svg = plot.to_string();
+        assert!(svg.contains(" data-name='L' d='"));
+        assert_eq!(svg.matches("<circle class='plot-point' data-x").count(), 2);
+        assert!(svg.contains(" data-x='0.5' data-y='1'"));
+    }
+
+    #[test]
+    fn scatter_labeled() {
+        let data = vec![
+            LabeledPoint::new((1.0, 2.0), "DE"),
+            LabeledPoint::unlabeled((2.0, 3.0)),
+            ((3.0, 1.0), "FR").into(),
+        ];
+        let domain = Domain::from(&[(1.0, 1.0), (3.0, 3.0)][..]);
+        let mut plot = Plot::scatter_labeled("S", data).label();
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 1000, 1000)));
+        let svg = plot.to_string();
+        assert!(svg.contains(">DE</tspan>"));
+        assert!(svg.contains(">FR</tspan>"));
+        assert_eq!(svg.matches("<tspan").count(), 2);
+    }
+
+    #[test]
+    fn line_with() {
+        struct Sample {
+            ts: i64,
+            cpu: f32,
+        }
+        let records =
+            [Sample { ts: 10, cpu: 0.5 }, Sample { ts: 20, cpu: 0.75 }];
+        let map = |r: &Sample| (r.ts as f32, r.cpu);
+        let domain = Domain::from_data_with(&records, map);
+        let rect = Rect::new(0, 0, 1000, 1000);
+        let mut a = Plot::line_with("cpu", &records, map);
+        a.bind_domain(domain.bind(rect));
+        let data: Vec<_> = records.iter().map(map).collect();
+        let mut b = Plot::line("cpu", &data);
+        b.bind_domain(domain.bind(rect));
+        assert_eq!(a.to_string(), b.to_string());
+    }
+
+    #[test]
+    fn trend_line() {
+        let data = [(0.0, 1.0), (1.0, f64::NAN), (2.0, 5.0), (4.0, 9.0)];
+        let mut plot = Plot::scatter("S", &data[..]).trend_line();
+        assert_eq!(plot.trend_coefficients(), Some((2.0, 1.0)));
+        let domain = Domain::from_ranges(0..10, 0..20);
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 1000, 1000)));
+        let svg = plot.to_string();
+        assert!(svg.contains(
+            "<path class='plot-0 plot-trend' stroke-dasharray='20 12' \
+            d='M0 950 1000 -50'/>"
+        ));
+        let vertical = [(3.0, 1.0), (3.0, 5.0), (3.0, 9.0)];
+        let plot = Plot::scatter("V", &vertical[..]).trend_line();
+        assert_eq!(plot.trend_coefficients(), None);
+        assert!(!plot.to_string().contains("plot-trend"));
+        let tenths = [(0.1, 1.0), (0.1, 2.0), (0.1, 4.0)];
+        assert_eq!(Plot::scatter("T", &tenths[..]).trend_coefficients(), None);
+    }
+
+    #[test]
+    fn line_with_band() {
+        let data = [(0.0, 5.0, 4.0, 6.0), (1.0, 6.0, 5.0, 8.0)];
+        let domain = Domain::from_band_data(&data[..]);
+        let mut plot = Plot::line_with_band("M", &data[..]);
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 100, 100)));
+        let svg = plot.to_string();
+        let band = svg.find("<path class='plot-0 plot-band' d='").unwrap();
+        assert!(band < svg.find("<path class='plot-0 plot-line'").unwrap());
+        assert!(svg.contains("d='M0 100 100 75 100 0 0 50z'"));
+        assert!(plot.has_band());
+    }
+
+    #[test]
+    fn stacked_bars() {
+        let a = [(0.0, 2.0), (1.0, 3.0), (2.0, -1.0)];
+        let b = [(0.0, 4.0), (1.0, 1.0), (2.0, -2.0)];
+        let svg = crate::Chart::new()
+            .margin(0)
+            .aspect_ratio(crate::AspectRatio::Custom(100, 100))
+            .domain(Domain::from_ranges(0..2, -4..4))
+            .plot(Plot::bar("A", &a[..]).stacked())
+            .plot(Plot::bar("B", &b[..]).stacked())
+            .to_string();
+        assert!(svg.contains("d='M3 60 3 44 30 44 30 60zM37 60"));
+        assert!(svg.contains("M70 68 70 84 97 84 97 68z'"));
+        assert!(svg.contains("d='M3 44 3 12 30 12 30 44z"));
+        let b = svg.find("legend-bar' d='M15 0h30v30h-30z'/>\n</svg>\nB");
+        assert!(b.unwrap() < svg.find("</svg>\nA").unwrap());
+    }
+
+    #[test]
+    fn normalized_bars() {
+        let a = [(0.0, 1.0), (1.0, 3.0), (2.0, 0.0)];
+        let b = [(0.0, 3.0), (1.0, 1.0), (2.0, 0.0)];
+        let svg = crate::Chart::new()
+            .margin(0)
+            .aspect_ratio(crate::AspectRatio::Custom(100, 100))
+            .domain(Domain::from_ranges(0..2, 0..10).percent_y())
+            .plot(Plot::bar("A", &a[..]).normalized())
+            .plot(Plot::bar("B", &b[..]).normalized())
+            .to_string();
+        assert!(svg.contains("d='M3 100 3 75 30 75 30 100zM37 100 37 25"));
+        assert!(svg.contains("d='M3 75 3 0 30 0 30 75zM37 25 37 0"));
+        assert!(svg.contains("M70 100 70 100 97 100 97 100z'"));
+        assert!(!svg.contains("NaN"));
+    }
+
+    #[test]
+    fn ranges() {
+        let data = [(1.0, 0.0, 4.0), (2.0, 10.0, 6.0)];
+        let domain = Domain::from_range_data(&data[..]);
+        let mut plot = Plot::ranges("Shifts", &data[..]);
+        plot.bind_domain(domain.bind(Rect::new(0, 0, 100, 100)));
+        let svg = plot.to_string();
+        assert!(svg.contains("d='M0 80 40 80 40 120 0 120z"));
+        assert!(svg.contains("zM60 -20 100 -20 100 20 60 20z'"));
+    }
 }
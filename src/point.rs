@@ -5,11 +5,158 @@
 
 /// Data point
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     /// `X` value
-    pub x: f32,
+    pub x: f64,
     /// `Y` value
-    pub y: f32,
+    pub y: f64,
+}
+
+impl Point {
+    /// Check if the point is missing a value (`NaN`)
+    ///
+    /// A `None` value in `(x, Option<y>)` data is converted to `NaN`.
+    pub fn is_missing(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan()
+    }
+}
+
+/// Data point with an optional label
+///
+/// Used with `Plot::scatter_labeled`, to display custom text instead of
+/// coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabeledPoint {
+    /// `X` value
+    pub x: f64,
+    /// `Y` value
+    pub y: f64,
+    /// Label text
+    pub label: Option<String>,
+}
+
+impl LabeledPoint {
+    /// Create a new labeled point
+    pub fn new<P, S>(pt: P, label: S) -> Self
+    where
+        P: Into<Point>,
+        S: Into<String>,
+    {
+        let pt = pt.into();
+        LabeledPoint {
+            x: pt.x,
+            y: pt.y,
+            label: Some(label.into()),
+        }
+    }
+
+    /// Create a new point with no label
+    pub fn unlabeled<P>(pt: P) -> Self
+    where
+        P: Into<Point>,
+    {
+        let pt = pt.into();
+        LabeledPoint {
+            x: pt.x,
+            y: pt.y,
+            label: None,
+        }
+    }
+}
+
+impl<P, S> From<(P, S)> for LabeledPoint
+where
+    P: IntoPoint,
+    S: Into<String>,
+{
+    fn from((pt, label): (P, S)) -> Self {
+        LabeledPoint::new(pt, label)
+    }
+}
+
+/// Data point with a range of uncertainty
+///
+/// Used with `Plot::line_with_band`, to draw a ribbon from `lo` to `hi`
+/// around a line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BandPoint {
+    /// `X` value
+    pub x: f64,
+    /// `Y` value
+    pub y: f64,
+    /// Low `Y` value of the band
+    pub lo: f64,
+    /// High `Y` value of the band
+    pub hi: f64,
+}
+
+impl<X, Y, L, H> From<(X, Y, L, H)> for BandPoint
+where
+    X: Into<f64>,
+    Y: Into<f64>,
+    L: Into<f64>,
+    H: Into<f64>,
+{
+    fn from((x, y, lo, hi): (X, Y, L, H)) -> Self {
+        BandPoint {
+            x: x.into(),
+            y: y.into(),
+            lo: lo.into(),
+            hi: hi.into(),
+        }
+    }
+}
+
+impl<X, Y, L, H> From<&(X, Y, L, H)> for BandPoint
+where
+    X: Into<f64> + Copy,
+    Y: Into<f64> + Copy,
+    L: Into<f64> + Copy,
+    H: Into<f64> + Copy,
+{
+    fn from(pt: &(X, Y, L, H)) -> Self {
+        BandPoint::from(*pt)
+    }
+}
+
+/// Interval of `X` values at one `Y` row
+///
+/// Used with `Plot::ranges`, to draw a horizontal bar from `start` to `end`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RangePoint {
+    /// `Y` value of the row
+    pub row: f64,
+    /// Starting `X` value
+    pub start: f64,
+    /// Ending `X` value
+    pub end: f64,
+}
+
+impl<R, S, E> From<(R, S, E)> for RangePoint
+where
+    R: Into<f64>,
+    S: Into<f64>,
+    E: Into<f64>,
+{
+    fn from((row, start, end): (R, S, E)) -> Self {
+        RangePoint {
+            row: row.into(),
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+}
+
+impl<R, S, E> From<&(R, S, E)> for RangePoint
+where
+    R: Into<f64> + Copy,
+    S: Into<f64> + Copy,
+    E: Into<f64> + Copy,
+{
+    fn from(pt: &(R, S, E)) -> Self {
+        RangePoint::from(*pt)
+    }
 }
 
 /// Data which can represent a point
@@ -22,7 +169,7 @@ macro_rules! impl_point_from {
         impl From<$val> for Point {
             fn from(item: $val) -> Self {
                 Point {
-                    x: item as f32,
+                    x: item as f64,
                     y: 0.0,
                 }
             }
@@ -32,7 +179,7 @@ macro_rules! impl_point_from {
         impl From<&$val> for Point {
             fn from(item: &$val) -> Self {
                 Point {
-                    x: *item as f32,
+                    x: *item as f64,
                     y: 0.0,
                 }
             }
@@ -49,47 +196,71 @@ impl_point_from!(i16);
 impl_point_from!(i32);
 impl_point_from!(i64);
 impl_point_from!(i128);
+impl_point_from!(usize);
+impl_point_from!(u8);
+impl_point_from!(u16);
+impl_point_from!(u32);
+impl_point_from!(u64);
+impl_point_from!(u128);
 
 macro_rules! impl_point_from_tuple {
-    ($val:ty) => {
-        impl From<($val, $val)> for Point {
-            fn from(item: ($val, $val)) -> Self {
+    ($a:ty, $b:ty) => {
+        impl From<($a, $b)> for Point {
+            fn from(item: ($a, $b)) -> Self {
                 Point {
-                    x: item.0 as f32,
-                    y: item.1 as f32,
+                    x: item.0 as f64,
+                    y: item.1 as f64,
                 }
             }
         }
-        impl IntoPoint for ($val, $val) {}
+        impl IntoPoint for ($a, $b) {}
 
-        impl From<(&$val, &$val)> for Point {
-            fn from(item: (&$val, &$val)) -> Self {
+        impl From<(&$a, &$b)> for Point {
+            fn from(item: (&$a, &$b)) -> Self {
                 Point {
-                    x: *item.0 as f32,
-                    y: *item.1 as f32,
+                    x: *item.0 as f64,
+                    y: *item.1 as f64,
                 }
             }
         }
-        impl IntoPoint for (&$val, &$val) {}
+        impl IntoPoint for (&$a, &$b) {}
+
+        impl From<($a, Option<$b>)> for Point {
+            fn from(item: ($a, Option<$b>)) -> Self {
+                Point {
+                    x: item.0 as f64,
+                    y: item.1.map_or(f64::NAN, |y| y as f64),
+                }
+            }
+        }
+        impl IntoPoint for ($a, Option<$b>) {}
+    };
+}
+
+/// Implement tuple conversions for every pair of types
+macro_rules! impl_point_from_tuples {
+    ($($a:ty),*) => {
+        impl_point_from_tuples!(@x [$($a),*] [$($a),*]);
+    };
+    (@x [$($a:ty),*] $b:tt) => {
+        $(impl_point_from_tuples!(@y $a $b);)*
+    };
+    (@y $a:ty [$($b:ty),*]) => {
+        $(impl_point_from_tuple!($a, $b);)*
     };
 }
 
-impl_point_from_tuple!(f32);
-impl_point_from_tuple!(f64);
-impl_point_from_tuple!(isize);
-impl_point_from_tuple!(i8);
-impl_point_from_tuple!(i16);
-impl_point_from_tuple!(i32);
-impl_point_from_tuple!(i64);
-impl_point_from_tuple!(i128);
+impl_point_from_tuples!(
+    f32, f64, isize, i8, i16, i32, i64, i128, usize, u8, u16, u32, u64, u128
+);
 
 macro_rules! impl_point_from_arr {
     ($val:ty) => {
         impl From<[$val; 2]> for Point {
             fn from(item: [$val; 2]) -> Self {
                 Point {
-                    x: item[0] as f32,
-                    y: item[1] as f32,
+                    x: item[0] as f64,
+                    y: item[1] as f64,
                 }
             }
         }
@@ -105,3 +276,79 @@ impl_point_from_arr!(i16);
 impl_point_from_arr!(i32);
 impl_point_from_arr!(i64);
 impl_point_from_arr!(i128);
+impl_point_from_arr!(usize);
+impl_point_from_arr!(u8);
+impl_point_from_arr!(u16);
+impl_point_from_arr!(u32);
+impl_point_from_arr!(u64);
+impl_point_from_arr!(u128);
+
+/// Implement conversions from `chrono` date / time tuples
+///
+/// The `X` value is a Unix timestamp in seconds, for use with a temporal
+/// scale (see `Domain::from_time_data`).  Sub-second precision is dropped.
+#[cfg(feature = "chrono")]
+macro_rules! impl_point_from_time {
+    ($val:ty) => {
+        impl From<(chrono::DateTime<chrono::Utc>, $val)> for Point {
+            fn from(item: (chrono::DateTime<chrono::Utc>, $val)) -> Self {
+                Point {
+                    x: item.0.timestamp() as f64,
+                    y: item.1 as f64,
+                }
+            }
+        }
+        impl IntoPoint for (chrono::DateTime<chrono::Utc>, $val) {}
+
+        impl From<(chrono::NaiveDateTime, $val)> for Point {
+            fn from(item: (chrono::NaiveDateTime, $val)) -> Self {
+                Point {
+                    x: item.0.and_utc().timestamp() as f64,
+                    y: item.1 as f64,
+                }
+            }
+        }
+        impl IntoPoint for (chrono::NaiveDateTime, $val) {}
+
+        impl From<(chrono::NaiveDate, $val)> for Point {
+            fn from(item: (chrono::NaiveDate, $val)) -> Self {
+                let dt = item.0.and_time(chrono::NaiveTime::MIN);
+                Point {
+                    x: dt.and_utc().timestamp() as f64,
+                    y: item.1 as f64,
+                }
+            }
+        }
+        impl IntoPoint for (chrono::NaiveDate, $val) {}
+    };
+}
+
+#[cfg(feature = "chrono")]
+impl_point_from_time!(f32);
+#[cfg(feature = "chrono")]
+impl_point_from_time!(f64);
+#[cfg(feature = "chrono")]
+impl_point_from_time!(i32);
+#[cfg(feature = "chrono")]
+impl_point_from_time!(i64);
+#[cfg(feature = "chrono")]
+impl_point_from_time!(u32);
+#[cfg(feature = "chrono")]
+impl_point_from_time!(u64);
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    #[test]
+    fn chrono() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let pt: Point = (date, 5.0).into();
+        assert_eq!(pt.x, 1_709_251_200.0);
+        assert_eq!(pt.y, 5.0);
+        let dt = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(Point::from((dt, 5)), pt);
+        assert_eq!(Point::from((dt.naive_utc(), 5i64)), pt);
+    }
+}
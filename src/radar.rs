@@ -0,0 +1,372 @@
+// radar.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+use crate::chart::AspectRatio;
+use crate::format::NumFormat;
+use crate::path::point_dec_fmt;
+use crate::rect::{Edge, Rect, ViewBox};
+use crate::scale::Scale;
+use crate::style::{Stylesheet, DEFAULT_CSS};
+use crate::text::{escape, indent, Anchor, Text, Tspan};
+use crate::title::Title;
+use std::borrow::Cow;
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::fmt;
+
+/// Space between the outer ring and the plot area edge, for labels
+const LABEL_SPACE: u16 = 160;
+
+/// Distance from the outer ring to category labels
+const LABEL_OFFSET: f64 = 24.0;
+
+/// Distance from the center spoke to ring value labels
+const RING_LABEL_PAD: f64 = 8.0;
+
+/// One series of values, by category
+struct Series<'a> {
+    name: Cow<'a, str>,
+    values: Vec<f64>,
+}
+
+/// Radar (spider) chart
+///
+/// Each category has an axis radiating from the center, and each series is
+/// drawn as a closed polygon over its values.  All axes share one value
+/// scale, starting at zero in the center.
+///
+/// A `NaN` or missing value skips that vertex, so the polygon connects the
+/// neighboring values directly.
+///
+/// ```rust
+/// use splot::Radar;
+///
+/// let radar = Radar::new(["Speed", "Memory", "Startup", "Size"])
+///     .title("Benchmarks")
+///     .series("v1", [8.0, 6.0, 7.0, 4.0])
+///     .series("v2", [9.0, 5.0, 8.0, 6.0]);
+/// println!("{radar}");
+/// ```
+pub struct Radar<'a> {
+    stylesheet: Stylesheet<'a>,
+    stand_alone: bool,
+    aspect_ratio: AspectRatio,
+    titles: Vec<Title<'a>>,
+    categories: Vec<Cow<'a, str>>,
+    max: Option<f64>,
+    series: Vec<Series<'a>>,
+    scale: Scale,
+    area: Rect,
+}
+
+impl<'a> Radar<'a> {
+    /// Create a new radar chart, with one axis per category
+    ///
+    /// Axes are placed clockwise, starting at the top.
+    pub fn new<I>(categories: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'a, str>>,
+    {
+        let mut radar = Radar {
+            stylesheet: Stylesheet::default(),
+            stand_alone: true,
+            aspect_ratio: AspectRatio::Square,
+            titles: Vec::new(),
+            categories: categories.into_iter().map(Into::into).collect(),
+            max: None,
+            series: Vec::new(),
+            scale: Scale::default(),
+            area: Rect::default(),
+        };
+        radar.layout();
+        radar
+    }
+
+    /// Embed the default stylesheet instead of linking to it
+    pub fn embed_css(self) -> Self {
+        self.css(DEFAULT_CSS)
+    }
+
+    /// Embed custom CSS text instead of linking to the default stylesheet
+    pub fn css(mut self, css: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Embed(css.into());
+        self
+    }
+
+    /// Set the stylesheet link href
+    ///
+    /// The default is `./css/splot.css`.  An empty href suppresses the link.
+    pub fn css_href(mut self, href: &'a str) -> Self {
+        self.stylesheet = Stylesheet::Link(href);
+        self
+    }
+
+    /// Make the chart stand-alone, linking or embedding its stylesheet
+    ///
+    /// This is the default.  On a `Page`, the stylesheet is in the HTML head.
+    pub fn stand_alone(mut self, stand_alone: bool) -> Self {
+        self.stand_alone = stand_alone;
+        self
+    }
+
+    /// Adjust the aspect ratio
+    ///
+    /// The default is `Square`.
+    pub fn aspect_ratio(mut self, aspect: AspectRatio) -> Self {
+        self.aspect_ratio = aspect;
+        self.layout();
+        self
+    }
+
+    /// Add a chart title
+    pub fn title<T>(mut self, title: T) -> Self
+    where
+        T: Into<Title<'a>>,
+    {
+        self.titles.push(title.into());
+        self.layout();
+        self
+    }
+
+    /// Set the value at the outer ring
+    ///
+    /// By default, the scale covers the largest value of any series.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self.layout();
+        self
+    }
+
+    /// Add a series, with one value per category
+    ///
+    /// Values are in category order.  Missing values at the end are
+    /// treated as `NaN`, skipping those vertices.
+    pub fn series<N, I>(mut self, name: N, values: I) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        I: IntoIterator,
+        I::Item: Into<f64>,
+    {
+        self.series.push(Series {
+            name: name.into(),
+            values: values.into_iter().map(Into::into).collect(),
+        });
+        self.layout();
+        self
+    }
+
+    /// Render the chart as indented SVG, for debugging
+    ///
+    /// Only indentation differs from the `Display` output.
+    pub fn to_string_pretty(&self) -> String {
+        indent(&self.to_string())
+    }
+
+    /// Lay out titles and the value scale
+    fn layout(&mut self) {
+        let mut area = self.aspect_ratio.rect().inset(40);
+        for title in self.titles.iter_mut() {
+            area = title.split(area);
+        }
+        self.area = area;
+        let max = self.max.unwrap_or_else(|| {
+            self.series
+                .iter()
+                .flat_map(|s| s.values.iter())
+                .filter(|v| v.is_finite())
+                .fold(0.0, |a: f64, b| a.max(*b))
+        });
+        let max = if max > 0.0 { max } else { 1.0 };
+        self.scale = Scale::from_range(0.0, max);
+    }
+
+    /// Get the center point
+    fn center(&self) -> (f64, f64) {
+        let cx = f64::from(self.area.x) + f64::from(self.area.width) / 2.0;
+        let cy = f64::from(self.area.y) + f64::from(self.area.height) / 2.0;
+        (cx, cy)
+    }
+
+    /// Get the radius of the outer ring
+    fn radius(&self) -> f64 {
+        let size = self.area.width.min(self.area.height) / 2;
+        f64::from(size.saturating_sub(LABEL_SPACE))
+    }
+
+    /// Get the point at a distance along the axis of a category
+    ///
+    /// The distance is normalized, with the outer ring at 1.
+    fn vertex(&self, i: usize, dist: f64) -> (f64, f64) {
+        let (cx, cy) = self.center();
+        let r = self.radius() * dist;
+        let angle = TAU * i as f64 / self.categories.len() as f64 - FRAC_PI_2;
+        (cx + r * angle.cos(), cy + r * angle.sin())
+    }
+
+    /// Format a closed polygon path through points
+    fn polygon_fmt<I>(&self, f: &mut fmt::Formatter, points: I) -> fmt::Result
+    where
+        I: IntoIterator<Item = (f64, f64)>,
+    {
+        for (j, (x, y)) in points.into_iter().enumerate() {
+            let cmd = if j == 0 { b'M' } else { b' ' };
+            point_dec_fmt(f, cmd, x, y, 0)?;
+        }
+        f.write_str("z")
+    }
+
+    /// Render grid rings and spokes
+    fn grid(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = self.categories.len();
+        let ticks = self.scale.ticks(&NumFormat::default());
+        let rings: Vec<_> = ticks.iter().filter(|t| t.value() > 0.0).collect();
+        write!(f, "<path class='grid-y' d='")?;
+        for tick in &rings {
+            self.polygon_fmt(f, (0..n).map(|i| self.vertex(i, tick.value())))?;
+        }
+        writeln!(f, "'/>")?;
+        let (cx, cy) = self.center();
+        write!(f, "<path class='grid-x' d='")?;
+        for i in 0..n {
+            let (x, y) = self.vertex(i, 1.0);
+            point_dec_fmt(f, b'M', cx, cy, 0)?;
+            point_dec_fmt(f, b'L', x, y, 0)?;
+        }
+        writeln!(f, "'/>")?;
+        let text = Text::new(Edge::Top)
+            .anchor(Anchor::Start)
+            .class_name("tick");
+        text.display(f)?;
+        for tick in &rings {
+            let (_, y) = self.vertex(0, tick.value());
+            let x = (cx + RING_LABEL_PAD).round() as i32;
+            let y = y.round() as i32;
+            write!(f, "{}", Tspan::new(tick.text()).x(x).y(y).dy(0.33))?;
+        }
+        text.display_done(f)
+    }
+
+    /// Render category labels around the outer ring
+    fn labels(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (cx, _) = self.center();
+        let dist = 1.0 + LABEL_OFFSET / self.radius().max(1.0);
+        for (i, category) in self.categories.iter().enumerate() {
+            let (x, y) = self.vertex(i, dist);
+            let anchor = if x > cx + 1.0 {
+                Anchor::Start
+            } else if x < cx - 1.0 {
+                Anchor::End
+            } else {
+                Anchor::Middle
+            };
+            let text = Text::new(Edge::Top).anchor(anchor).class_name("axis");
+            text.display(f)?;
+            let category = escape(category);
+            let tspan = Tspan::new(category.as_ref())
+                .x(x.round() as i32)
+                .y(y.round() as i32)
+                .dy(0.33);
+            write!(f, "{tspan}")?;
+            text.display_done(f)?;
+        }
+        Ok(())
+    }
+
+    /// Render one series polygon
+    fn series_fmt(
+        &self,
+        f: &mut fmt::Formatter,
+        num: usize,
+        series: &Series,
+    ) -> fmt::Result {
+        let points: Vec<_> = (0..self.categories.len())
+            .filter_map(|i| {
+                let value = series.values.get(i).copied().unwrap_or(f64::NAN);
+                (!value.is_nan())
+                    .then(|| self.vertex(i, self.scale.normalize(value)))
+            })
+            .collect();
+        if points.is_empty() {
+            return Ok(());
+        }
+        write!(f, "<path class='plot-{} plot-radar' d='", num % 11)?;
+        self.polygon_fmt(f, points)?;
+        writeln!(f, "'/>")
+    }
+
+    /// Render the legend as an HTML fragment
+    fn legend(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "<div class='legend'>")?;
+        for (num, series) in self.series.iter().enumerate() {
+            writeln!(f, "<div>")?;
+            writeln!(f, "<svg width='20' height='10' viewBox='0 0 60 30'>")?;
+            write!(f, "<path class='plot-{} legend-radar'", num % 11)?;
+            writeln!(f, " d='M0 5h60v20h-60z'/>")?;
+            writeln!(f, "</svg>")?;
+            writeln!(f, "{}", escape(&series.name))?;
+            writeln!(f, "</div>")?;
+        }
+        writeln!(f, "</div>")
+    }
+}
+
+impl<'a> fmt::Display for Radar<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let view_box = ViewBox(self.aspect_ratio.rect());
+        write!(f, "<svg")?;
+        if self.stand_alone {
+            write!(f, " xmlns='http://www.w3.org/2000/svg'")?;
+        }
+        writeln!(f, " {view_box} role='img'>")?;
+        if self.stand_alone {
+            self.stylesheet.svg_fmt(f)?;
+        }
+        for title in &self.titles {
+            writeln!(f, "{title}")?;
+        }
+        if !self.categories.is_empty() {
+            self.grid(f)?;
+            self.labels(f)?;
+            for (num, series) in self.series.iter().enumerate() {
+                self.series_fmt(f, num, series)?;
+            }
+        }
+        writeln!(f, "</svg>")?;
+        self.legend(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygons() {
+        let radar = Radar::new(["A", "B", "C", "D"])
+            .aspect_ratio(AspectRatio::Custom(1000, 1000))
+            .series("One", [10.0, 5.0, 10.0, 5.0])
+            .series("Two", [10.0, f64::NAN, 5.0]);
+        let svg = radar.to_string();
+        // center (500, 500), outer ring radius 300
+        assert!(svg.contains(
+            "<path class='plot-0 plot-radar' d='M500 200 650 500 500 800 350 500z'/>"
+        ));
+        assert!(svg.contains(
+            "<path class='plot-1 plot-radar' d='M500 200 500 650z'/>"
+        ));
+        assert!(svg.contains("<path class='grid-x' d='M500 500L500 200"));
+        assert!(svg.contains("text-anchor='start'>\n<tspan x='824' y='500'"));
+        assert!(svg.contains(">10</tspan>"));
+        assert!(svg.contains("<path class='plot-1 legend-radar'"));
+    }
+
+    #[test]
+    fn page() {
+        let radar = Radar::new(["A", "B", "C"]).series("One", [1, 2, 3]);
+        let html = crate::Page::new().radar(radar).to_string();
+        assert!(html.contains("<div class='chart'>\n<svg viewBox="));
+        assert!(!html.contains("xmlns='http://www.w3.org/2000/svg'"));
+        assert_eq!(html.matches("<link").count(), 1);
+    }
+}
@@ -0,0 +1,132 @@
+// raster.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! PNG rasterization of charts
+//!
+use crate::chart::Chart;
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::fontdb::{self, Family, Query};
+use resvg::usvg::{Options, Tree};
+use std::fmt;
+use std::sync::Arc;
+
+/// Error rendering a chart to a raster image
+#[derive(Debug)]
+pub enum RenderError {
+    /// Invalid image size
+    Size(u32, u32),
+    /// SVG could not be parsed
+    Svg(resvg::usvg::Error),
+    /// PNG encoding failed
+    Png(String),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderError::Size(w, h) => write!(f, "invalid image size {w}x{h}"),
+            RenderError::Svg(e) => write!(f, "SVG error: {e}"),
+            RenderError::Png(e) => write!(f, "PNG error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// Load system fonts
+///
+/// If no font matches the generic `sans-serif` family, the first installed
+/// sans font (or any font) is used instead.
+fn system_fonts() -> fontdb::Database {
+    let mut fontdb = fontdb::Database::new();
+    fontdb.load_system_fonts();
+    let query = Query {
+        families: &[Family::SansSerif],
+        ..Query::default()
+    };
+    if fontdb.query(&query).is_none() {
+        let families = fontdb.faces().filter_map(|face| face.families.first());
+        let mut fallback = None;
+        for (family, _lang) in families {
+            if family.contains("Sans") {
+                fallback = Some(family);
+                break;
+            }
+            fallback = fallback.or(Some(family));
+        }
+        if let Some(family) = fallback.cloned() {
+            fontdb.set_sans_serif_family(family);
+        }
+    }
+    fontdb
+}
+
+impl<'a> Chart<'a> {
+    /// Render the chart to PNG bytes, using system fonts
+    ///
+    /// The image is `width` pixels wide, with height set by the aspect ratio.
+    /// The rasterizer does not support CSS custom properties, which the
+    /// default stylesheet uses for colors, so build the chart with
+    /// `inline_styles`.  Otherwise, the chart CSS is embedded as-is.
+    pub fn to_png(&self, width: u32) -> Result<Vec<u8>, RenderError> {
+        self.to_png_with_fonts(width, Arc::new(system_fonts()))
+    }
+
+    /// Render the chart to PNG bytes, using a font database
+    pub fn to_png_with_fonts(
+        &self,
+        width: u32,
+        fontdb: Arc<fontdb::Database>,
+    ) -> Result<Vec<u8>, RenderError> {
+        let opts = Options {
+            fontdb,
+            ..Options::default()
+        };
+        let tree = Tree::from_str(&self.embedded_svg(), &opts)
+            .map_err(RenderError::Svg)?;
+        let size = tree.size();
+        let scale = width as f32 / size.width();
+        let height = (size.height() * scale).round() as u32;
+        let mut pixmap = Pixmap::new(width, height)
+            .ok_or(RenderError::Size(width, height))?;
+        let transform = Transform::from_scale(scale, scale);
+        resvg::render(&tree, transform, &mut pixmap.as_mut());
+        pixmap
+            .encode_png()
+            .map_err(|e| RenderError::Png(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Edge, Plot};
+
+    /// Get PNG image dimensions from the IHDR chunk
+    fn png_size(png: &[u8]) -> (u32, u32) {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        let w = u32::from_be_bytes(png[16..20].try_into().unwrap());
+        let h = u32::from_be_bytes(png[20..24].try_into().unwrap());
+        (w, h)
+    }
+
+    #[test]
+    fn png() {
+        let data = [(0.0, 1.0), (1.0, 2.0), (2.0, 1.5)];
+        let chart = Chart::new()
+            .inline_styles()
+            .title("Raster")
+            .domain(&data[..])
+            .axis("X", Edge::Bottom)
+            .axis("Y", Edge::Left)
+            .plot(Plot::line("A", &data[..]).markers());
+        let png = chart.to_png(400).unwrap();
+        assert_eq!(png_size(&png), (400, 300));
+        let fonts = Arc::new(fontdb::Database::new());
+        let png = chart.to_png_with_fonts(200, fonts).unwrap();
+        assert_eq!(png_size(&png), (200, 150));
+        assert!(matches!(chart.to_png(0), Err(RenderError::Size(0, 0))));
+    }
+}
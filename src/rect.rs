@@ -6,6 +6,7 @@ use std::fmt;
 
 /// Edge of rendered item
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Edge {
     Top,
     Left,
@@ -26,6 +27,13 @@ pub struct Rect {
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct ViewBox(pub Rect);
 
+impl Edge {
+    /// Check if an edge is horizontal (top or bottom)
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Edge::Top | Edge::Bottom)
+    }
+}
+
 impl Rect {
     /// Create a new rectangle
     pub fn new(x: i32, y: i32, width: u16, height: u16) -> Self {
@@ -47,6 +55,11 @@ impl Rect {
         self.y + i32::from(self.height)
     }
 
+    /// Check if the rectangle has no area
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
     /// Make a new rectangle inset on all edges
     pub fn inset(&self, value: u16) -> Self {
         let vi = i32::from(value);
@@ -58,6 +71,32 @@ impl Rect {
         Rect::new(x, y, width, height)
     }
 
+    /// Make a new rectangle inset by a different value on each edge
+    pub fn inset_edges(
+        &self,
+        top: u16,
+        right: u16,
+        bottom: u16,
+        left: u16,
+    ) -> Self {
+        let x = self.x + i32::from(left);
+        let y = self.y + i32::from(top);
+        let width = self.width.saturating_sub(left.saturating_add(right));
+        let height = self.height.saturating_sub(top.saturating_add(bottom));
+        Rect::new(x, y, width, height)
+    }
+
+    /// Make a new rectangle outset on all edges
+    pub fn outset(&self, value: u16) -> Self {
+        let vi = i32::from(value);
+        let x = self.x - vi;
+        let y = self.y - vi;
+        let v2 = 2 * value;
+        let width = self.width.saturating_add(v2);
+        let height = self.height.saturating_add(v2);
+        Rect::new(x, y, width, height)
+    }
+
     /// Split off rectangle from an edge
     pub fn split(&self, edge: Edge, value: u16) -> (Self, Self) {
         match edge {
@@ -97,19 +136,23 @@ impl Rect {
     }
 
     /// Intersect horizontally
+    ///
+    /// The width is zero if the rectangles do not overlap.
     pub fn intersect_horiz(&mut self, rhs: &Rect) {
         let x = self.x.max(rhs.x);
         let x2 = self.right().min(rhs.right());
         self.x = x;
-        self.width = (x2 - x) as u16;
+        self.width = (x2 - x).max(0) as u16;
     }
 
     /// Intersect vertically
+    ///
+    /// The height is zero if the rectangles do not overlap.
     pub fn intersect_vert(&mut self, rhs: &Rect) {
         let y = self.y.max(rhs.y);
         let y2 = self.bottom().min(rhs.bottom());
         self.y = y;
-        self.height = (y2 - y) as u16;
+        self.height = (y2 - y).max(0) as u16;
     }
 }
 
@@ -132,3 +175,21 @@ impl fmt::Display for ViewBox {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersect() {
+        let mut rect = Rect::new(0, 0, 100, 100);
+        rect.intersect_horiz(&Rect::new(150, 0, 100, 100));
+        assert_eq!(rect.width, 0);
+        let mut rect = Rect::new(0, 0, 100, 100);
+        rect.intersect_vert(&Rect::new(0, 50, 100, 100));
+        assert_eq!(rect, Rect::new(0, 50, 100, 50));
+        let (rest, top) = Rect::new(0, 0, 100, 100).split(Edge::Top, 200);
+        assert!(rest.is_empty());
+        assert_eq!(top.height, 100);
+    }
+}
@@ -0,0 +1,193 @@
+// sample.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Downsampling and smoothing of data points
+//!
+use crate::point::Point;
+
+/// Downsample points using Largest-Triangle-Three-Buckets
+///
+/// Points are ordered by `X` value before sampling.  Missing points split the
+/// data into runs, which share `max_points` in proportion to their length.
+/// Each run is sampled separately, keeping its first and last points, and
+/// runs are separated by a missing point to preserve gaps.
+pub fn lttb(mut data: Vec<Point>, max_points: usize) -> Vec<Point> {
+    let threshold = max_points.max(2);
+    if data.len() <= threshold {
+        return data;
+    }
+    data.sort_by(|a, b| a.x.total_cmp(&b.x));
+    let runs: Vec<&[Point]> = data
+        .split(|pt| pt.is_missing())
+        .filter(|run| !run.is_empty())
+        .collect();
+    let total: usize = runs.iter().map(|run| run.len()).sum();
+    let mut sampled = Vec::with_capacity(threshold + runs.len());
+    for (i, run) in runs.iter().enumerate() {
+        if i > 0 {
+            sampled.push(Point {
+                x: f64::NAN,
+                y: f64::NAN,
+            });
+        }
+        let share = threshold * run.len() / total;
+        sampled.extend(lttb_run(run, share.max(2)));
+    }
+    sampled
+}
+
+/// Downsample one run of present points
+fn lttb_run(data: &[Point], threshold: usize) -> Vec<Point> {
+    if data.len() <= threshold {
+        return data.to_vec();
+    }
+    let len = data.len();
+    let every = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut sampled = Vec::with_capacity(threshold);
+    let mut a = 0;
+    sampled.push(data[0]);
+    for i in 0..threshold - 2 {
+        // average of next bucket
+        let start = ((i + 1) as f64 * every) as usize + 1;
+        let end = (((i + 2) as f64 * every) as usize + 1).min(len);
+        let next = &data[start..end];
+        let n = next.len() as f64;
+        let avg_x = next.iter().map(|pt| pt.x).sum::<f64>() / n;
+        let avg_y = next.iter().map(|pt| pt.y).sum::<f64>() / n;
+        // point in current bucket with largest triangle area
+        let start = (i as f64 * every) as usize + 1;
+        let end = ((i + 1) as f64 * every) as usize + 1;
+        let pa = data[a];
+        let (ax, ay) = (pa.x, pa.y);
+        let mut max_area = -1.0;
+        for (j, pt) in data[start..end].iter().enumerate() {
+            let (x, y) = (pt.x, pt.y);
+            let area =
+                ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                a = start + j;
+            }
+        }
+        sampled.push(data[a]);
+    }
+    sampled.push(data[len - 1]);
+    sampled
+}
+
+/// Smooth `Y` values with a moving average
+///
+/// The window is centered on each point, or trailing (ending at each point).
+/// Near the edges, the window shrinks to the available points.  A window
+/// covering all points gives the overall mean.  Missing points are kept as
+/// gaps, and left out of the averages.
+pub fn moving_average(
+    data: &[Point],
+    window: usize,
+    trailing: bool,
+) -> Vec<Point> {
+    let len = data.len();
+    let window = window.clamp(1, len.max(1));
+    // prefix sums of present values and counts
+    let mut sums = Vec::with_capacity(len + 1);
+    let mut counts = Vec::with_capacity(len + 1);
+    let (mut sum, mut count) = (0.0, 0_usize);
+    sums.push(sum);
+    counts.push(count);
+    for pt in data {
+        if !pt.is_missing() {
+            sum += pt.y;
+            count += 1;
+        }
+        sums.push(sum);
+        counts.push(count);
+    }
+    data.iter()
+        .enumerate()
+        .map(|(i, pt)| {
+            if pt.is_missing() {
+                return Point {
+                    x: pt.x,
+                    y: f64::NAN,
+                };
+            }
+            let (start, end) = if window >= len {
+                (0, len)
+            } else if trailing {
+                ((i + 1).saturating_sub(window), i + 1)
+            } else {
+                let start = i.saturating_sub((window - 1) / 2);
+                (start, (i + 1 + window / 2).min(len))
+            };
+            let n = counts[end] - counts[start];
+            let y = (sums[end] - sums[start]) / n as f64;
+            Point { x: pt.x, y }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spike() {
+        let mut data: Vec<Point> = (0..1000)
+            .map(|i| Point {
+                x: i as f64,
+                y: 0.0,
+            })
+            .collect();
+        data[500].y = 100.0;
+        let sampled = lttb(data, 10);
+        assert_eq!(sampled.len(), 10);
+        assert_eq!(sampled[0].x, 0.0);
+        assert_eq!(sampled[9].x, 999.0);
+        assert!(sampled.iter().any(|pt| pt.y == 100.0));
+    }
+
+    #[test]
+    fn gap() {
+        let data: Vec<Point> = (0..1000)
+            .map(|i| Point {
+                x: i as f64,
+                y: if i == 400 { f64::NAN } else { 1.0 },
+            })
+            .collect();
+        let sampled = lttb(data, 10);
+        let gaps: Vec<_> = sampled
+            .iter()
+            .enumerate()
+            .filter(|(_, pt)| pt.is_missing())
+            .collect();
+        assert_eq!(gaps.len(), 1);
+        let i = gaps[0].0;
+        assert_eq!(sampled[i - 1].x, 399.0);
+        assert_eq!(sampled[i + 1].x, 401.0);
+        assert_eq!(sampled[0].x, 0.0);
+        assert_eq!(sampled.last().unwrap().x, 999.0);
+    }
+
+    #[test]
+    fn average() {
+        let data: Vec<Point> = [1.0, 2.0, 6.0, f64::NAN, 4.0]
+            .iter()
+            .enumerate()
+            .map(|(i, y)| Point { x: i as f64, y: *y })
+            .collect();
+        let ys = |pts: Vec<Point>| -> Vec<f64> {
+            pts.iter().map(|pt| pt.y).collect()
+        };
+        let centered = ys(moving_average(&data, 3, false));
+        assert_eq!(centered[..3], [1.5, 3.0, 4.0]);
+        assert!(centered[3].is_nan());
+        assert_eq!(centered[4], 4.0);
+        let trailing = ys(moving_average(&data, 2, true));
+        assert_eq!(trailing[..3], [1.0, 1.5, 4.0]);
+        assert_eq!(trailing[4], 4.0);
+        let mean = ys(moving_average(&data, 10, false));
+        assert_eq!(mean[..3], [3.25, 3.25, 3.25]);
+        assert!(moving_average(&[], 5, true).is_empty());
+    }
+}
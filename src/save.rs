@@ -0,0 +1,68 @@
+// save.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Saving output to files
+//!
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Get a temporary path next to a file
+fn temp_path(path: &Path) -> io::Result<PathBuf> {
+    let name = path.file_name().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "path has no file name")
+    })?;
+    let mut temp = name.to_os_string();
+    temp.push(format!(".{}.tmp", std::process::id()));
+    Ok(path.with_file_name(temp))
+}
+
+/// Write a file atomically
+///
+/// Parent directories are created as needed.  Contents are written to a
+/// temporary file, which is then renamed to `path`, so that a failure never
+/// leaves a truncated file behind.
+pub(crate) fn write_atomic<P, F>(path: P, write: F) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut File) -> io::Result<()>,
+{
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+    let temp = temp_path(path)?;
+    let res = File::create(&temp)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if res.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn atomic() {
+        let dir = std::env::temp_dir().join("splot-save-atomic");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("a/b/out.txt");
+        write_atomic(&path, |f| f.write_all(b"hello")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+        let err = write_atomic(&path, |_| Err(io::ErrorKind::Other.into()));
+        assert!(err.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+        assert_eq!(fs::read_dir(dir.join("a/b")).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,29 +4,113 @@
 //
 //! Scale items
 //!
+use crate::format::NumFormat;
 use crate::point::{IntoPoint, Point};
 use crate::text::Tick;
+use crate::time::DateTime;
+
+/// Maximum number of ticks on a numeric scale
+const MAX_TICKS: usize = 1000;
+
+/// Maximum number of categories appended past the end of the list
+const MAX_APPENDED: usize = 100;
 
 /// Numeric scale
 #[derive(Clone, Debug)]
 pub struct Numeric {
-    start: f32,
-    stop: f32,
-    tick_spacing: f32,
+    min: f64,
+    max: f64,
+    start: f64,
+    stop: f64,
+    tick_spacing: f64,
+}
+
+/// Logarithmic (base 10) scale
+#[derive(Clone, Debug)]
+pub struct Logarithmic {
+    start: i32,
+    stop: i32,
+    inverted: bool,
+}
+
+/// Temporal unit
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+/// Tick intervals for temporal scales
+const INTERVALS: &[(Unit, i64)] = &[
+    (Unit::Second, 1),
+    (Unit::Second, 5),
+    (Unit::Second, 15),
+    (Unit::Second, 30),
+    (Unit::Minute, 1),
+    (Unit::Minute, 5),
+    (Unit::Minute, 15),
+    (Unit::Minute, 30),
+    (Unit::Hour, 1),
+    (Unit::Hour, 3),
+    (Unit::Hour, 6),
+    (Unit::Hour, 12),
+    (Unit::Day, 1),
+    (Unit::Day, 2),
+    (Unit::Day, 7),
+    (Unit::Month, 1),
+    (Unit::Month, 3),
+    (Unit::Month, 6),
+    (Unit::Year, 1),
+    (Unit::Year, 2),
+    (Unit::Year, 5),
+    (Unit::Year, 10),
+    (Unit::Year, 20),
+    (Unit::Year, 50),
+    (Unit::Year, 100),
+];
+
+/// Temporal scale (Unix timestamps in seconds)
+#[derive(Clone, Debug)]
+pub struct Temporal {
+    min: f64,
+    max: f64,
+    start: i64,
+    stop: i64,
+    unit: Unit,
+    count: i64,
+    inverted: bool,
+    format: Option<String>,
+    boundaries: bool,
+}
+
+/// Categorical scale
+///
+/// Each category is mapped to an evenly spaced band, addressed by index.
+#[derive(Clone, Debug)]
+pub struct Categorical {
+    categories: Vec<String>,
+    inverted: bool,
 }
 
 /// Scale
 #[derive(Clone, Debug)]
 pub enum Scale {
     Numeric(Numeric),
+    Log(Logarithmic),
+    Temporal(Temporal),
+    Categorical(Categorical),
 }
 
 impl Numeric {
     /// Calculate tick spacing
-    fn tick_spacing(min: f32, max: f32) -> f32 {
+    fn tick_spacing(min: f64, max: f64) -> f64 {
         let span = max - min;
         let power = span.log10().floor() as i32;
-        let spc = 10_f32.powi(power);
+        let spc = 10_f64.powi(power);
         let start = (min / spc).floor() * spc;
         let stop = (max / spc).ceil() * spc;
         let steps = (stop - start) / spc;
@@ -41,12 +125,129 @@ impl Numeric {
         }
     }
 
+    /// Expand a zero (or near-zero) span range
+    fn expand_span(min: f64, max: f64) -> (f64, f64) {
+        let tiny = f64::EPSILON * min.abs().max(max.abs()).max(1.0);
+        if max - min > tiny {
+            (min, max)
+        } else if min == 0.0 {
+            (min - 1.0, max + 1.0)
+        } else {
+            let pad = min.abs() * 0.1;
+            (min - pad, max + pad)
+        }
+    }
+
     /// Create a new numeric scale
-    pub fn new(min: f32, max: f32) -> Self {
-        let tick_spacing = Self::tick_spacing(min, max);
+    ///
+    /// A zero-span range (such as a single point) is expanded by 10% of the
+    /// value on each side, or by 1 for a value of zero.
+    pub fn new(min: f64, max: f64) -> Self {
+        let (lo, hi) = Self::expand_span(min, max);
+        let tick_spacing = Self::tick_spacing(lo, hi);
+        let start = (lo / tick_spacing).floor() * tick_spacing;
+        let stop = (hi / tick_spacing).ceil() * tick_spacing;
+        Self {
+            min,
+            max,
+            start,
+            stop,
+            tick_spacing,
+        }
+    }
+
+    /// Create a new numeric scale with a tick count hint
+    ///
+    /// The spacing is the nice step (1, 2, 2.5 or 5 × 10ⁿ) which yields
+    /// closest to `count` intervals.  The count is clamped to `2..=50`.
+    pub fn with_ticks(min: f64, max: f64, count: usize) -> Self {
+        let count = count.clamp(2, 50) as f64;
+        let span = max - min;
+        if span.partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
+            return Numeric::new(min, max);
+        }
+        let base = 10_f64.powi((span / count).log10().floor() as i32);
+        let mut best = (f64::INFINITY, base);
+        for mul in [1.0, 2.0, 2.5, 5.0, 10.0] {
+            let spc = base * mul;
+            let steps = (max / spc).ceil() - (min / spc).floor();
+            let diff = (steps - count).abs();
+            if diff < best.0 {
+                best = (diff, spc);
+            }
+        }
+        let tick_spacing = best.1;
+        let start = (min / tick_spacing).floor() * tick_spacing;
+        let stop = (max / tick_spacing).ceil() * tick_spacing;
+        Self {
+            min,
+            max,
+            start,
+            stop,
+            tick_spacing,
+        }
+    }
+
+    /// Create a new numeric scale with explicit tick spacing
+    ///
+    /// Non-positive steps are ignored, and steps which would produce more
+    /// than `MAX_TICKS` ticks are clamped.
+    pub fn with_step(min: f64, max: f64, step: f64) -> Self {
+        if step.partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
+            return Numeric::new(min, max);
+        }
+        let tick_spacing = step.max((max - min) / MAX_TICKS as f64);
         let start = (min / tick_spacing).floor() * tick_spacing;
         let stop = (max / tick_spacing).ceil() * tick_spacing;
         Self {
+            min,
+            max,
+            start,
+            stop,
+            tick_spacing,
+        }
+    }
+
+    /// Create a new numeric scale padded by a fraction of the span
+    ///
+    /// The range is not rounded out to tick multiples, so ticks are only
+    /// placed within the padded range.
+    pub fn padded(min: f64, max: f64, fraction: f64) -> Self {
+        let span = max - min;
+        let pad = if span > 0.0 {
+            span * fraction
+        } else if min != 0.0 {
+            min.abs() * fraction.max(0.1)
+        } else {
+            1.0
+        };
+        let (start, stop) = (min - pad, max + pad);
+        let tick_spacing = Self::tick_spacing(start, stop);
+        Self {
+            min,
+            max,
+            start,
+            stop,
+            tick_spacing,
+        }
+    }
+
+    /// Create a new numeric scale with an exact range
+    ///
+    /// The range is not rounded out to tick multiples.  Without a step, tick
+    /// spacing is automatic.  A zero-span range is expanded, as with `new`.
+    #[cfg(feature = "serde")]
+    pub fn exact(min: f64, max: f64, step: Option<f64>) -> Self {
+        let (start, stop) = Self::expand_span(min, max);
+        let tick_spacing = match step {
+            Some(step) if step > 0.0 => {
+                step.max((stop - start) / MAX_TICKS as f64)
+            }
+            _ => Self::tick_spacing(start, stop),
+        };
+        Self {
+            min,
+            max,
             start,
             stop,
             tick_spacing,
@@ -57,12 +258,17 @@ impl Numeric {
     fn union(&self, rhs: Self) -> Self {
         let min = self.start.min(rhs.start);
         let max = self.stop.max(rhs.stop);
-        Numeric::new(min, max)
+        let mut num = Numeric::new(min, max);
+        num.min = self.min.min(rhs.min);
+        num.max = self.max.max(rhs.max);
+        num
     }
 
     /// Create inverted scale
     fn inverted(&self) -> Self {
         Numeric {
+            min: self.min,
+            max: self.max,
             start: self.start,
             stop: self.stop,
             tick_spacing: -self.tick_spacing,
@@ -70,10 +276,10 @@ impl Numeric {
     }
 
     /// Normalize a value
-    fn normalize(&self, value: f32) -> f32 {
+    fn normalize(&self, value: f64) -> f64 {
         let a = self.start;
         let b = self.stop;
-        if b - a > f32::EPSILON {
+        if b - a > f64::EPSILON {
             if self.tick_spacing > 0.0 {
                 (value - a) / (b - a)
             } else {
@@ -84,31 +290,374 @@ impl Numeric {
         }
     }
 
-    /// Add a tick
-    fn add_tick(&self, val: f32, ticks: &mut Vec<Tick>) {
-        let value = self.normalize(val);
-        let text = format!("{val}");
-        let tick = Tick::new(value, text);
-        ticks.push(tick);
+    /// Get tick values
+    fn tick_values(&self) -> Vec<f64> {
+        let spacing = self.tick_spacing.abs();
+        // multiples of spacing within range, with tolerance for rounding
+        let first = (self.start / spacing - 1e-4).ceil() as i64;
+        let last = (self.stop / spacing + 1e-4).floor() as i64;
+        let last = last.min(first + MAX_TICKS as i64);
+        // round to the nominal value to avoid text like "0.30000001"
+        let digits = (2 - spacing.log10().floor() as i32).max(0);
+        let scale = 10_f64.powi(digits);
+        let mut values: Vec<f64> = (first..=last)
+            .map(|i| (i as f64 * spacing * scale).round() / scale)
+            .map(|val| val + 0.0) // avoid -0
+            .collect();
+        if self.tick_spacing < 0.0 {
+            values.reverse();
+        }
+        values
     }
 
     /// Create a `Vec` of ticks
-    pub fn ticks(&self) -> Vec<Tick> {
+    pub fn ticks(&self, fmt: &NumFormat) -> Vec<Tick> {
+        let values = self.tick_values();
+        let spacing = self.tick_spacing.abs();
+        let texts = fmt.with_spacing(spacing).format_all(&values);
+        values
+            .iter()
+            .zip(texts)
+            .map(|(val, text)| Tick::new(self.normalize(*val), text))
+            .collect()
+    }
+
+    /// Get offset text shared by all tick labels
+    pub fn offset_text(&self, fmt: &NumFormat) -> Option<String> {
+        let spacing = self.tick_spacing.abs();
+        fmt.with_spacing(spacing).offset_text(&self.tick_values())
+    }
+}
+
+impl Logarithmic {
+    /// Create a new logarithmic scale
+    ///
+    /// Since zero and negative values cannot be represented, a non-positive
+    /// `min` is clamped to three decades below `max`.
+    pub fn new(min: f64, max: f64) -> Self {
+        let max = if max > 0.0 { max } else { 1.0 };
+        let min = if min > 0.0 { min } else { max / 1000.0 };
+        let start = min.log10().floor() as i32;
+        let stop = (max.log10().ceil() as i32).max(start + 1);
+        Logarithmic {
+            start,
+            stop,
+            inverted: false,
+        }
+    }
+
+    /// Create a union with another scale
+    fn union(&self, rhs: Self) -> Self {
+        Logarithmic {
+            start: self.start.min(rhs.start),
+            stop: self.stop.max(rhs.stop),
+            inverted: self.inverted,
+        }
+    }
+
+    /// Create inverted scale
+    fn inverted(&self) -> Self {
+        Logarithmic {
+            start: self.start,
+            stop: self.stop,
+            inverted: !self.inverted,
+        }
+    }
+
+    /// Normalize a value
+    ///
+    /// Non-positive values are clamped to the start of the scale.
+    fn normalize(&self, value: f64) -> f64 {
+        let a = self.start as f64;
+        let b = self.stop as f64;
+        let v = if value > 0.0 { value.log10() } else { a };
+        if self.inverted {
+            (b - v) / (b - a)
+        } else {
+            (v - a) / (b - a)
+        }
+    }
+
+    /// Create a `Vec` of ticks
+    ///
+    /// Major ticks are at powers of ten, with unlabeled minor ticks at 2 and
+    /// 5 multiples.
+    pub fn ticks(&self, fmt: &NumFormat) -> Vec<Tick> {
         let mut ticks = vec![];
-        let spacing = self.tick_spacing;
-        if spacing > 0.0 {
-            let mut val = self.start;
-            while val <= self.stop {
-                self.add_tick(val, &mut ticks);
-                val += spacing;
-            }
+        let values: Vec<f64> = (self.start..=self.stop)
+            .map(|power| 10_f64.powi(power))
+            .collect();
+        let texts = if fmt.sci_exponent(&values).is_some() {
+            // each power of ten has its own exponent
+            values.iter().map(|v| fmt.format(*v)).collect()
+        } else if *fmt == NumFormat::default() {
+            values.iter().map(|v| format!("{v}")).collect()
         } else {
-            let mut val = self.stop;
-            while val >= self.start {
-                self.add_tick(val, &mut ticks);
-                val += spacing;
+            fmt.format_all(&values)
+        };
+        for (power, text) in (self.start..=self.stop).zip(texts) {
+            let val = 10_f64.powi(power);
+            ticks.push(Tick::new(self.normalize(val), text));
+            if power < self.stop {
+                for mul in [2.0, 5.0] {
+                    let value = self.normalize(mul * val);
+                    ticks.push(Tick::new(value, ""));
+                }
             }
+        }
+        if self.inverted {
+            ticks.reverse();
+        }
+        ticks
+    }
+}
+
+impl Unit {
+    /// Get approximate number of seconds
+    fn seconds(self) -> i64 {
+        match self {
+            Unit::Second => 1,
+            Unit::Minute => 60,
+            Unit::Hour => 3600,
+            Unit::Day => 86_400,
+            Unit::Month => 2_629_746,
+            Unit::Year => 31_556_952,
+        }
+    }
+
+    /// Get default tick format
+    fn format(self) -> &'static str {
+        match self {
+            Unit::Second => "%H:%M:%S",
+            Unit::Minute | Unit::Hour => "%H:%M",
+            Unit::Day => "%b %e",
+            Unit::Month => "%b %Y",
+            Unit::Year => "%Y",
+        }
+    }
+
+    /// Get format for ticks on a larger boundary
+    ///
+    /// This is midnight for sub-day units, or new year's day for days.
+    fn boundary_format(self, dt: &DateTime) -> Option<&'static str> {
+        match self {
+            Unit::Second | Unit::Minute | Unit::Hour
+                if dt.hour == 0 && dt.minute == 0 && dt.second == 0 =>
+            {
+                Some("%b %e")
+            }
+            Unit::Day if dt.month == 1 && dt.day == 1 => Some("%Y"),
+            _ => None,
+        }
+    }
+}
+
+impl Temporal {
+    /// Create a new temporal scale
+    ///
+    /// The tick interval is chosen at natural boundaries (minutes, hours,
+    /// days, months, years) to produce no more than 10 intervals.
+    pub fn new(min: f64, max: f64) -> Self {
+        let span = (max - min).max(1.0);
+        let (unit, count) = INTERVALS
+            .iter()
+            .find(|(unit, count)| {
+                span / (unit.seconds() * count) as f64 <= 10.0
+            })
+            .copied()
+            .unwrap_or((Unit::Year, 100));
+        let mut scale = Temporal {
+            min,
+            max,
+            start: 0,
+            stop: 0,
+            unit,
+            count,
+            inverted: false,
+            format: None,
+            boundaries: false,
         };
+        scale.start = scale.floor(min as i64);
+        scale.stop = scale.start;
+        while (scale.stop as f64) < max || scale.stop == scale.start {
+            scale.stop = scale.step(scale.stop);
+        }
+        scale
+    }
+
+    /// Round a timestamp down to an interval boundary
+    fn floor(&self, ts: i64) -> i64 {
+        match self.unit {
+            Unit::Month => {
+                let dt = DateTime::from_timestamp(ts);
+                let months = dt.year * 12 + i64::from(dt.month) - 1;
+                let months = months - months.rem_euclid(self.count);
+                let month = (months.rem_euclid(12) + 1) as u32;
+                DateTime::from_date(months.div_euclid(12), month, 1).timestamp()
+            }
+            Unit::Year => {
+                let dt = DateTime::from_timestamp(ts);
+                let year = dt.year - dt.year.rem_euclid(self.count);
+                DateTime::from_date(year, 1, 1).timestamp()
+            }
+            _ => {
+                let step = self.unit.seconds() * self.count;
+                ts.div_euclid(step) * step
+            }
+        }
+    }
+
+    /// Step a timestamp forward by one interval
+    fn step(&self, ts: i64) -> i64 {
+        match self.unit {
+            Unit::Month => {
+                let dt = DateTime::from_timestamp(ts);
+                let months = dt.year * 12 + i64::from(dt.month) - 1;
+                let months = months + self.count;
+                let month = (months.rem_euclid(12) + 1) as u32;
+                DateTime::from_date(months.div_euclid(12), month, 1).timestamp()
+            }
+            Unit::Year => {
+                let dt = DateTime::from_timestamp(ts);
+                DateTime::from_date(dt.year + self.count, 1, 1).timestamp()
+            }
+            _ => ts + self.unit.seconds() * self.count,
+        }
+    }
+
+    /// Create a union with another scale
+    fn union(&self, rhs: Self) -> Self {
+        let mut scale =
+            Temporal::new(self.min.min(rhs.min), self.max.max(rhs.max));
+        scale.inverted = self.inverted;
+        scale.format = self.format.clone();
+        scale.boundaries = self.boundaries;
+        scale
+    }
+
+    /// Create inverted scale
+    fn inverted(&self) -> Self {
+        let mut scale = self.clone();
+        scale.inverted = !self.inverted;
+        scale
+    }
+
+    /// Normalize a value
+    fn normalize(&self, value: f64) -> f64 {
+        let a = self.start as f64;
+        let b = self.stop as f64;
+        if self.inverted {
+            (b - value) / (b - a)
+        } else {
+            (value - a) / (b - a)
+        }
+    }
+
+    /// Create a `Vec` of ticks
+    pub fn ticks(&self) -> Vec<Tick> {
+        let format = self.format.as_deref().unwrap_or(self.unit.format());
+        let mut ticks = vec![];
+        let mut ts = self.start;
+        while ts <= self.stop {
+            let dt = DateTime::from_timestamp(ts);
+            let mut tick =
+                Tick::new(self.normalize(ts as f64), dt.format(format));
+            if self.boundaries {
+                if let Some(fmt) = self.unit.boundary_format(&dt) {
+                    tick = tick.with_sub_text(dt.format(fmt));
+                }
+            }
+            ticks.push(tick);
+            ts = self.step(ts);
+        }
+        if self.inverted {
+            ticks.reverse();
+        }
+        ticks
+    }
+}
+
+impl Categorical {
+    /// Create a new categorical scale
+    pub fn new<S>(categories: &[S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let categories =
+            categories.iter().map(|c| c.as_ref().to_string()).collect();
+        Categorical {
+            categories,
+            inverted: false,
+        }
+    }
+
+    /// Get the index of a category
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.categories.iter().position(|c| c == name)
+    }
+
+    /// Extend to cover an index, appending categories named by index
+    ///
+    /// Indices more than `MAX_APPENDED` past the end are ignored.
+    fn covering(&self, index: f64) -> Self {
+        let mut scale = self.clone();
+        let len = scale.categories.len();
+        if index >= 0.0 && index < (len + MAX_APPENDED) as f64 {
+            for i in len..=index.round() as usize {
+                scale.categories.push(i.to_string());
+            }
+        }
+        scale
+    }
+
+    /// Create a union with another scale
+    ///
+    /// Unknown categories are appended.
+    fn union(&self, rhs: Self) -> Self {
+        let mut scale = self.clone();
+        for cat in rhs.categories {
+            if scale.index(&cat).is_none() {
+                scale.categories.push(cat);
+            }
+        }
+        scale
+    }
+
+    /// Create inverted scale
+    fn inverted(&self) -> Self {
+        let mut scale = self.clone();
+        scale.inverted = !self.inverted;
+        scale
+    }
+
+    /// Normalize a value (category index)
+    ///
+    /// The value is mapped to the center of its band.
+    fn normalize(&self, value: f64) -> f64 {
+        let n = self.categories.len() as f64;
+        if n > 0.0 {
+            let norm = (value + 0.5) / n;
+            if self.inverted {
+                1.0 - norm
+            } else {
+                norm
+            }
+        } else {
+            0.5
+        }
+    }
+
+    /// Create a `Vec` of ticks
+    pub fn ticks(&self) -> Vec<Tick> {
+        let mut ticks: Vec<Tick> = self
+            .categories
+            .iter()
+            .enumerate()
+            .map(|(i, cat)| Tick::new(self.normalize(i as f64), cat.as_str()))
+            .collect();
+        if self.inverted {
+            ticks.reverse();
+        }
         ticks
     }
 }
@@ -121,17 +670,19 @@ impl Default for Scale {
 
 impl Scale {
     /// Create a scale from data points
-    pub fn from_data<'a, I, P>(data: I, get: fn(Point) -> f32) -> Self
+    pub fn from_data<'a, I, P>(data: I, get: fn(Point) -> f64) -> Self
     where
         I: IntoIterator<Item = &'a P>,
         P: IntoPoint + 'a,
     {
-        let mut it = data.into_iter();
-        if let Some(pt) = it.next() {
-            let mut min = get((*pt).into());
+        let mut it = data
+            .into_iter()
+            .map(|pt| get((*pt).into()))
+            .filter(|x| !x.is_nan());
+        if let Some(x) = it.next() {
+            let mut min = x;
             let mut max = min;
-            for pt in it {
-                let x = get((*pt).into());
+            for x in it {
                 if x < min {
                     min = x;
                 }
@@ -145,12 +696,191 @@ impl Scale {
         }
     }
 
+    /// Create a scale from a range of values
+    ///
+    /// A zero-span range is expanded, as with a single data point.
+    pub fn from_range(start: f64, end: f64) -> Self {
+        Scale::Numeric(Numeric::new(start.min(end), start.max(end)))
+    }
+
+    /// Create a scale from an exact range, not rounded out to ticks
+    #[cfg(feature = "serde")]
+    pub fn exact(start: f64, end: f64, step: Option<f64>) -> Self {
+        Scale::Numeric(Numeric::exact(start.min(end), start.max(end), step))
+    }
+
+    /// Get the data range (min, max) of the scale
+    fn range(&self) -> (f64, f64) {
+        match self {
+            Scale::Numeric(num) => (num.min, num.max),
+            Scale::Log(log) => (10_f64.powi(log.start), 10_f64.powi(log.stop)),
+            Scale::Temporal(tmp) => (tmp.min, tmp.max),
+            Scale::Categorical(cat) => {
+                (0.0, cat.categories.len().saturating_sub(1) as f64)
+            }
+        }
+    }
+
+    /// Adjust tick spacing to approximately `count` intervals
+    ///
+    /// Only numeric scales are affected.
+    pub fn with_ticks(&self, count: usize) -> Self {
+        match self {
+            Scale::Numeric(num) => {
+                let scale = Numeric::with_ticks(num.min, num.max, count);
+                if num.tick_spacing < 0.0 {
+                    Scale::Numeric(scale.inverted())
+                } else {
+                    Scale::Numeric(scale)
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Set explicit tick spacing
+    ///
+    /// Only numeric scales are affected.
+    pub fn with_step(&self, step: f64) -> Self {
+        match self {
+            Scale::Numeric(num) => {
+                let scale = Numeric::with_step(num.min, num.max, step);
+                if num.tick_spacing < 0.0 {
+                    Scale::Numeric(scale.inverted())
+                } else {
+                    Scale::Numeric(scale)
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Extend a numeric scale to include a value
+    ///
+    /// Tick spacing is recalculated for the new range.
+    pub fn including(&self, value: f64) -> Self {
+        match self {
+            Scale::Numeric(num) => {
+                let min = num.min.min(value);
+                let max = num.max.max(value);
+                let scale = Numeric::new(min, max);
+                if num.tick_spacing < 0.0 {
+                    Scale::Numeric(scale.inverted())
+                } else {
+                    Scale::Numeric(scale)
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Pad a numeric scale by a fraction of the span on both ends
+    pub fn padded(&self, fraction: f64) -> Self {
+        match self {
+            Scale::Numeric(num) => {
+                let scale = Numeric::padded(num.min, num.max, fraction);
+                if num.tick_spacing < 0.0 {
+                    Scale::Numeric(scale.inverted())
+                } else {
+                    Scale::Numeric(scale)
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Adjust tick spacing to be round in percent terms
+    ///
+    /// Spacing below 10% which is not a multiple of 1% (such as 2.5%) is
+    /// doubled.
+    pub fn percent_spacing(&self) -> Self {
+        match self {
+            Scale::Numeric(num) => {
+                let pct = (num.tick_spacing * 100.0).abs();
+                if pct < 10.0 && (pct - pct.round()).abs() > 1e-3 {
+                    self.with_step(num.tick_spacing.abs() * 2.0)
+                } else {
+                    self.clone()
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Get the index of a category
+    pub fn category(&self, name: &str) -> Option<usize> {
+        match self {
+            Scale::Categorical(cat) => cat.index(name),
+            _ => None,
+        }
+    }
+
+    /// Extend a categorical scale to cover an index
+    ///
+    /// Categories past the end are appended, named by index, up to a
+    /// limit.  Other scales are not affected.
+    pub fn covering_index(&self, index: f64) -> Self {
+        match self {
+            Scale::Categorical(cat) => Scale::Categorical(cat.covering(index)),
+            _ => self.clone(),
+        }
+    }
+
+    /// Convert to a temporal scale
+    pub fn temporal(&self) -> Self {
+        let (min, max) = self.range();
+        Scale::Temporal(Temporal::new(min, max))
+    }
+
+    /// Set the tick format of a temporal scale
+    pub fn time_format(&mut self, format: &str) {
+        if let Scale::Temporal(tmp) = self {
+            tmp.format = Some(format.to_string());
+        }
+    }
+
+    /// Add a second line to temporal ticks on larger boundaries
+    pub fn time_boundaries(&mut self) {
+        if let Scale::Temporal(tmp) = self {
+            tmp.boundaries = true;
+        }
+    }
+
+    /// Convert to a logarithmic scale
+    pub fn log(&self) -> Self {
+        let (min, max) = self.range();
+        Scale::Log(Logarithmic::new(min, max))
+    }
+
     /// Create a union with another scale
     pub fn union(&self, rhs: Self) -> Self {
         match (self, rhs) {
             (Scale::Numeric(num), Scale::Numeric(rhs)) => {
                 Scale::Numeric(num.union(rhs))
             }
+            (Scale::Log(log), Scale::Log(rhs)) => Scale::Log(log.union(rhs)),
+            (Scale::Temporal(tmp), Scale::Temporal(rhs)) => {
+                Scale::Temporal(tmp.union(rhs))
+            }
+            (Scale::Categorical(cat), Scale::Categorical(rhs)) => {
+                Scale::Categorical(cat.union(rhs))
+            }
+            (Scale::Numeric(num), rhs) => {
+                let (min, max) = rhs.range();
+                Scale::Numeric(num.union(Numeric::new(min, max)))
+            }
+            (Scale::Log(log), rhs) => {
+                let (min, max) = rhs.range();
+                Scale::Log(log.union(Logarithmic::new(min, max)))
+            }
+            (Scale::Temporal(tmp), rhs) => {
+                let (min, max) = rhs.range();
+                Scale::Temporal(tmp.union(Temporal::new(min, max)))
+            }
+            (Scale::Categorical(cat), rhs) => {
+                let (_min, max) = rhs.range();
+                Scale::Categorical(cat.covering(max))
+            }
         }
     }
 
@@ -158,20 +888,62 @@ impl Scale {
     pub fn inverted(&self) -> Self {
         match self {
             Scale::Numeric(num) => Scale::Numeric(num.inverted()),
+            Scale::Log(log) => Scale::Log(log.inverted()),
+            Scale::Temporal(tmp) => Scale::Temporal(tmp.inverted()),
+            Scale::Categorical(cat) => Scale::Categorical(cat.inverted()),
         }
     }
 
     /// Normalize a value
-    pub fn normalize(&self, value: f32) -> f32 {
+    pub fn normalize(&self, value: f64) -> f64 {
         match self {
             Scale::Numeric(num) => num.normalize(value),
+            Scale::Log(log) => log.normalize(value),
+            Scale::Temporal(tmp) => tmp.normalize(value),
+            Scale::Categorical(cat) => cat.normalize(value),
+        }
+    }
+
+    /// Get the values at the start and stop of the scale
+    pub fn extent(&self) -> (f64, f64) {
+        match self {
+            Scale::Numeric(num) => (num.start, num.stop),
+            Scale::Log(log) => (10_f64.powi(log.start), 10_f64.powi(log.stop)),
+            Scale::Temporal(tmp) => (tmp.start as f64, tmp.stop as f64),
+            Scale::Categorical(cat) => {
+                (-0.5, cat.categories.len() as f64 - 0.5)
+            }
+        }
+    }
+
+    /// Get normalized position of zero, if within the scale
+    pub fn zero(&self) -> Option<f64> {
+        match self {
+            Scale::Numeric(num)
+                if num.start.min(num.stop) <= 0.0
+                    && num.start.max(num.stop) >= 0.0 =>
+            {
+                Some(num.normalize(0.0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get offset text shared by all tick labels
+    pub fn offset_text(&self, fmt: &NumFormat) -> Option<String> {
+        match self {
+            Scale::Numeric(num) => num.offset_text(fmt),
+            _ => None,
         }
     }
 
     /// Create a `Vec` of ticks
-    pub fn ticks(&self) -> Vec<Tick> {
+    pub fn ticks(&self, fmt: &NumFormat) -> Vec<Tick> {
         match self {
-            Scale::Numeric(num) => num.ticks(),
+            Scale::Numeric(num) => num.ticks(fmt),
+            Scale::Log(log) => log.ticks(fmt),
+            Scale::Temporal(tmp) => tmp.ticks(),
+            Scale::Categorical(cat) => cat.ticks(),
         }
     }
 }
@@ -197,4 +969,159 @@ mod tests {
         assert_eq!(Numeric::new(0.0, 0.1).tick_spacing, 0.01);
         assert_eq!(Numeric::new(0.0, 0.1).tick_spacing, 0.01);
     }
+
+    #[test]
+    fn tick_count() {
+        assert_eq!(Numeric::with_ticks(0.0, 10.0, 5).tick_spacing, 2.0);
+        assert_eq!(Numeric::with_ticks(0.0, 10.0, 10).tick_spacing, 1.0);
+        assert_eq!(Numeric::with_ticks(0.0, 100.0, 4).tick_spacing, 25.0);
+        assert_eq!(Numeric::with_ticks(0.0, 100.0, 1).tick_spacing, 50.0);
+        let num = Numeric::with_ticks(9.5, 10.0, 5);
+        assert_eq!(num.tick_spacing, 0.1);
+        assert_eq!((num.start, num.stop), (9.5, 10.0));
+        assert_eq!(num.ticks(&NumFormat::default()).len(), 6);
+        let num = Numeric::with_ticks(9.5, 10.0, 2);
+        assert_eq!(num.tick_spacing, 0.25);
+        assert_eq!(num.ticks(&NumFormat::default()).len(), 3);
+    }
+
+    #[test]
+    fn tick_step() {
+        let num = Numeric::with_step(3.0, 62.0, 15.0);
+        assert_eq!((num.start, num.stop), (0.0, 75.0));
+        assert_eq!(num.ticks(&NumFormat::default()).len(), 6);
+        let num = Numeric::with_step(0.0, 1.0, 0.5);
+        assert_eq!(num.inverted().ticks(&NumFormat::default()).len(), 3);
+        let num = Numeric::with_step(0.0, 1.0e6, 0.001);
+        assert_eq!(num.tick_spacing, 1000.0);
+        let num = Numeric::with_step(0.0, 10.0, -1.0);
+        assert_eq!(num.tick_spacing, 1.0);
+    }
+
+    #[test]
+    fn percent() {
+        let scale = Scale::Numeric(Numeric::new(0.0, 0.2)).percent_spacing();
+        let Scale::Numeric(num) = scale else { panic!() };
+        assert_eq!(num.tick_spacing, 0.05);
+        let scale = Scale::Numeric(Numeric::new(0.0, 1.0)).percent_spacing();
+        let Scale::Numeric(num) = scale else { panic!() };
+        assert_eq!(num.tick_spacing, 0.1);
+    }
+
+    #[test]
+    fn including() {
+        let scale = Scale::Numeric(Numeric::new(95.0, 100.0)).including(0.0);
+        let Scale::Numeric(num) = scale else { panic!() };
+        assert_eq!((num.start, num.stop), (0.0, 100.0));
+        assert_eq!(num.tick_spacing, 10.0);
+    }
+
+    #[test]
+    fn padded() {
+        let num = Numeric::padded(0.0, 100.0, 0.05);
+        assert_eq!((num.start, num.stop), (-5.0, 105.0));
+        let ticks = num.ticks(&NumFormat::default());
+        assert_eq!(ticks.first().unwrap().text(), "0");
+        assert_eq!(ticks.last().unwrap().text(), "100");
+        let num = Numeric::padded(5.0, 5.0, 0.05);
+        assert!(num.stop > num.start);
+        let num = Numeric::padded(0.0, 0.0, 0.05);
+        assert_eq!((num.start, num.stop), (-1.0, 1.0));
+    }
+
+    #[test]
+    fn drift() {
+        let texts = |num: Numeric| -> Vec<String> {
+            let ticks = num.ticks(&NumFormat::default());
+            ticks.iter().map(|t| t.text().to_string()).collect()
+        };
+        let ticks = texts(Numeric::new(0.0, 0.7));
+        assert_eq!(ticks.len(), 8);
+        assert_eq!(ticks[3], "0.3");
+        assert_eq!(ticks[7], "0.7");
+        let ticks = texts(Numeric::new(0.0, 0.3));
+        assert_eq!(ticks.last().unwrap(), "0.30");
+        assert!(ticks.iter().all(|t| t.len() == 4));
+        let ticks = texts(Numeric::new(1.0e6, 1.0e6 + 7.0));
+        assert_eq!(ticks.len(), 8);
+        assert_eq!(ticks[7], "1000007");
+        let ticks = texts(Numeric::new(0.0, 0.7).inverted());
+        assert_eq!(ticks[0], "0.7");
+        assert_eq!(ticks[7], "0.0");
+    }
+
+    #[test]
+    fn zero_span() {
+        let texts = |scale: Scale| -> Vec<String> {
+            let ticks = scale.ticks(&NumFormat::default());
+            ticks.iter().map(|t| t.text().to_string()).collect()
+        };
+        let scale = Scale::from_data(&[(3.0, 50.0)], |pt| pt.y);
+        let ticks = texts(scale.clone());
+        assert_eq!(ticks.first().unwrap(), "45.0");
+        assert_eq!(ticks.last().unwrap(), "55.0");
+        assert_eq!(scale.normalize(50.0), 0.5);
+        let data = [(1.0, 7.0), (2.0, 7.0), (3.0, 7.0)];
+        let ticks = texts(Scale::from_data(&data, |pt| pt.y));
+        assert!(ticks.len() > 2);
+        let ticks = texts(Scale::from_data(&[(0.0, 0.0)], |pt| pt.y));
+        assert_eq!(ticks.first().unwrap(), "-1.00");
+        assert_eq!(ticks.last().unwrap(), "1.00");
+        assert!(ticks.contains(&"0.00".to_string()));
+    }
+
+    #[test]
+    fn missing() {
+        let data = [(0.0, 5.0), (1.0, f64::NAN), (10.0, 2.0)];
+        let scale = Scale::from_data(&data, |pt| pt.y);
+        assert_eq!(scale.range(), (2.0, 5.0));
+    }
+
+    #[test]
+    fn log() {
+        let log = Logarithmic::new(3.0, 5000.0);
+        assert_eq!(log.start, 0);
+        assert_eq!(log.stop, 4);
+        assert_eq!(log.normalize(100.0), 0.5);
+        assert_eq!(log.normalize(0.0), 0.0);
+        assert_eq!(log.normalize(-5.0), 0.0);
+        let ticks = log.ticks(&NumFormat::default());
+        assert_eq!(ticks.len(), 13);
+        assert_eq!(ticks[0].text(), "1");
+        assert_eq!(ticks[3].text(), "10");
+        assert_eq!(ticks[12].text(), "10000");
+        let log = Logarithmic::new(0.0, 100.0);
+        assert_eq!(log.start, -1);
+    }
+
+    #[test]
+    fn temporal() {
+        // 90 minutes starting at 2024-06-03 08:00
+        let tmp = Temporal::new(1_717_401_600.0, 1_717_407_000.0);
+        assert_eq!(tmp.unit, Unit::Minute);
+        assert_eq!(tmp.count, 15);
+        let ticks = tmp.ticks();
+        assert_eq!(ticks[0].text(), "08:00");
+        assert_eq!(ticks[1].text(), "08:15");
+        // about 3 months
+        let tmp = Temporal::new(1_704_067_200.0, 1_711_929_600.0);
+        let ticks = tmp.ticks();
+        assert_eq!(ticks[0].text(), "Jan 2024");
+        assert_eq!(ticks[1].text(), "Feb 2024");
+    }
+
+    #[test]
+    fn boundaries() {
+        // 2024-06-03 08:00 until the next morning
+        let mut scale =
+            Scale::Temporal(Temporal::new(1_717_401_600.0, 1_717_480_000.0));
+        let ticks = scale.ticks(&NumFormat::default());
+        assert!(ticks.iter().all(|t| t.sub_text().is_none()));
+        scale.time_boundaries();
+        let ticks = scale.ticks(&NumFormat::default());
+        let subs: Vec<_> = ticks.iter().filter_map(|t| t.sub_text()).collect();
+        assert_eq!(subs, ["Jun 4"]);
+        let tick = ticks.iter().find(|t| t.sub_text().is_some()).unwrap();
+        assert_eq!(tick.text(), "00:00");
+    }
 }
@@ -0,0 +1,227 @@
+// spec.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Serializable chart specifications
+//!
+use crate::chart::Chart;
+use crate::domain::Domain;
+use crate::error::ChartError;
+use crate::plot::Plot;
+use crate::point::Point;
+use crate::rect::Edge;
+use crate::scale::Scale;
+use serde::{Deserialize, Serialize};
+
+/// Scale range of one axis
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScaleSpec {
+    /// Start value
+    pub start: f64,
+    /// Stop value
+    pub stop: f64,
+    /// Tick spacing, if not automatic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<f64>,
+}
+
+/// Domain of `X` and `Y` scales
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DomainSpec {
+    /// `X` scale
+    pub x: ScaleSpec,
+    /// `Y` scale
+    pub y: ScaleSpec,
+}
+
+/// Axis name and edge
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AxisSpec {
+    /// Axis name
+    pub name: String,
+    /// Chart edge
+    pub edge: Edge,
+}
+
+/// Type of plot
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlotKind {
+    /// Stacked area plot
+    Area,
+    /// Line plot
+    Line,
+    /// Scatter plot
+    Scatter,
+}
+
+/// Plot with inline data
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlotSpec {
+    /// Type of plot
+    pub kind: PlotKind,
+    /// Plot name
+    pub name: String,
+    /// Data points
+    pub data: Vec<Point>,
+}
+
+/// Chart specification
+///
+/// A spec can be converted into a `Chart` with `try_from`.  If no domain is
+/// given, it is computed from the data of all plots.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChartSpec {
+    /// Chart titles
+    #[serde(default)]
+    pub titles: Vec<String>,
+    /// Chart domain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<DomainSpec>,
+    /// Chart axes
+    #[serde(default)]
+    pub axes: Vec<AxisSpec>,
+    /// Chart plots
+    #[serde(default)]
+    pub plots: Vec<PlotSpec>,
+}
+
+impl ScaleSpec {
+    /// Check whether all values are finite
+    fn is_finite(&self) -> bool {
+        self.start.is_finite()
+            && self.stop.is_finite()
+            && self.spacing.is_none_or(f64::is_finite)
+    }
+}
+
+impl From<ScaleSpec> for Scale {
+    fn from(spec: ScaleSpec) -> Self {
+        Scale::exact(spec.start, spec.stop, spec.spacing)
+    }
+}
+
+impl From<DomainSpec> for Domain {
+    fn from(spec: DomainSpec) -> Self {
+        Domain::from_scales(spec.x.into(), spec.y.into())
+    }
+}
+
+impl From<PlotSpec> for Plot<'static> {
+    fn from(spec: PlotSpec) -> Self {
+        match spec.kind {
+            PlotKind::Area => Plot::area_owned(spec.name, spec.data),
+            PlotKind::Line => Plot::line_owned(spec.name, spec.data),
+            PlotKind::Scatter => Plot::scatter_owned(spec.name, spec.data),
+        }
+    }
+}
+
+impl TryFrom<ChartSpec> for Chart<'static> {
+    type Error = ChartError;
+
+    fn try_from(spec: ChartSpec) -> Result<Self, Self::Error> {
+        let domain = match spec.domain {
+            Some(domain) if !domain.x.is_finite() || !domain.y.is_finite() => {
+                return Err(ChartError::NonFiniteDomain);
+            }
+            Some(domain) => Domain::from(domain),
+            None if spec.plots.iter().all(|p| p.data.is_empty()) => {
+                return Err(ChartError::NoData);
+            }
+            None => Domain::from_data(
+                spec.plots.iter().flat_map(|p| p.data.iter().copied()),
+            ),
+        };
+        let mut chart = Chart::new();
+        for title in spec.titles {
+            chart = chart.title(title);
+        }
+        chart = chart.domain(domain);
+        for axis in spec.axes {
+            chart = chart.axis(axis.name, axis.edge);
+        }
+        for plot in spec.plots {
+            chart = chart.plot(plot.into());
+        }
+        Ok(chart)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rect::Rect;
+
+    #[test]
+    fn round_trip() {
+        let data = vec![
+            Point { x: 0.0, y: 1.0 },
+            Point { x: 1.0, y: 3.0 },
+            Point { x: 2.0, y: 2.0 },
+        ];
+        let spec = ChartSpec {
+            titles: vec!["Spec".to_string()],
+            domain: None,
+            axes: vec![AxisSpec {
+                name: "X".to_string(),
+                edge: Edge::Bottom,
+            }],
+            plots: vec![PlotSpec {
+                kind: PlotKind::Line,
+                name: "A".to_string(),
+                data: data.clone(),
+            }],
+        };
+        let json = serde_json::to_string(&spec).unwrap();
+        let spec: ChartSpec = serde_json::from_str(&json).unwrap();
+        let chart = Chart::try_from(spec).unwrap();
+        let code = Chart::new()
+            .title("Spec")
+            .domain(&data[..])
+            .axis("X", Edge::Bottom)
+            .plot(Plot::line("A", &data));
+        assert_eq!(chart.to_string(), code.to_string());
+    }
+
+    #[test]
+    fn domain() {
+        let json = r#"{"x":{"start":0,"stop":10,"spacing":5},
+            "y":{"start":-1,"stop":1}}"#;
+        let spec: DomainSpec = serde_json::from_str(json).unwrap();
+        let svg = Chart::new().domain(spec).axis("", Edge::Bottom).to_string();
+        assert_eq!(svg.matches("<tspan").count(), 3);
+        assert!(svg.contains(">5</tspan>"));
+    }
+
+    #[test]
+    fn exact_domain() {
+        let json = r#"{"x":{"start":0.3,"stop":9.7},
+            "y":{"start":-1,"stop":1}}"#;
+        let spec: DomainSpec = serde_json::from_str(json).unwrap();
+        let domain = Domain::from(spec).bind(Rect::new(0, 0, 1000, 1000));
+        assert_eq!(domain.x_extent(), (0.3, 9.7));
+        assert_eq!(domain.x_pos(0.3), 0.0);
+        assert_eq!(domain.x_pos(9.7), 1000.0);
+    }
+
+    #[test]
+    fn invalid() {
+        let spec = ChartSpec::default();
+        assert_eq!(Chart::try_from(spec).err(), Some(ChartError::NoData));
+        let scale = |stop| ScaleSpec {
+            start: 0.0,
+            stop,
+            spacing: None,
+        };
+        let spec = ChartSpec {
+            domain: Some(DomainSpec {
+                x: scale(f64::INFINITY),
+                y: scale(1.0),
+            }),
+            ..Default::default()
+        };
+        let err = Chart::try_from(spec).err();
+        assert_eq!(err, Some(ChartError::NonFiniteDomain));
+    }
+}
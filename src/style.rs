@@ -0,0 +1,326 @@
+// style.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Stylesheets for charts and pages
+//!
+use crate::text::escape;
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// Default splot stylesheet
+pub const DEFAULT_CSS: &str = include_str!("../css/splot.css");
+
+/// Default stylesheet href
+const SPLOT_HREF: &str = "./css/splot.css";
+
+/// Write the default stylesheet to a file
+///
+/// ```rust,no_run
+/// splot::write_css("./css/splot.css").unwrap();
+/// ```
+pub fn write_css<P>(path: P) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    std::fs::write(path, DEFAULT_CSS)
+}
+
+/// Start of a class attribute
+const CLASS_ATTR: &[u8] = b"class='";
+
+/// Writer adding a prefix to every class in `class` attributes
+pub(crate) struct ClassPrefix<'w, W: fmt::Write> {
+    writer: &'w mut W,
+    prefix: &'w str,
+    /// Number of bytes of `CLASS_ATTR` matched
+    matched: usize,
+    /// Within a class attribute
+    in_class: bool,
+    /// At the start of a class name
+    class_start: bool,
+}
+
+impl<'w, W: fmt::Write> ClassPrefix<'w, W> {
+    /// Create a new class prefix writer
+    pub fn new(writer: &'w mut W, prefix: &'w str) -> Self {
+        ClassPrefix {
+            writer,
+            prefix,
+            matched: 0,
+            in_class: false,
+            class_start: false,
+        }
+    }
+}
+
+impl<'w, W: fmt::Write> fmt::Write for ClassPrefix<'w, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = 0;
+        for (i, b) in s.bytes().enumerate() {
+            if self.in_class {
+                match b {
+                    b'\'' => self.in_class = false,
+                    b' ' => self.class_start = true,
+                    _ if self.class_start => {
+                        self.writer.write_str(&s[start..i])?;
+                        self.writer.write_str(self.prefix)?;
+                        start = i;
+                        self.class_start = false;
+                    }
+                    _ => (),
+                }
+            } else if b == CLASS_ATTR[self.matched] {
+                self.matched += 1;
+                if self.matched == CLASS_ATTR.len() {
+                    self.matched = 0;
+                    self.in_class = true;
+                    self.class_start = true;
+                }
+            } else {
+                self.matched = usize::from(b == CLASS_ATTR[0]);
+            }
+        }
+        self.writer.write_str(&s[start..])
+    }
+}
+
+/// Add a prefix to every class selector in CSS text
+pub(crate) fn prefix_css(css: &str, prefix: &str) -> String {
+    let mut out = String::with_capacity(css.len() * 2);
+    let mut prev = ' ';
+    for c in css.chars() {
+        // class names start with a letter; numbers contain digits
+        if prev == '.' && c.is_ascii_alphabetic() {
+            out.push_str(prefix);
+        }
+        out.push(c);
+        prev = c;
+    }
+    out
+}
+
+/// Get inline presentational attributes for a class
+///
+/// These match the default (light) stylesheet, for SVG consumers which
+/// ignore CSS.
+pub(crate) fn inline_attrs(class_name: &str) -> &'static str {
+    match class_name {
+        "title" => " fill='#333' font-size='50' font-family='sans-serif'",
+        "subtitle" => " fill='#333' font-size='32' font-family='sans-serif'",
+        "axis" => " fill='#333' font-size='40' font-family='sans-serif'",
+        "tick" | "plot-label" => {
+            " fill='#333' font-size='32' font-family='sans-serif'"
+        }
+        "axis-line" | "axis-tick" => {
+            " fill='none' stroke='#333' stroke-width='1' \
+            vector-effect='non-scaling-stroke'"
+        }
+        "grid-x" | "grid-y" => {
+            " fill='none' stroke='gray' stroke-opacity='0.5' stroke-width='1' \
+            stroke-dasharray='1 3' vector-effect='non-scaling-stroke'"
+        }
+        "grid-zero" => {
+            " fill='none' stroke='#333' stroke-opacity='0.75' \
+            stroke-width='2' vector-effect='non-scaling-stroke'"
+        }
+        "chart-bg" => " fill='#eee'",
+        "plot-bg" => " fill='#333' fill-opacity='0.05'",
+        "annotation" => " fill='#333' font-size='32' font-family='sans-serif'",
+        "annotation-leader" => {
+            " fill='none' stroke='#333' stroke-width='1' \
+            vector-effect='non-scaling-stroke'"
+        }
+        "band" => " fill='#333' fill-opacity='0.1'",
+        "band-label" => " fill='#333' font-size='32' font-family='sans-serif'",
+        "plot-point" | "crosshair-target" => {
+            " fill='transparent' stroke='none'"
+        }
+        "crosshair" => {
+            " fill='none' stroke='#333' stroke-width='1' visibility='hidden' \
+            vector-effect='non-scaling-stroke'"
+        }
+        "crosshair-label" => {
+            " fill='#333' font-size='32' font-family='sans-serif' \
+            visibility='hidden'"
+        }
+        _ => "",
+    }
+}
+
+/// Stylesheet source
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Stylesheet<'a> {
+    /// Link to a stylesheet href (empty for no link)
+    Link(&'a str),
+    /// Embedded CSS text
+    Embed(Cow<'a, str>),
+}
+
+impl<'a> Default for Stylesheet<'a> {
+    fn default() -> Self {
+        Stylesheet::Link(SPLOT_HREF)
+    }
+}
+
+impl<'a> Stylesheet<'a> {
+    /// Get the link href, if it is a relative path
+    ///
+    /// Paths with `..` components are rejected, since they could lead
+    /// outside of the output directory.
+    pub fn relative_href(&self) -> Option<&'a str> {
+        match self {
+            Stylesheet::Link(href)
+                if !href.is_empty()
+                    && !href.starts_with('/')
+                    && !href.contains(':')
+                    && !href.split(['/', '\\']).any(|c| c == "..") =>
+            {
+                Some(href)
+            }
+            _ => None,
+        }
+    }
+
+    /// Render for an SVG element
+    pub fn svg_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stylesheet::Link("") => Ok(()),
+            Stylesheet::Link(href) => {
+                write!(f, "<link")?;
+                write!(f, " xmlns='http://www.w3.org/1999/xhtml'")?;
+                write!(f, " type='text/css'")?;
+                write!(f, " rel='stylesheet'")?;
+                writeln!(f, " href='{}' />", escape(href))
+            }
+            Stylesheet::Embed(css) => {
+                writeln!(f, "<style><![CDATA[")?;
+                // split any end marker, so it can't close the section early
+                for (i, part) in css.split("]]>").enumerate() {
+                    if i > 0 {
+                        f.write_str("]]]]><![CDATA[>")?;
+                    }
+                    f.write_str(part)?;
+                }
+                writeln!(f, "\n]]></style>")
+            }
+        }
+    }
+
+    /// Render for an HTML head element
+    pub fn html_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stylesheet::Link("") => Ok(()),
+            Stylesheet::Link(href) => {
+                let href = escape(href);
+                writeln!(f, "<link href='{href}' rel='stylesheet'/>")
+            }
+            Stylesheet::Embed(css) => {
+                writeln!(f, "<style>")?;
+                writeln!(f, "{css}")?;
+                writeln!(f, "</style>")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stylesheet;
+    use crate::{Chart, Page, Theme};
+
+    #[test]
+    fn embed() {
+        let svg = Chart::new().to_string();
+        assert!(svg.contains("href='./css/splot.css'"));
+        let svg = Chart::new().embed_css().to_string();
+        assert!(!svg.contains("<link"));
+        assert!(svg.contains(".grid-x {"));
+        let svg = Chart::new().css(".tick {}").to_string();
+        assert!(svg.contains("<style><![CDATA[\n.tick {}\n]]></style>"));
+        let svg = Chart::new().css("a[b]]>c {}").to_string();
+        assert!(svg.contains("<![CDATA[\na[b]]]]><![CDATA[>c {}\n]]></style>"));
+        let html = Page::new().embed_css().to_string();
+        assert!(!html.contains("<link"));
+        assert!(html.contains("<style>\n@media"));
+    }
+
+    #[test]
+    fn inline() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = Chart::new()
+            .title("Title")
+            .axis("X", crate::Edge::Bottom)
+            .plot(crate::Plot::line("A", &data[..]).label());
+        let plain = chart.to_string();
+        assert!(!plain.contains("font-size='"));
+        let svg = Chart::new()
+            .inline_styles()
+            .title("Title")
+            .axis("X", crate::Edge::Bottom)
+            .plot(crate::Plot::line("A", &data[..]).label())
+            .to_string();
+        assert!(svg.contains("class='title' fill='#333' font-size='50'"));
+        assert!(svg.contains("class='axis-line' fill='none' stroke='#333'"));
+        assert!(svg.contains("class='grid-x' fill='none' stroke='gray'"));
+        assert!(svg.contains("class='plot-label' fill='#333'"));
+        assert!(svg.contains("class='plot-0 plot-line' fill='none'"));
+        assert!(svg.contains(" stroke='#4E79A7'"));
+        assert!(
+            svg.contains("<marker id='marker-0' class='plot-0' fill='#4E79A7'")
+        );
+        assert_eq!(svg.matches("marker-mid=").count(), 2);
+    }
+
+    #[test]
+    fn theme() {
+        let html = Page::new().theme(Theme::dark()).to_string();
+        assert!(!html.contains("<link"));
+        assert!(html.contains("--bg: #333;\n  --fg: #eee;\n}"));
+    }
+
+    #[test]
+    fn class_prefix() {
+        use super::{prefix_css, ClassPrefix};
+        use std::fmt::Write;
+        let mut out = String::new();
+        let mut w = ClassPrefix::new(&mut out, "s-");
+        write!(w, "<path clas").unwrap();
+        write!(w, "s='plot-0 plot-line' d='M0 0'/><text class='tick'").unwrap();
+        write!(w, ">x</text>").unwrap();
+        assert_eq!(
+            out,
+            "<path class='s-plot-0 s-plot-line' d='M0 0'/>\
+            <text class='s-tick'>x</text>"
+        );
+        let css = prefix_css(".a:hover > .b-c {\n  opacity: 0.5;\n}", "s-");
+        assert_eq!(css, ".s-a:hover > .s-b-c {\n  opacity: 0.5;\n}");
+    }
+
+    #[test]
+    fn href() {
+        let href = "/static/charts/splot.css";
+        let svg = Chart::new().css_href(href).to_string();
+        assert!(svg.contains("href='/static/charts/splot.css'"));
+        let html = Page::new().css_href(href).to_string();
+        assert!(html.contains("<link href='/static/charts/splot.css'"));
+        let svg = Chart::new().css_href("").to_string();
+        assert!(!svg.contains("<link"));
+        let html = Page::new().css_href("").to_string();
+        assert!(!html.contains("<link"));
+        let relative = |href| Stylesheet::Link(href).relative_href();
+        assert_eq!(relative("./css/splot.css"), Some("./css/splot.css"));
+        assert_eq!(relative("css/..splot.css"), Some("css/..splot.css"));
+        assert_eq!(relative("../splot.css"), None);
+        assert_eq!(relative("css/../../splot.css"), None);
+        assert_eq!(relative("css\\..\\splot.css"), None);
+        assert_eq!(relative("https://example.com/splot.css"), None);
+        let href = "/css?a=1&b='2'";
+        let svg = Chart::new().css_href(href).to_string();
+        assert!(svg.contains("href='/css?a=1&amp;b=&apos;2&apos;' />"));
+        let html = Page::new().css_href(href).to_string();
+        assert!(html.contains("href='/css?a=1&amp;b=&apos;2&apos;' rel="));
+    }
+}
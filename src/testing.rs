@@ -0,0 +1,140 @@
+// testing.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Helpers for snapshot testing chart output
+//!
+
+/// Normalize SVG or HTML output for comparison in snapshot tests
+///
+/// - Attributes of each element are sorted by name
+/// - Unique ID prefixes (from [Chart::unique_ids]) are removed
+///
+/// Text content is left unchanged.
+///
+/// [Chart::unique_ids]: crate::Chart::unique_ids
+pub fn normalize_svg(svg: &str) -> String {
+    let mut out = String::with_capacity(svg.len());
+    let mut rest = svg;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").map_or(cdata.len(), |e| e + 3);
+            out.push_str("<![CDATA[");
+            out.push_str(&cdata[..end]);
+            rest = &cdata[end..];
+            continue;
+        }
+        match parse_tag(&rest[1..]) {
+            Some((tag, len)) => {
+                out.push_str(&tag);
+                rest = &rest[1 + len..];
+            }
+            None => {
+                out.push('<');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Parse an element start tag, after the `<`
+///
+/// Returns the normalized tag and the number of bytes consumed.
+fn parse_tag(text: &str) -> Option<(String, usize)> {
+    if !text.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name_len = text
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(text.len());
+    let name = &text[..name_len];
+    let mut attrs = Vec::new();
+    let mut pos = name_len;
+    loop {
+        let tail = &text[pos..];
+        let trimmed = tail.trim_start();
+        pos += tail.len() - trimmed.len();
+        if let Some(end) = ["/>", ">"].iter().find(|e| trimmed.starts_with(*e))
+        {
+            attrs.sort();
+            let mut tag = format!("<{name}");
+            for (attr, quote, value) in attrs {
+                let value = strip_unique(value);
+                tag.push_str(&format!(" {attr}={quote}{value}{quote}"));
+            }
+            tag.push_str(end);
+            return Some((tag, pos + end.len()));
+        }
+        let eq = trimmed.find('=')?;
+        let attr = trimmed[..eq].trim_end();
+        let value = &trimmed[eq + 1..];
+        let quote = value.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+        let len = value[1..].find(quote)?;
+        attrs.push((attr, quote, &value[1..1 + len]));
+        pos += eq + 1 + len + 2;
+    }
+}
+
+/// Remove unique ID prefixes (`splot-{n}-`) from an attribute value
+fn strip_unique(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("splot-") {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start + 6..];
+        let digits = tail.len()
+            - tail.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits > 0 && tail[digits..].starts_with('-') {
+            rest = &tail[digits + 1..];
+        } else {
+            out.push_str("splot-");
+            rest = tail;
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Chart, Edge, Page, Plot};
+
+    #[test]
+    fn sort_attributes() {
+        assert_eq!(
+            normalize_svg("<a y='1' x=\"2\">b<c/></a>"),
+            "<a x=\"2\" y='1'>b<c/></a>"
+        );
+        assert_eq!(normalize_svg("1 < 2"), "1 < 2");
+    }
+
+    #[test]
+    fn unique_ids() {
+        let data = [(0.0, 1.0), (1.0, 2.0)];
+        let chart = || {
+            Chart::new()
+                .axis("X", Edge::Bottom)
+                .plot(Plot::line("A", &data[..]).markers())
+        };
+        let a = chart().unique_ids().to_string();
+        let b = chart().unique_ids().to_string();
+        assert_ne!(a, b);
+        assert_eq!(normalize_svg(&a), normalize_svg(&b));
+        let pinned = chart().id_prefix("t-").to_string();
+        assert!(pinned.contains("<clipPath id='t-clip-chart'>"));
+        assert!(pinned.contains("<marker id='t-marker-0'"));
+        assert!(pinned.contains("--marker: url(#t-marker-0)"));
+        let html = Page::new()
+            .chart(chart())
+            .chart(chart().id_prefix("t-"))
+            .to_string();
+        assert!(html.contains("<clipPath id='clip-chart'>"));
+        assert!(html.contains("<clipPath id='t-clip-chart-1'>"));
+        assert!(html.contains("<marker id='t-marker-1-0'"));
+    }
+}
@@ -2,8 +2,10 @@
 //
 // Copyright (c) 2021-2024  Douglas P Lau
 //
-use crate::point::{IntoPoint, Point};
+use crate::axis::TickStyle;
 use crate::rect::{Edge, Rect};
+use crate::style::inline_attrs;
+use std::borrow::Cow;
 use std::fmt;
 
 /// Vertical offset relative to point
@@ -28,6 +30,74 @@ pub enum Anchor {
     End,
 }
 
+/// Escape text for use in XML content or attribute values
+pub fn escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '\'', '"']) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + 16);
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\'' => out.push_str("&apos;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Get the change in element nesting depth over one line of markup
+fn nesting(line: &str) -> isize {
+    let mut depth = 0;
+    let mut rest = line;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let end = rest.find('>').unwrap_or(rest.len());
+        let tag = &rest[..end];
+        let name = tag.split_whitespace().next().unwrap_or("");
+        if tag.starts_with('/') {
+            depth -= 1;
+        } else if !(tag.starts_with('!')
+            || tag.ends_with('/')
+            || matches!(name, "meta" | "link"))
+        {
+            depth += 1;
+        }
+        rest = &rest[end..];
+    }
+    depth
+}
+
+/// Indent markup by element nesting depth, two spaces per level
+///
+/// Only leading whitespace is added; lines within `<style>` elements are
+/// left unchanged.
+pub(crate) fn indent(markup: &str) -> String {
+    let mut out = String::with_capacity(markup.len() * 2);
+    let mut depth: isize = 0;
+    let mut style = false;
+    for line in markup.split_inclusive('\n') {
+        if style && !line.starts_with("</style>") {
+            style = !line.contains("</style>");
+        } else {
+            if !line.trim().is_empty() {
+                let closing = line.starts_with("</");
+                let level = if closing { depth - 1 } else { depth };
+                for _ in 0..level.max(0) {
+                    out.push_str("  ");
+                }
+            }
+            style = line.starts_with("<style") && !line.contains("</style>");
+        }
+        depth += nesting(line);
+        out.push_str(line);
+    }
+    out
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Label {
     offset: VerticalOffset,
@@ -41,10 +111,13 @@ pub struct Text<'a> {
     rect: Option<Rect>,
     dy: Option<f32>,
     class_name: Option<&'a str>,
+    inline: bool,
 }
 
 pub struct Tspan<'a> {
     text: &'a str,
+    class_name: Option<&'a str>,
+    inline: bool,
     x: Option<i32>,
     y: Option<i32>,
     dy: Option<f32>,
@@ -53,8 +126,9 @@ pub struct Tspan<'a> {
 /// Tick marks for axis labels
 #[derive(Debug, PartialEq)]
 pub struct Tick {
-    value: f32,
+    value: f64,
     text: String,
+    sub_text: Option<String>,
 }
 
 impl fmt::Display for Anchor {
@@ -70,19 +144,22 @@ impl fmt::Display for Anchor {
 impl Default for Label {
     fn default() -> Self {
         Label {
-            offset: VerticalOffset::At,
+            offset: VerticalOffset::Above,
             anchor: Anchor::Middle,
             rounding_precision: None,
         }
     }
 }
 
-#[allow(dead_code)]
 impl Label {
     pub fn new() -> Self {
         Self::default()
     }
 
+    pub fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
     pub fn vertical_offset(&self) -> f32 {
         match self.offset {
             VerticalOffset::Above => -1.0,
@@ -101,6 +178,11 @@ impl Label {
         self
     }
 
+    pub fn at(mut self) -> Self {
+        self.offset = VerticalOffset::At;
+        self
+    }
+
     pub fn start(mut self) -> Self {
         self.anchor = Anchor::Start;
         self
@@ -111,26 +193,32 @@ impl Label {
         self
     }
 
-    pub fn rounded(&self, value: f32) -> String {
+    pub fn precision(mut self, digits: usize) -> Self {
+        self.rounding_precision = Some(digits);
+        self
+    }
+
+    pub fn has_precision(&self) -> bool {
+        self.rounding_precision.is_some()
+    }
+
+    pub fn rounded(&self, value: f64) -> String {
         match self.rounding_precision {
             None => value.to_string(),
             Some(digits) => format!("{:.1$}", value, digits),
         }
     }
 
-    pub fn display<P>(
+    pub fn display(
         &self,
         f: &mut fmt::Formatter,
         x: i32,
         y: i32,
-        pt: P,
-    ) -> fmt::Result
-    where
-        P: IntoPoint,
-    {
-        let pt: Point = pt.into();
-        let lbl = format!("({} {})", pt.x, pt.y);
-        let tspan = Tspan::new(&lbl).x(x).y(y).dy(-0.66);
+        text: &str,
+    ) -> fmt::Result {
+        // centered vertically, then shifted by offset (rounded to 0.01em)
+        let dy = ((0.33 + self.vertical_offset()) * 100.0).round() / 100.0;
+        let tspan = Tspan::new(text).x(x).y(y).dy(dy);
         write!(f, "{tspan}")
     }
 }
@@ -143,6 +231,7 @@ impl<'a> Text<'a> {
             rect: None,
             dy: None,
             class_name: None,
+            inline: false,
         }
     }
 
@@ -167,10 +256,18 @@ impl<'a> Text<'a> {
         self
     }
 
+    pub fn inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
+    }
+
     pub fn display(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "<text")?;
         if let Some(class_name) = self.class_name {
             write!(f, " class='{}'", class_name)?;
+            if self.inline {
+                write!(f, "{}", inline_attrs(class_name))?;
+            }
         }
         if let Some(rect) = self.rect {
             self.transform(f, rect)?;
@@ -215,6 +312,12 @@ impl<'a> Text<'a> {
 impl<'a> fmt::Display for Tspan<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "<tspan")?;
+        if let Some(class_name) = self.class_name {
+            write!(f, " class='{}'", class_name)?;
+            if self.inline {
+                write!(f, "{}", inline_attrs(class_name))?;
+            }
+        }
         if let Some(x) = self.x {
             write!(f, " x='{x}'")?;
         }
@@ -233,12 +336,24 @@ impl<'a> Tspan<'a> {
     pub fn new(text: &'a str) -> Self {
         Tspan {
             text,
+            class_name: None,
+            inline: false,
             x: None,
             y: None,
             dy: None,
         }
     }
 
+    pub fn class_name(mut self, class_name: &'a str) -> Self {
+        self.class_name = Some(class_name);
+        self
+    }
+
+    pub fn inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
+    }
+
     pub fn x(mut self, x: i32) -> Self {
         self.x = Some(x);
         self
@@ -256,41 +371,102 @@ impl<'a> Tspan<'a> {
 }
 
 impl Tick {
-    pub const LEN: i32 = 20;
-    pub const HLEN: i32 = Tick::LEN + 8;
-    pub const VLEN: i32 = Tick::LEN * 2;
-
-    pub fn new<T>(value: f32, text: T) -> Self
+    pub fn new<T>(value: f64, text: T) -> Self
     where
         T: Into<String>,
     {
         let text = text.into();
-        Tick { value, text }
+        Tick {
+            value,
+            text,
+            sub_text: None,
+        }
+    }
+
+    pub fn with_sub_text<T>(mut self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.sub_text = Some(text.into());
+        self
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
     }
 
     pub fn text(&self) -> &str {
         &self.text
     }
 
+    #[cfg(test)]
+    pub fn sub_text(&self) -> Option<&str> {
+        self.sub_text.as_deref()
+    }
+
     pub fn x(&self, edge: Edge, rect: Rect, len: i32) -> i32 {
+        self.x_pos(edge, rect, len).round() as i32
+    }
+
+    pub fn y(&self, edge: Edge, rect: Rect, len: i32) -> i32 {
+        self.y_pos(edge, rect, len).round() as i32
+    }
+
+    /// Get `X` position, without rounding
+    pub fn x_pos(&self, edge: Edge, rect: Rect, len: i32) -> f64 {
         match edge {
-            Edge::Left => rect.right() - len,
-            Edge::Right => rect.x + len,
-            _ => rect.x + (self.value * rect.width as f32).round() as i32,
+            Edge::Left => f64::from(rect.right() - len),
+            Edge::Right => f64::from(rect.x + len),
+            _ => f64::from(rect.x) + self.value * f64::from(rect.width),
         }
     }
 
-    pub fn y(&self, edge: Edge, rect: Rect, len: i32) -> i32 {
+    /// Get `Y` position, without rounding
+    pub fn y_pos(&self, edge: Edge, rect: Rect, len: i32) -> f64 {
         match edge {
-            Edge::Top => rect.bottom() - len,
-            Edge::Bottom => rect.y + len,
-            _ => rect.y + (self.value * rect.height as f32).round() as i32,
+            Edge::Top => f64::from(rect.bottom() - len),
+            Edge::Bottom => f64::from(rect.y + len),
+            _ => f64::from(rect.y) + self.value * f64::from(rect.height),
         }
     }
 
-    pub fn tspan(&self, edge: Edge, rect: Rect) -> Tspan {
-        let x = self.x(edge, rect, Tick::HLEN);
-        let y = self.y(edge, rect, Tick::VLEN);
-        Tspan::new(self.text()).x(x).y(y).dy(0.33)
+    /// Get lines of label text, split on newlines, then sub-text
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.split('\n').chain(self.sub_text.as_deref())
+    }
+
+    /// Get the number of label lines
+    pub fn line_count(&self) -> usize {
+        self.lines().count()
+    }
+
+    /// Get one `Tspan` per line of label text
+    ///
+    /// Lines extend away from a horizontal axis, with sub-text furthest,
+    /// or are centered on the tick of a vertical axis.
+    pub fn tspans(
+        &self,
+        edge: Edge,
+        rect: Rect,
+        style: &TickStyle,
+    ) -> Vec<Tspan<'_>> {
+        let x = self.x(edge, rect, style.hlen());
+        let y = self.y(edge, rect, style.vlen());
+        let text_lines = self.text.split('\n').count() as f32;
+        let count = self.line_count() as f32;
+        self.lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let i = i as f32;
+                let offset = match edge {
+                    Edge::Bottom => i,
+                    Edge::Top if i < text_lines => i + 1.0 - text_lines,
+                    Edge::Top => -text_lines,
+                    _ => i - (count - 1.0) / 2.0,
+                };
+                let dy = ((style.label_dy + offset) * 100.0).round() / 100.0;
+                Tspan::new(line).x(x).y(y).dy(dy)
+            })
+            .collect()
     }
 }
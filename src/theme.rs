@@ -0,0 +1,299 @@
+// theme.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+use crate::palette::Palette;
+use crate::style::{prefix_css, DEFAULT_CSS};
+use std::fmt::Write;
+
+/// Classes of text elements scaled with the theme font size
+///
+/// Sizes are relative to axis names, matching the default stylesheet.
+const FONT_CLASSES: &[(&str, u16)] = &[
+    (".title", 125),
+    (
+        ".subtitle, .tick, .plot-label, .band-label, .annotation",
+        80,
+    ),
+    (".crosshair-label", 80),
+    (".axis", 100),
+];
+
+/// Theme for generating a stylesheet
+///
+/// Rules overriding the default stylesheet cover colors, grid opacity and
+/// fonts for every class used by splot.
+///
+/// ```rust
+/// use splot::{Palette, Theme};
+///
+/// let css = Theme::dark().palette(Palette::colorblind()).css();
+/// let css = Theme::new()
+///     .background("#111")
+///     .series_colors(&["#0af", "#fa0"])
+///     .grid("#333")
+///     .font("Inter", 40)
+///     .css();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    background: String,
+    foreground: String,
+    title: String,
+    tick: String,
+    grid: String,
+    grid_opacity: u8,
+    font: Option<(String, u16)>,
+    palette: Palette,
+    class_prefix: String,
+    dark: Option<Box<Theme>>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Theme {
+    /// Create a new theme, starting from the light preset
+    pub fn new() -> Self {
+        Self::light()
+    }
+
+    /// Light theme
+    pub fn light() -> Self {
+        Theme {
+            background: "#eee".to_string(),
+            foreground: "#333".to_string(),
+            title: "#333".to_string(),
+            tick: "#333".to_string(),
+            grid: "gray".to_string(),
+            grid_opacity: 50,
+            font: None,
+            palette: Palette::default(),
+            class_prefix: String::new(),
+            dark: None,
+        }
+    }
+
+    /// Dark theme
+    ///
+    /// Grid lines are fainter and tick labels brighter than the light
+    /// theme, to keep contrast on a dark background.
+    pub fn dark() -> Self {
+        Theme {
+            background: "#333".to_string(),
+            foreground: "#eee".to_string(),
+            title: "#eee".to_string(),
+            tick: "#fff".to_string(),
+            grid: "gray".to_string(),
+            grid_opacity: 35,
+            font: None,
+            palette: Palette::default(),
+            class_prefix: String::new(),
+            dark: None,
+        }
+    }
+
+    /// High contrast theme
+    pub fn high_contrast() -> Self {
+        Theme {
+            background: "#fff".to_string(),
+            foreground: "#000".to_string(),
+            title: "#000".to_string(),
+            tick: "#000".to_string(),
+            grid: "#000".to_string(),
+            grid_opacity: 100,
+            font: None,
+            palette: Palette::colorblind(),
+            class_prefix: String::new(),
+            dark: None,
+        }
+    }
+
+    /// Set the background color
+    pub fn background(mut self, color: &str) -> Self {
+        self.background = color.to_string();
+        self
+    }
+
+    /// Set the foreground color, used for axes and labels
+    pub fn foreground(mut self, color: &str) -> Self {
+        self.foreground = color.to_string();
+        self
+    }
+
+    /// Set the title color
+    pub fn title(mut self, color: &str) -> Self {
+        self.title = color.to_string();
+        self
+    }
+
+    /// Set the tick label and tick mark color
+    pub fn tick(mut self, color: &str) -> Self {
+        self.tick = color.to_string();
+        self
+    }
+
+    /// Set the grid line color
+    pub fn grid(mut self, color: &str) -> Self {
+        self.grid = color.to_string();
+        self
+    }
+
+    /// Set the grid line opacity, in percent
+    pub fn grid_opacity(mut self, percent: u8) -> Self {
+        self.grid_opacity = percent.min(100);
+        self
+    }
+
+    /// Set the font family and size
+    ///
+    /// The size applies to axis names; other text is scaled relative to
+    /// it, as in the default stylesheet.
+    pub fn font(mut self, family: &str, size: u16) -> Self {
+        self.font = Some((family.to_string(), size));
+        self
+    }
+
+    /// Set the plot color palette
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Set the plot colors
+    pub fn series_colors<C>(self, colors: &[C]) -> Self
+    where
+        C: AsRef<str>,
+    {
+        self.palette(Palette::new(colors))
+    }
+
+    /// Set a prefix for every class selector
+    ///
+    /// This should match `Chart::class_prefix`.
+    pub fn class_prefix(mut self, prefix: &str) -> Self {
+        self.class_prefix = prefix.to_string();
+        self
+    }
+
+    /// Use another theme when the user prefers a dark color scheme
+    ///
+    /// Its rules are wrapped in a `prefers-color-scheme` media query, so
+    /// one stylesheet adapts automatically.
+    pub fn prefers_dark(mut self, dark: Theme) -> Self {
+        self.dark = Some(Box::new(Theme { dark: None, ..dark }));
+        self
+    }
+
+    /// Generate CSS text
+    ///
+    /// This is the default stylesheet, followed by rules overriding it.
+    pub fn css(&self) -> String {
+        let mut css = DEFAULT_CSS.to_string();
+        css.push_str(&self.rules());
+        if let Some(dark) = &self.dark {
+            css.push_str("@media (prefers-color-scheme: dark) {\n");
+            for line in dark.rules().lines() {
+                css.push_str("  ");
+                css.push_str(line);
+                css.push('\n');
+            }
+            css.push_str("}\n");
+        }
+        if self.class_prefix.is_empty() {
+            css
+        } else {
+            prefix_css(&css, &self.class_prefix)
+        }
+    }
+
+    /// Get rules overriding the default stylesheet
+    fn rules(&self) -> String {
+        let mut css = String::new();
+        // writing to a String cannot fail
+        let _ = writeln!(
+            css,
+            "* {{\n  --bg: {};\n  --fg: {};\n}}",
+            self.background, self.foreground
+        );
+        let _ = writeln!(css, ".title {{\n  fill: {};\n}}", self.title);
+        let _ = writeln!(css, ".tick {{\n  fill: {};\n}}", self.tick);
+        let _ = writeln!(css, ".axis-tick {{\n  stroke: {};\n}}", self.tick);
+        let _ = writeln!(
+            css,
+            ".grid-x, .grid-y {{\n  stroke: {};\n  stroke-opacity: {}%;\n}}",
+            self.grid, self.grid_opacity
+        );
+        if let Some((family, size)) = &self.font {
+            let _ = writeln!(
+                css,
+                "svg text {{\n  font-family: {family};\n}}\n\
+                .caption, .legend {{\n  font-family: {family};\n}}"
+            );
+            for (class, pct) in FONT_CLASSES {
+                let px = u32::from(*size) * u32::from(*pct) / 100;
+                let _ = writeln!(css, "{class} {{\n  font-size: {px}px;\n}}");
+            }
+        }
+        for num in 0..10 {
+            if let Some(color) = self.palette.color(num) {
+                let _ =
+                    writeln!(css, ".plot-{num} {{\n  --color: {color};\n}}");
+            }
+        }
+        css
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn css() {
+        let css = Theme::dark().css();
+        assert!(css.starts_with(DEFAULT_CSS));
+        assert!(css.contains("--bg: #333;\n  --fg: #eee;"));
+        assert!(css.contains(".plot-9 {\n  --color: #BAB0AB;\n}"));
+        let css = Theme::light().palette(Palette::colorblind()).css();
+        assert!(css.contains(".plot-0 {\n  --color: #E69F00;\n}"));
+        assert!(css.contains(".plot-8 {\n  --color: #E69F00;\n}"));
+    }
+
+    #[test]
+    fn builder() {
+        let css = Theme::new()
+            .background("#111")
+            .series_colors(&["#0af", "#fa0"])
+            .grid("#333")
+            .grid_opacity(20)
+            .font("Inter", 40)
+            .css();
+        assert!(css.contains("--bg: #111;"));
+        assert!(css.contains(".plot-1 {\n  --color: #fa0;\n}"));
+        assert!(css.contains(".plot-2 {\n  --color: #0af;\n}"));
+        assert!(css.contains("stroke: #333;\n  stroke-opacity: 20%;"));
+        assert!(css.contains("font-family: Inter;"));
+        assert!(css.contains(".title {\n  font-size: 50px;\n}"));
+        assert!(css.contains(".axis {\n  font-size: 40px;\n}"));
+        assert!(!Theme::new().css().contains("svg text"));
+        let css = Theme::new().class_prefix("splot-").css();
+        assert!(css.contains(".splot-plot-0 {\n  --color: #4E79A7;"));
+        assert!(!css.contains(".tick"));
+    }
+
+    #[test]
+    fn prefers_dark() {
+        let css = Theme::high_contrast().prefers_dark(Theme::dark()).css();
+        let (light, dark) = css
+            .rsplit_once("@media (prefers-color-scheme: dark) {\n  * {")
+            .unwrap();
+        assert!(light.contains("--bg: #fff;"));
+        assert!(dark.contains("    --bg: #333;"));
+        assert!(dark.contains("    stroke-opacity: 35%;"));
+        assert!(dark.ends_with("}\n}\n"));
+    }
+}
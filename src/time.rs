@@ -0,0 +1,134 @@
+// time.rs
+//
+// Copyright (c) 2021-2024  Douglas P Lau
+//
+//! Date / time helpers for temporal scales
+//!
+use std::fmt::Write;
+
+/// Abbreviated month names
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+    "Nov", "Dec",
+];
+
+/// Seconds per day
+pub const DAY: i64 = 86_400;
+
+/// Civil date / time (UTC)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+/// Convert days since Unix epoch to (year, month, day)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Convert (year, month, day) to days since Unix epoch
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+impl DateTime {
+    /// Create from a Unix timestamp (seconds)
+    pub fn from_timestamp(ts: i64) -> Self {
+        let (year, month, day) = civil_from_days(ts.div_euclid(DAY));
+        let secs = ts.rem_euclid(DAY);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (secs / 3600) as u32,
+            minute: (secs / 60 % 60) as u32,
+            second: (secs % 60) as u32,
+        }
+    }
+
+    /// Create from a date (at midnight)
+    pub fn from_date(year: i64, month: u32, day: u32) -> Self {
+        DateTime {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0,
+        }
+    }
+
+    /// Get Unix timestamp (seconds)
+    pub fn timestamp(&self) -> i64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        days * DAY
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+    }
+
+    /// Format with a `strftime`-like format string
+    ///
+    /// Supported specifiers: `%Y`, `%m`, `%d`, `%e`, `%b`, `%H`, `%M`, `%S`
+    /// and `%%`.
+    pub fn format(&self, fmt: &str) -> String {
+        let mut out = String::with_capacity(fmt.len() + 8);
+        let mut chars = fmt.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let _ = match chars.next() {
+                Some('Y') => write!(out, "{}", self.year),
+                Some('m') => write!(out, "{:02}", self.month),
+                Some('d') => write!(out, "{:02}", self.day),
+                Some('e') => write!(out, "{}", self.day),
+                Some('b') => write!(out, "{}", MONTHS[self.month as usize - 1]),
+                Some('H') => write!(out, "{:02}", self.hour),
+                Some('M') => write!(out, "{:02}", self.minute),
+                Some('S') => write!(out, "{:02}", self.second),
+                Some('%') => write!(out, "%"),
+                Some(c) => write!(out, "%{c}"),
+                None => write!(out, "%"),
+            };
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil() {
+        let dt = DateTime::from_timestamp(1_717_372_800);
+        assert_eq!(dt, DateTime::from_date(2024, 6, 3));
+        assert_eq!(dt.timestamp(), 1_717_372_800);
+        assert_eq!(dt.format("%b %e, %Y %H:%M"), "Jun 3, 2024 00:00");
+        let dt = DateTime::from_timestamp(-1);
+        assert_eq!(dt.format("%Y-%m-%d %H:%M:%S"), "1969-12-31 23:59:59");
+    }
+}
@@ -3,9 +3,22 @@
 // Copyright (c) 2021-2024  Douglas P Lau
 //
 use crate::rect::{Edge, Rect};
-use crate::text::{Anchor, Text};
+use crate::text::{Anchor, Text, Tspan};
+use std::borrow::Cow;
 use std::fmt;
 
+/// Space reserved for a single-line title
+const TITLE_SPACE: u16 = 100;
+
+/// Height of each additional title line
+const LINE_HEIGHT: u16 = 60;
+
+/// Height of subtitle line
+const SUBTITLE_HEIGHT: u16 = 48;
+
+/// Title font size
+const FONT_SIZE: f32 = 50.0;
+
 /// Chart title
 ///
 /// ```rust
@@ -13,12 +26,17 @@ use std::fmt;
 ///
 /// let title = Title::from("Grand Title");
 /// let left_title = Title::from(("A Title", Edge::Left));
+/// let owned_title = Title::from(format!("Latency on {}", "host"));
+/// let sub_title = Title::new("Requests").with_subtitle("Last 24 hours");
+/// let two_lines = Title::new("A Long Title\nOn Two Lines");
 /// ````
 pub struct Title<'a> {
-    text: &'a str,
+    text: Cow<'a, str>,
+    subtitle: Option<Cow<'a, str>>,
     anchor: Anchor,
     edge: Edge,
     rect: Rect,
+    inline: bool,
 }
 
 impl<'a> From<&'a str> for Title<'a> {
@@ -27,14 +45,21 @@ impl<'a> From<&'a str> for Title<'a> {
     }
 }
 
+impl<'a> From<String> for Title<'a> {
+    fn from(text: String) -> Self {
+        Title::new(text)
+    }
+}
+
 impl<'a> From<(&'a str, Edge)> for Title<'a> {
     fn from((text, edge): (&'a str, Edge)) -> Self {
-        Title {
-            text,
-            anchor: Anchor::Middle,
-            edge,
-            rect: Rect::default(),
-        }
+        Title::new(text).edge(edge)
+    }
+}
+
+impl<'a> From<(String, Edge)> for Title<'a> {
+    fn from((text, edge): (String, Edge)) -> Self {
+        Title::new(text).edge(edge)
     }
 }
 
@@ -43,24 +68,54 @@ impl<'a> fmt::Display for Title<'a> {
         let text = Text::new(self.edge)
             .rect(self.rect)
             .anchor(self.anchor)
-            .class_name("title");
+            .class_name("title")
+            .inline(self.inline);
         text.display(f)?;
-        writeln!(f, "{}", self.text)?;
+        if self.subtitle.is_none() && !self.text.contains('\n') {
+            writeln!(f, "{}", self.text)?;
+        } else {
+            // shift first line up to center all lines within the band
+            let extra = self.space() - TITLE_SPACE;
+            let mut dy = -f32::from(extra) / 2.0 / FONT_SIZE;
+            for line in self.text.split('\n') {
+                write!(f, "{}", Tspan::new(line).x(0).dy(dy))?;
+                dy = f32::from(LINE_HEIGHT) / FONT_SIZE;
+            }
+            if let Some(subtitle) = &self.subtitle {
+                let tspan = Tspan::new(subtitle)
+                    .class_name("subtitle")
+                    .inline(self.inline)
+                    .x(0)
+                    .dy(1.5);
+                write!(f, "{tspan}")?;
+            }
+        }
         text.display_done(f)
     }
 }
 
 impl<'a> Title<'a> {
     /// Create a new title
-    pub fn new(text: &'a str) -> Self {
+    pub fn new<T>(text: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
         Title {
-            text,
+            text: text.into(),
+            subtitle: None,
             anchor: Anchor::Middle,
             edge: Edge::Top,
             rect: Rect::default(),
+            inline: false,
         }
     }
 
+    /// Set the edge
+    fn edge(mut self, edge: Edge) -> Self {
+        self.edge = edge;
+        self
+    }
+
     /// Anchor title text at start
     pub fn at_start(mut self) -> Self {
         self.anchor = Anchor::Start;
@@ -73,9 +128,68 @@ impl<'a> Title<'a> {
         self
     }
 
+    /// Get the title text, with lines joined by spaces
+    pub(crate) fn text(&self) -> String {
+        self.text.replace('\n', " ")
+    }
+
+    /// Add a smaller subtitle below the title
+    pub fn with_subtitle<S>(mut self, subtitle: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Use inline presentational attributes
+    pub(crate) fn set_inline(&mut self) {
+        self.inline = true;
+    }
+
+    /// Get the space required
+    fn space(&self) -> u16 {
+        let lines = self.text.split('\n').count() as u16;
+        let mut space = TITLE_SPACE + LINE_HEIGHT * (lines - 1);
+        if self.subtitle.is_some() {
+            space += SUBTITLE_HEIGHT;
+        }
+        space
+    }
+
     /// Split title area from rectangle
     pub(crate) fn split(&mut self, mut area: Rect) -> Rect {
-        (area, self.rect) = area.split(self.edge, 100);
+        (area, self.rect) = area.split(self.edge, self.space());
         area
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subtitle() {
+        let mut title = Title::new("Requests").with_subtitle("us-east-1");
+        let area = title.split(Rect::new(0, 0, 2000, 1500));
+        assert_eq!(area.y, 148);
+        let svg = title.to_string();
+        assert!(svg.contains("<tspan x='0' dy='-0.48em'>Requests</tspan>"));
+        assert!(svg.contains(
+            "<tspan class='subtitle' x='0' dy='1.5em'>us-east-1</tspan>"
+        ));
+    }
+
+    #[test]
+    fn lines() {
+        let mut title = Title::new("A Long Title\nOn Two Lines");
+        let area = title.split(Rect::new(0, 0, 2000, 1500));
+        assert_eq!(area.y, 160);
+        let svg = title.to_string();
+        assert!(svg.contains("<tspan x='0' dy='-0.6em'>A Long Title</tspan>"));
+        assert!(svg.contains("<tspan x='0' dy='1.2em'>On Two Lines</tspan>"));
+        let mut title = Title::new("Simple");
+        title.split(Rect::new(0, 0, 2000, 1500));
+        assert!(!title.to_string().contains("tspan"));
+    }
+}